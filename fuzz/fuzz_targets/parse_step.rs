@@ -0,0 +1,16 @@
+//! Parses arbitrary bytes as a STEP file; any panic is a bug.
+//!
+//! Run with `cargo +nightly fuzz run parse_step` from the repository root.
+
+#![no_main]
+
+use ifc_inspector::parser::StepFile;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        let _ = StepFile::parse(content);
+    }
+    // The reader path also decodes text that is not UTF-8
+    let _ = StepFile::from_reader(data);
+});
@@ -13,9 +13,48 @@ pub enum ParseError {
         source: std::io::Error,
     },
 
+    /// Failed to read STEP data from a stream.
+    #[error("failed to read STEP data: {source}")]
+    Read { source: std::io::Error },
+
+    /// Failed to read a zip archive (`.ifczip`).
+    #[error("failed to read archive '{path}': {source}")]
+    Archive {
+        path: PathBuf,
+        source: zip::result::ZipError,
+    },
+
+    /// The requested model is not in the archive.
+    #[error("no model named '{name}' in archive '{path}'")]
+    ModelNotFound { path: PathBuf, name: String },
+
+    /// The archive contains no `.ifc` or `.ifcxml` model.
+    #[error("archive '{path}' contains no .ifc or .ifcxml model")]
+    EmptyArchive { path: PathBuf },
+
     /// The STEP format is invalid or malformed.
     #[error("invalid STEP format: {message}")]
     InvalidStep { message: String },
+
+    /// The file cannot be memory-mapped (it is compressed or ifcXML).
+    #[error("cannot memory-map '{path}': only uncompressed STEP files can be mapped")]
+    NotMappable { path: PathBuf },
+
+    /// The input exceeds one of the configured [`Limits`](crate::parser::Limits).
+    #[error("limit exceeded: {message}")]
+    LimitExceeded { message: String },
+
+    /// The parse was stopped through its [`CancellationToken`](crate::parser::CancellationToken).
+    #[error("parsing was cancelled")]
+    Cancelled,
+
+    /// The ifcXML document is not well-formed XML.
+    #[error("invalid ifcXML: {message}")]
+    InvalidXml { message: String },
+
+    /// The EXPRESS schema file could not be parsed.
+    #[error("invalid EXPRESS schema at line {line}: {message}")]
+    InvalidExpress { line: usize, message: String },
 }
 
 /// Errors that can occur when exporting data.
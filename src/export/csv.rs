@@ -1,8 +1,16 @@
 use crate::error::ExportError;
-use crate::model::IfcProject;
+use crate::model::{ElementType, IfcProject, SpatialKind, SpatialNode};
+use std::collections::HashMap;
 use std::fs::File;
 use std::path::Path;
 
+/// Export the type summary: one row per type, storey and space it has
+/// instances in, storeys grouped by building as in `IfcProject::storeys`.
+/// Instances directly in a building get an empty Storey, those in neither
+/// an empty Building and Storey, those in no space an empty Space. Instances
+/// in or bounding several spaces get a row of their own with the space
+/// numbers separated by `; `, so the counts of a type add up to its
+/// instance count.
 pub fn export_csv<P: AsRef<Path>>(project: &IfcProject, path: P) -> Result<(), ExportError> {
     let path_ref = path.as_ref();
     let file = File::create(path_ref).map_err(|source| ExportError::FileCreate {
@@ -12,16 +20,30 @@ pub fn export_csv<P: AsRef<Path>>(project: &IfcProject, path: P) -> Result<(), E
 
     let mut writer = csv::Writer::from_writer(file);
 
-    writer.write_record(["Category", "Type Name", "Instance Count", "Global ID"])?;
+    writer.write_record([
+        "Category",
+        "Type Name",
+        "Instance Count",
+        "Global ID",
+        "Building",
+        "Storey",
+        "Space",
+    ])?;
 
+    let locations = Locations::new(project);
     for category in &project.categories {
         for element_type in &category.types {
-            writer.write_record([
-                &category.name,
-                &element_type.name,
-                &element_type.instance_count.to_string(),
-                &element_type.global_id,
-            ])?;
+            for (building, storey, spaces, count) in locations.counts(element_type) {
+                writer.write_record([
+                    category.name.as_str(),
+                    &element_type.name,
+                    &count.to_string(),
+                    &element_type.global_id,
+                    building,
+                    storey,
+                    &spaces,
+                ])?;
+            }
         }
     }
 
@@ -31,3 +53,172 @@ pub fn export_csv<P: AsRef<Path>>(project: &IfcProject, path: P) -> Result<(), E
 
     Ok(())
 }
+
+/// Lookups shared by the rows of every type, built once per export.
+struct Locations<'a> {
+    project: &'a IfcProject,
+    // Storey id → (rank in `IfcProject::storeys`, building name)
+    storeys: HashMap<u64, (usize, &'a str)>,
+    space_rank: HashMap<u64, usize>,
+    // Building id → name, for instances contained in a building directly
+    buildings: HashMap<u64, &'a str>,
+}
+
+impl<'a> Locations<'a> {
+    fn new(project: &'a IfcProject) -> Self {
+        let mut buildings = HashMap::new();
+        let mut nodes: Vec<&SpatialNode> = project.spatial.iter().collect();
+        while let Some(node) = nodes.pop() {
+            if node.kind == SpatialKind::Building {
+                buildings.insert(node.id, node.name.as_str());
+            }
+            nodes.extend(&node.children);
+        }
+        let storeys = project
+            .storeys
+            .iter()
+            .enumerate()
+            .map(|(rank, storey)| {
+                let building = storey
+                    .building_id
+                    .and_then(|id| buildings.get(&id).copied())
+                    .unwrap_or("");
+                (storey.id, (rank, building))
+            })
+            .collect();
+        let space_rank = project
+            .spaces
+            .iter()
+            .enumerate()
+            .map(|(rank, space)| (space.id, rank))
+            .collect();
+        Self {
+            project,
+            storeys,
+            space_rank,
+            buildings,
+        }
+    }
+
+    /// Instances of a type per location as (building, storey, spaces, count):
+    /// storeys in order, then buildings the instances are contained in
+    /// directly, then neither; within each, by spaces in `IfcProject::spaces`
+    /// order and instances in no space last. An instance in or bounding
+    /// several spaces is counted once, in a row that lists all of them. A
+    /// type without instances gets a single row.
+    fn counts(&self, element_type: &ElementType) -> Vec<(&'a str, &'a str, String, usize)> {
+        let project = self.project;
+        let mut counts: HashMap<(Option<u64>, Vec<usize>), usize> = HashMap::new();
+        for id in &element_type.instance_ids {
+            // Contained in a site or other structure above the buildings: no location
+            let storey_id = project
+                .element_to_storey
+                .get(id)
+                .copied()
+                .filter(|id| self.storeys.contains_key(id) || self.buildings.contains_key(id));
+            let mut spaces: Vec<usize> = project
+                .element_to_spaces
+                .get(id)
+                .into_iter()
+                .flatten()
+                .filter_map(|space_id| self.space_rank.get(space_id).copied())
+                .collect();
+            spaces.sort_unstable();
+            spaces.dedup();
+            *counts.entry((storey_id, spaces)).or_insert(0) += 1;
+        }
+        if counts.is_empty() {
+            return vec![("", "", String::new(), 0)];
+        }
+
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_by(|((a_storey, a_spaces), _), ((b_storey, b_spaces), _)| {
+            let storey = |storey_id: &Option<u64>| match storey_id {
+                Some(id) => self
+                    .storeys
+                    .get(id)
+                    .map_or((1, *id), |&(rank, _)| (0, rank as u64)),
+                None => (2, 0),
+            };
+            storey(a_storey)
+                .cmp(&storey(b_storey))
+                .then_with(|| a_spaces.is_empty().cmp(&b_spaces.is_empty()))
+                .then_with(|| a_spaces.cmp(b_spaces))
+        });
+        counts
+            .into_iter()
+            .map(|((storey_id, spaces), count)| {
+                let (building_name, storey_name) = match storey_id {
+                    Some(id) => match self.storeys.get(&id) {
+                        Some(&(rank, building)) => (building, project.storeys[rank].name.as_str()),
+                        None => (self.buildings.get(&id).copied().unwrap_or(""), ""),
+                    },
+                    None => ("", ""),
+                };
+                let space_names: Vec<&str> = spaces
+                    .iter()
+                    .map(|&rank| project.spaces[rank].number.as_str())
+                    .collect();
+                (building_name, storey_name, space_names.join("; "), count)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_ifc_file;
+    use pretty_assertions::assert_eq;
+
+    const MODEL: &str = "ISO-10303-21;HEADER;FILE_SCHEMA(('IFC4'));ENDSEC;DATA;
+#1=IFCPROJECT('p',$,'Project',$,$,$,$,$,$);
+#2=IFCBUILDING('b',$,'Main',$,$,$,$,$,.ELEMENT.,$,$,$);
+#3=IFCBUILDINGSTOREY('s0',$,'Level 0',$,$,$,$,$,.ELEMENT.,0.);
+#4=IFCBUILDINGSTOREY('s1',$,'Level 1',$,$,$,$,$,.ELEMENT.,3000.);
+#5=IFCRELAGGREGATES('a1',$,$,$,#1,(#2));
+#6=IFCRELAGGREGATES('a2',$,$,$,#2,(#3,#4));
+#7=IFCSPACE('sp1',$,'101',$,$,$,$,$,.ELEMENT.,.INTERNAL.,$);
+#8=IFCSPACE('sp2',$,'102',$,$,$,$,$,.ELEMENT.,.INTERNAL.,$);
+#9=IFCRELAGGREGATES('a3',$,$,$,#4,(#7,#8));
+#10=IFCWALLTYPE('wt',$,'Basic Wall',$,$,$,$,$,$,.STANDARD.);
+#11=IFCWALL('w1',$,'W1',$,$,$,$,$,$);
+#12=IFCWALL('w2',$,'W2',$,$,$,$,$,$);
+#13=IFCWALL('w3',$,'W3',$,$,$,$,$,$);
+#14=IFCWALL('w4',$,'W4',$,$,$,$,$,$);
+#15=IFCWALL('w5',$,'W5',$,$,$,$,$,$);
+#16=IFCRELDEFINESBYTYPE('t',$,$,$,(#11,#12,#13,#14,#15),#10);
+#17=IFCRELCONTAINEDINSPATIALSTRUCTURE('c0',$,$,$,(#11),#3);
+#18=IFCRELCONTAINEDINSPATIALSTRUCTURE('c1',$,$,$,(#12,#13,#14),#4);
+#19=IFCRELCONTAINEDINSPATIALSTRUCTURE('c2',$,$,$,(#15),#2);
+#20=IFCRELSPACEBOUNDARY('sb1',$,$,$,#7,#12,$,.PHYSICAL.,.INTERNAL.);
+#21=IFCRELSPACEBOUNDARY('sb2',$,$,$,#8,#12,$,.PHYSICAL.,.INTERNAL.);
+#22=IFCRELSPACEBOUNDARY('sb3',$,$,$,#8,#13,$,.PHYSICAL.,.INTERNAL.);
+#23=IFCRELSPACEBOUNDARY('sb4',$,$,$,#7,#14,$,.PHYSICAL.,.INTERNAL.);
+ENDSEC;END-ISO-10303-21;";
+
+    #[test]
+    fn counts_each_instance_once() {
+        let dir = std::env::temp_dir();
+        let model = dir.join(format!("ifc-inspector-{}-csv.ifc", std::process::id()));
+        let csv = dir.join(format!("ifc-inspector-{}-csv.csv", std::process::id()));
+        std::fs::write(&model, MODEL).unwrap();
+        let project = parse_ifc_file(&model).unwrap();
+        export_csv(&project, &csv).unwrap();
+        let written = std::fs::read_to_string(&csv).unwrap();
+        let _ = std::fs::remove_file(model);
+        let _ = std::fs::remove_file(csv);
+
+        assert_eq!(
+            written.lines().collect::<Vec<_>>(),
+            vec![
+                "Category,Type Name,Instance Count,Global ID,Building,Storey,Space",
+                "Walls,Basic Wall,1,wt,Main,Level 1,101",
+                "Walls,Basic Wall,1,wt,Main,Level 1,101; 102",
+                "Walls,Basic Wall,1,wt,Main,Level 1,102",
+                "Walls,Basic Wall,1,wt,Main,Level 0,",
+                "Walls,Basic Wall,1,wt,Main,,",
+            ]
+        );
+    }
+}
@@ -1,6 +1,8 @@
 pub mod csv;
 pub mod json;
+pub mod step;
 
 pub use crate::error::ExportError;
 pub use csv::export_csv;
 pub use json::export_json;
+pub use step::export_step;
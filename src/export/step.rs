@@ -0,0 +1,20 @@
+use crate::error::ExportError;
+use crate::parser::StepFile;
+use std::fs::File;
+use std::path::Path;
+
+pub fn export_step<P: AsRef<Path>>(step_file: &StepFile, path: P) -> Result<(), ExportError> {
+    let path_ref = path.as_ref();
+    let file = File::create(path_ref).map_err(|source| ExportError::FileCreate {
+        path: path_ref.to_path_buf(),
+        source,
+    })?;
+
+    step_file
+        .write_to(file)
+        .map_err(|e| ExportError::WriteError {
+            message: e.to_string(),
+        })?;
+
+    Ok(())
+}
@@ -23,4 +23,5 @@ pub mod error;
 pub mod export;
 pub mod model;
 pub mod parser;
+pub mod schema;
 pub mod ui;
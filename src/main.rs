@@ -1,20 +1,33 @@
-use clap::Parser;
+use clap::{ArgGroup, Parser};
+use color_eyre::eyre::bail;
 use color_eyre::Result;
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
+use ifc_inspector::error::ParseError;
 use ifc_inspector::export::{export_csv, export_json};
-use ifc_inspector::parser::parse_ifc_file;
+use ifc_inspector::model::{IfcProject, Space, Storey};
+use ifc_inspector::parser::{
+    parse_ifc_model_with_progress, validate_ifc_model, CancellationToken, FallbackEncoding,
+    ParseOptions, Progress,
+};
+use ifc_inspector::schema::ExpressSchema;
 use ifc_inspector::ui::App;
 
 #[derive(Parser, Debug)]
 #[command(name = "ifc-inspector")]
 #[command(about = "IFC Inspector - browse families and types from IFC files")]
 #[command(version)]
+#[command(group(ArgGroup::new("export").args(["csv", "json"]).multiple(true)))]
 struct Args {
-    /// Path to IFC file
+    /// Path to IFC file (.ifc, .ifcxml, .ifczip or gzip-compressed)
     #[arg(required = true)]
     file: PathBuf,
 
+    /// Model to open from an .ifczip archive (default: the first one)
+    #[arg(long, value_name = "NAME")]
+    model: Option<String>,
+
     /// Export to CSV (optional output path)
     #[arg(long, value_name = "FILE")]
     csv: Option<PathBuf>,
@@ -22,13 +35,131 @@ struct Args {
     /// Export to JSON (optional output path)
     #[arg(long, value_name = "FILE")]
     json: Option<PathBuf>,
+
+    /// Export only the instances on this storey; "BUILDING/STOREY" picks
+    /// one of several storeys with the same name
+    #[arg(long, value_name = "NAME", requires = "export")]
+    level: Option<String>,
+
+    /// Export only the instances in or bounding the space with this number
+    #[arg(long, value_name = "NUMBER", requires = "export")]
+    space: Option<String>,
+
+    /// Fail on the first malformed entity instead of skipping it
+    #[arg(long)]
+    strict: bool,
+
+    /// Encoding for text that is not valid UTF-8 (windows-1252, windows-1250,
+    /// windows-1251, windows-1253, iso-8859-1 or lossy)
+    #[arg(long, value_name = "NAME", default_value_t = FallbackEncoding::default())]
+    encoding: FallbackEncoding,
+
+    /// Memory-map the file and parse entities on demand (uncompressed STEP
+    /// only; lowers peak memory for very large models)
+    #[arg(long)]
+    mmap: bool,
+
+    /// Check every entity against an EXPRESS schema (.exp), print the
+    /// violations and exit with status 1 if there are any
+    #[arg(long, value_name = "SCHEMA")]
+    validate: Option<PathBuf>,
 }
 
 fn main() -> Result<()> {
     color_eyre::install()?;
     let args = Args::parse();
 
-    let project = parse_ifc_file(&args.file)?;
+    let options = ParseOptions {
+        strict: args.strict,
+        encoding: args.encoding,
+        memory_map: args.mmap,
+        ..ParseOptions::default()
+    };
+
+    if let Some(schema_path) = &args.validate {
+        return validate(&args, schema_path, options);
+    }
+
+    // Ctrl-C stops the parse; once it is done, a Ctrl-C exits right away
+    let cancel = CancellationToken::new();
+    let handle = cancel.clone();
+    ctrlc::set_handler(move || {
+        if handle.is_cancelled() {
+            std::process::exit(130);
+        }
+        handle.cancel();
+    })?;
+
+    // Progress bar on stderr, unless it is redirected
+    let show_progress = std::io::stderr().is_terminal();
+    let project = parse_ifc_model_with_progress(
+        &args.file,
+        args.model.as_deref(),
+        options,
+        |progress| {
+            if show_progress {
+                print_progress(progress);
+            }
+        },
+        &cancel,
+    );
+    // Nothing checks the token any more: let the handler exit instead
+    cancel.cancel();
+    if show_progress {
+        eprint!("\r\x1b[2K");
+    }
+    let mut project = match project {
+        Err(ParseError::Cancelled) => std::process::exit(130),
+        project => project?,
+    };
+
+    if project.archive_models.len() > 1 {
+        eprintln!(
+            "Archive contains {} models (use --model to open another):",
+            project.archive_models.len()
+        );
+        for model in &project.archive_models {
+            let marker = if project.model.as_ref() == Some(model) {
+                "*"
+            } else {
+                " "
+            };
+            eprintln!("  {marker} {model}");
+        }
+    }
+
+    if !project.diagnostics.is_empty() {
+        // Duplicate IDs and text decoded with --encoding keep their entities
+        if project.skipped_entities > 0 {
+            eprintln!(
+                "Warning: skipped {} entities ({} parse diagnostics)",
+                project.skipped_entities,
+                project.diagnostics.len()
+            );
+        } else {
+            eprintln!("Warning: {} parse diagnostics", project.diagnostics.len());
+        }
+        for diagnostic in project.diagnostics.iter().take(10) {
+            eprintln!("  {diagnostic}");
+        }
+        if project.diagnostics.len() > 10 {
+            eprintln!("  ... and {} more", project.diagnostics.len() - 10);
+        }
+    }
+
+    if args.level.is_some() || args.space.is_some() {
+        let storey_id = args
+            .level
+            .as_deref()
+            .map(|level| find_storey(&project, level))
+            .transpose()?;
+        let space_id = args
+            .space
+            .as_deref()
+            .map(|number| find_space(&project, number, storey_id))
+            .transpose()?;
+        project.retain_instances(storey_id, space_id);
+    }
 
     if let Some(csv_path) = &args.csv {
         export_csv(&project, csv_path)?;
@@ -49,3 +180,93 @@ fn main() -> Result<()> {
     ratatui::restore();
     result
 }
+
+/// The storey named `level`, or `BUILDING/STOREY`.
+fn find_storey(project: &IfcProject, level: &str) -> Result<u64> {
+    let building_name = |storey: &Storey| {
+        storey
+            .building_id
+            .and_then(|id| project.spatial_node(id))
+            .map_or("", |b| b.name.as_str())
+    };
+    let matches: Vec<&Storey> = project
+        .storeys
+        .iter()
+        .filter(|storey| {
+            storey.name == level
+                || level.split_once('/').is_some_and(|(building, name)| {
+                    storey.name == name && building_name(storey) == building
+                })
+        })
+        .collect();
+    match matches[..] {
+        [storey] => Ok(storey.id),
+        [] => bail!("no storey named {level:?}"),
+        _ => bail!(
+            "{} storeys are named {level:?}, use one of: {}",
+            matches.len(),
+            matches
+                .iter()
+                .map(|storey| format!("{}/{}", building_name(storey), storey.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// The space numbered `number`, on the storey `storey_id` if given.
+fn find_space(project: &IfcProject, number: &str, storey_id: Option<u64>) -> Result<u64> {
+    let matches: Vec<&Space> = project
+        .spaces
+        .iter()
+        .filter(|space| space.number == number)
+        .filter(|space| storey_id.is_none() || space.storey_id == storey_id)
+        .collect();
+    match matches[..] {
+        [space] => Ok(space.id),
+        [] => bail!("no space numbered {number:?}"),
+        _ => bail!(
+            "{} spaces are numbered {number:?}, pick the storey with --level",
+            matches.len()
+        ),
+    }
+}
+
+/// Print the schema violations of the model and exit with status 1 if
+/// there are any.
+fn validate(args: &Args, schema_path: &Path, options: ParseOptions) -> Result<()> {
+    let schema = ExpressSchema::from_file(schema_path)?;
+    let violations = validate_ifc_model(&args.file, args.model.as_deref(), options, &schema)?;
+    for violation in &violations {
+        println!("{violation}");
+    }
+    if violations.is_empty() {
+        println!("{}: conforms to {}", args.file.display(), schema.name);
+        Ok(())
+    } else {
+        eprintln!(
+            "{}: {} violations of {}",
+            args.file.display(),
+            violations.len(),
+            schema.name
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Redraw the progress line: bar, percentage of the file read, entity count
+/// and the current phase.
+fn print_progress(progress: Progress) {
+    const WIDTH: usize = 30;
+    let filled = (progress.bytes_read.min(progress.total_bytes) * WIDTH as u64)
+        .checked_div(progress.total_bytes)
+        .map_or(WIDTH, |filled| filled as usize);
+    eprint!(
+        "\r\x1b[2K[{}{}] {:>3.0}%  {} entities  {}",
+        "#".repeat(filled),
+        "-".repeat(WIDTH - filled),
+        progress.fraction() * 100.0,
+        progress.entities,
+        progress.phase
+    );
+}
@@ -9,5 +9,6 @@ pub struct Element {
     pub tag: Option<String>,
     pub type_id: Option<u64>,
     pub storey_id: Option<u64>,
+    #[serde(serialize_with = "super::sorted::sorted")]
     pub properties: HashMap<String, String>,
 }
@@ -9,5 +9,7 @@ pub struct ElementType {
     pub category: String,
     pub instance_count: usize,
     pub instance_ids: Vec<u64>,
+    #[serde(serialize_with = "super::sorted::sorted")]
     pub properties: HashMap<String, String>,
+    pub untyped: bool, // synthetic entry for instances without a type object (id 0)
 }
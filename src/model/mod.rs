@@ -1,7 +1,10 @@
 pub mod element;
 pub mod element_type;
 pub mod project;
+mod sorted;
+pub mod spatial;
 
 pub use element::Element;
 pub use element_type::ElementType;
-pub use project::{Category, IfcProject, Storey};
+pub use project::{Category, Discipline, IfcProject, Space, Storey};
+pub use spatial::{SpatialKind, SpatialNode};
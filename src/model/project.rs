@@ -1,10 +1,38 @@
-use super::{Element, ElementType};
+use super::{Element, ElementType, SpatialNode};
+use crate::parser::{Diagnostic, StepHeader};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Discipline a category belongs to; categories are grouped in this order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum Discipline {
+    Architecture,
+    Structure,
+    #[serde(rename = "MEP")]
+    Mep,
+    /// Infrastructure elements of IFC4x3: pavements, tracks, earthworks, ...
+    Civil,
+    /// Proxies, assemblies, openings and other non-physical elements
+    Other,
+}
+
+impl fmt::Display for Discipline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Architecture => "Architecture",
+            Self::Structure => "Structure",
+            Self::Mep => "MEP",
+            Self::Civil => "Civil",
+            Self::Other => "Other",
+        })
+    }
+}
 
 #[derive(Debug, Clone, Serialize)]
 pub struct Category {
     pub name: String,
+    pub discipline: Discipline,
     pub is_priority: bool,
     pub types: Vec<ElementType>,
     pub total_count: usize,
@@ -15,11 +43,26 @@ pub struct IfcProject {
     pub name: String,
     pub schema: String,
     pub file_path: String,
+    pub model: Option<String>,       // model opened from an .ifczip archive
+    pub archive_models: Vec<String>, // all models in the archive
+    pub header: StepHeader,
+    pub diagnostics: Vec<Diagnostic>,
+    pub skipped_entities: usize, // entities dropped because they could not be parsed
     pub categories: Vec<Category>,
-    pub storeys: Vec<Storey>,
+    pub storeys: Vec<Storey>, // grouped by building, highest first within each
+    pub spatial: Vec<SpatialNode>, // roots of the spatial structure, normally the IfcProject
+    pub spaces: Vec<Space>,   // grouped by storey, then by number
+    #[serde(serialize_with = "super::sorted::sorted")]
     pub elements: HashMap<u64, Element>,
+    #[serde(serialize_with = "super::sorted::sorted")]
     pub element_to_storey: HashMap<u64, u64>, // element_id → storey_id
+    #[serde(serialize_with = "super::sorted::sorted")]
+    pub element_to_spaces: HashMap<u64, Vec<u64>>, // element_id → spaces it is in or bounds
+    #[serde(serialize_with = "super::sorted::sorted_nested")]
     pub element_properties: HashMap<u64, HashMap<String, String>>, // instance_id → properties
+    #[serde(serialize_with = "super::sorted::sorted_nested")]
+    pub property_measures: HashMap<u64, HashMap<String, String>>, // instance_id → property → measure type
+    #[serde(serialize_with = "super::sorted::sorted")]
     pub instance_global_ids: HashMap<u64, String>, // instance_id → GlobalId
 }
 
@@ -29,6 +72,19 @@ pub struct Storey {
     pub name: String,
     pub elevation: f64,
     pub element_count: usize,
+    pub building_id: Option<u64>, // enclosing building (or IFC4x3 facility)
+}
+
+/// A room or other `IfcSpace`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Space {
+    pub id: u64,
+    pub number: String,            // Name, by convention the room number
+    pub long_name: Option<String>, // e.g. "Office"
+    pub area: Option<f64>,         // net (else gross) floor area
+    pub volume: Option<f64>,       // net (else gross) volume
+    pub storey_id: Option<u64>,
+    pub element_count: usize, // elements contained in or bounding the space
 }
 
 impl IfcProject {
@@ -38,11 +94,20 @@ impl IfcProject {
             name,
             schema,
             file_path,
+            model: None,
+            archive_models: Vec::new(),
+            header: StepHeader::default(),
+            diagnostics: Vec::new(),
+            skipped_entities: 0,
             categories: Vec::new(),
             storeys: Vec::new(),
+            spatial: Vec::new(),
+            spaces: Vec::new(),
             elements: HashMap::new(),
             element_to_storey: HashMap::new(),
+            element_to_spaces: HashMap::new(),
             element_properties: HashMap::new(),
+            property_measures: HashMap::new(),
             instance_global_ids: HashMap::new(),
         }
     }
@@ -52,8 +117,71 @@ impl IfcProject {
         self.categories.iter().map(|c| c.total_count).sum()
     }
 
+    /// The spatial structure node with `id` (site, building, storey, ...).
+    #[must_use]
+    pub fn spatial_node(&self, id: u64) -> Option<&SpatialNode> {
+        self.spatial.iter().find_map(|root| root.find(id))
+    }
+
+    /// Whether an instance is on the storey `storey_id` and in (or bounding)
+    /// the space `space_id`; `None` matches any storey or space.
+    #[must_use]
+    pub fn instance_matches(
+        &self,
+        instance_id: u64,
+        storey_id: Option<u64>,
+        space_id: Option<u64>,
+    ) -> bool {
+        let on_storey = storey_id
+            .is_none_or(|storey_id| self.element_to_storey.get(&instance_id) == Some(&storey_id));
+        let in_space = space_id.is_none_or(|space_id| {
+            self.element_to_spaces
+                .get(&instance_id)
+                .is_some_and(|spaces| spaces.contains(&space_id))
+        });
+        on_storey && in_space
+    }
+
+    /// Keep only the instances that match `storey_id` and `space_id` (see
+    /// [`Self::instance_matches`]), recounting types and categories and
+    /// dropping those left empty. The per-instance maps are narrowed to the
+    /// remaining instances (and the spaces); storeys and spaces are kept.
+    pub fn retain_instances(&mut self, storey_id: Option<u64>, space_id: Option<u64>) {
+        let mut categories = std::mem::take(&mut self.categories);
+        for category in &mut categories {
+            for element_type in &mut category.types {
+                element_type
+                    .instance_ids
+                    .retain(|&id| self.instance_matches(id, storey_id, space_id));
+                element_type.instance_count = element_type.instance_ids.len();
+            }
+            category.types.retain(|t| t.instance_count > 0);
+            category.total_count = category.types.iter().map(|t| t.instance_count).sum();
+        }
+        categories.retain(|c| !c.types.is_empty());
+        self.categories = categories;
+
+        let kept: HashSet<u64> = self
+            .categories
+            .iter()
+            .flat_map(|c| &c.types)
+            .flat_map(|t| t.instance_ids.iter().copied())
+            .chain(self.spaces.iter().map(|s| s.id))
+            .collect();
+        self.element_to_storey.retain(|id, _| kept.contains(id));
+        self.element_to_spaces.retain(|id, _| kept.contains(id));
+        self.element_properties.retain(|id, _| kept.contains(id));
+        self.property_measures.retain(|id, _| kept.contains(id));
+        self.instance_global_ids.retain(|id, _| kept.contains(id));
+    }
+
+    /// Number of type objects; the "(untyped)" entries are not counted.
     #[must_use]
     pub fn total_types(&self) -> usize {
-        self.categories.iter().map(|c| c.types.len()).sum()
+        self.categories
+            .iter()
+            .flat_map(|c| &c.types)
+            .filter(|t| !t.untyped)
+            .count()
     }
 }
@@ -0,0 +1,40 @@
+//! Serialize `HashMap` fields with their keys in ascending order, so that
+//! exports are byte-for-byte identical from run to run.
+
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+
+pub(crate) fn sorted<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: Ord + Serialize,
+    V: Serialize,
+    S: Serializer,
+{
+    let mut entries: Vec<(&K, &V)> = map.iter().collect();
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    serializer.collect_map(entries)
+}
+
+/// Like [`sorted`], for maps whose values are maps themselves.
+pub(crate) fn sorted_nested<K, K2, V, S>(
+    map: &HashMap<K, HashMap<K2, V>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    K: Ord + Serialize,
+    K2: Ord + Serialize,
+    V: Serialize,
+    S: Serializer,
+{
+    let mut entries: Vec<(&K, Sorted<K2, V>)> = map.iter().map(|(k, v)| (k, Sorted(v))).collect();
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    serializer.collect_map(entries)
+}
+
+struct Sorted<'a, K, V>(&'a HashMap<K, V>);
+
+impl<K: Ord + Serialize, V: Serialize> Serialize for Sorted<'_, K, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        sorted(self.0, serializer)
+    }
+}
@@ -0,0 +1,64 @@
+use serde::Serialize;
+
+/// Level of the spatial structure a node stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SpatialKind {
+    Project,
+    Site,
+    /// `IfcBuilding`, or another `IfcFacility` (bridge, road, ...) in IFC4x3
+    Building,
+    Storey,
+    Space,
+    /// Any other spatial structure element, e.g. an IFC4x3 `IfcFacilityPart`
+    Other,
+}
+
+/// A node of the spatial structure: the project, its sites, buildings,
+/// storeys and spaces, nested as declared by `IfcRelAggregates`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpatialNode {
+    pub id: u64,
+    pub entity_type: String,
+    pub kind: SpatialKind,
+    pub name: String,
+    pub elevation: Option<f64>, // storeys only
+    pub element_count: usize,   // elements contained directly in this node
+    pub children: Vec<SpatialNode>,
+}
+
+impl SpatialNode {
+    /// The node with `id` in this subtree.
+    #[must_use]
+    pub fn find(&self, id: u64) -> Option<&SpatialNode> {
+        if self.id == id {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(id))
+    }
+
+    /// This node and all nodes below it, depth-first in tree order.
+    #[must_use]
+    pub fn descendants(&self) -> Vec<&SpatialNode> {
+        let mut nodes = Vec::new();
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            nodes.push(node);
+            stack.extend(node.children.iter().rev());
+        }
+        nodes
+    }
+
+    /// The nodes from this one down to `id`, both included.
+    #[must_use]
+    pub fn path_to(&self, id: u64) -> Option<Vec<&SpatialNode>> {
+        if self.id == id {
+            return Some(vec![self]);
+        }
+        self.children.iter().find_map(|child| {
+            child.path_to(id).map(|mut path| {
+                path.insert(0, self);
+                path
+            })
+        })
+    }
+}
@@ -0,0 +1,159 @@
+//! Transparent decompression of `.ifczip` archives and gzip-compressed files.
+//!
+//! The container is detected from the first bytes of the file, not from its
+//! extension, so an `.ifc.gz` or an `.ifczip` renamed to `.ifc` opens the same
+//! way as a plain STEP file.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek};
+use std::path::Path;
+use std::sync::atomic::AtomicU64;
+
+use flate2::bufread::MultiGzDecoder;
+use zip::ZipArchive;
+
+use crate::error::ParseError;
+use crate::parser::progress::CountingReader;
+
+const GZIP_MAGIC: &[u8] = &[0x1F, 0x8B];
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+const ZIP_EMPTY_MAGIC: &[u8] = b"PK\x05\x06";
+
+/// File extensions (lowercase) of models inside an archive.
+const MODEL_EXTENSIONS: &[&str] = &[".ifc", ".ifcxml"];
+
+/// How the model bytes are stored on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Container {
+    Plain,
+    Gzip,
+    Zip,
+}
+
+impl Container {
+    /// Detect the container from the first bytes of a file.
+    ///
+    /// ```
+    /// use ifc_inspector::parser::Container;
+    ///
+    /// assert_eq!(Container::detect(b"ISO-10303-21;"), Container::Plain);
+    /// assert_eq!(Container::detect(&[0x1F, 0x8B, 0x08]), Container::Gzip);
+    /// assert_eq!(Container::detect(b"PK\x03\x04"), Container::Zip);
+    /// ```
+    #[must_use]
+    pub fn detect(magic: &[u8]) -> Self {
+        if magic.starts_with(GZIP_MAGIC) {
+            Self::Gzip
+        } else if magic.starts_with(ZIP_MAGIC) || magic.starts_with(ZIP_EMPTY_MAGIC) {
+            Self::Zip
+        } else {
+            Self::Plain
+        }
+    }
+}
+
+/// Names of the models (`.ifc`, `.ifcxml`) in a zip archive, in archive order.
+///
+/// Plain and gzip-compressed files hold a single unnamed model, so the list
+/// is empty for them.
+pub fn list_models<P: AsRef<Path>>(path: P) -> Result<Vec<String>, ParseError> {
+    let path = path.as_ref();
+    let counter = AtomicU64::new(0);
+    let mut reader = open(path, &counter)?;
+    if detect(path, &mut reader)? != Container::Zip {
+        return Ok(Vec::new());
+    }
+    let archive = ZipArchive::new(reader).map_err(|source| archive_error(path, source))?;
+    Ok(model_names(&archive))
+}
+
+/// Open the model `name` (the first model if `None`) in the file at `path`
+/// and hand a reader over its decompressed bytes to `f`.
+///
+/// `name` is ignored for plain and gzip files. For zip archives `f` also
+/// receives the name of the model that was opened and the names of all
+/// models in the archive.
+///
+/// `bytes_read` follows the position in the file on disk, before decompression.
+pub(crate) fn with_model_reader<T>(
+    path: &Path,
+    name: Option<&str>,
+    bytes_read: &AtomicU64,
+    f: impl FnOnce(&mut dyn BufRead, Option<ArchiveModels>) -> Result<T, ParseError>,
+) -> Result<T, ParseError> {
+    let mut reader = open(path, bytes_read)?;
+    match detect(path, &mut reader)? {
+        Container::Plain => f(&mut reader, None),
+        Container::Gzip => f(&mut BufReader::new(MultiGzDecoder::new(reader)), None),
+        Container::Zip => {
+            let mut archive =
+                ZipArchive::new(reader).map_err(|source| archive_error(path, source))?;
+            let models = model_names(&archive);
+            let selected = match name {
+                Some(name) => models.iter().find(|m| *m == name).cloned().ok_or_else(|| {
+                    ParseError::ModelNotFound {
+                        path: path.to_path_buf(),
+                        name: name.to_string(),
+                    }
+                })?,
+                None => models
+                    .first()
+                    .cloned()
+                    .ok_or_else(|| ParseError::EmptyArchive {
+                        path: path.to_path_buf(),
+                    })?,
+            };
+            let mut entry = BufReader::new(
+                archive
+                    .by_name(&selected)
+                    .map_err(|source| archive_error(path, source))?,
+            );
+            f(&mut entry, Some(ArchiveModels { selected, models }))
+        }
+    }
+}
+
+/// The model that was opened from an archive, and all models it contains.
+#[derive(Debug, Clone)]
+pub(crate) struct ArchiveModels {
+    pub selected: String,
+    pub models: Vec<String>,
+}
+
+type FileReader<'a> = BufReader<CountingReader<'a, File>>;
+
+fn open<'a>(path: &Path, bytes_read: &'a AtomicU64) -> Result<FileReader<'a>, ParseError> {
+    let file = File::open(path).map_err(|source| ParseError::FileRead {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(BufReader::new(CountingReader::new(file, bytes_read)))
+}
+
+fn detect(path: &Path, reader: &mut FileReader<'_>) -> Result<Container, ParseError> {
+    let magic = reader.fill_buf().map_err(|source| ParseError::FileRead {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(Container::detect(magic))
+}
+
+fn model_names<R: Read + Seek>(archive: &ZipArchive<R>) -> Vec<String> {
+    archive
+        .file_names()
+        .filter(|name| {
+            let lower = name.to_ascii_lowercase();
+            // Skip resource forks added by the macOS archiver
+            !lower.starts_with("__macosx/")
+                && MODEL_EXTENSIONS.iter().any(|ext| lower.ends_with(ext))
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+fn archive_error(path: &Path, source: zip::result::ZipError) -> ParseError {
+    ParseError::Archive {
+        path: path.to_path_buf(),
+        source,
+    }
+}
@@ -0,0 +1,258 @@
+//! Problems found while parsing that did not stop the parse.
+
+use serde::Serialize;
+use std::fmt;
+
+use crate::parser::encoding::FallbackEncoding;
+
+/// Options that control how a STEP file is parsed.
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Turn every diagnostic into a hard [`ParseError::InvalidStep`](crate::error::ParseError::InvalidStep)
+    pub strict: bool,
+    /// How statements that are not valid UTF-8 are decoded
+    pub encoding: FallbackEncoding,
+    /// Memory-map uncompressed STEP files and parse entities on demand
+    /// (see [`MappedStepFile`](crate::parser::MappedStepFile)); other files
+    /// are read as usual
+    pub memory_map: bool,
+    /// Bounds on input size and structure, see [`Limits`]
+    pub limits: Limits,
+}
+
+/// Upper bounds that keep a malformed or hostile file from exhausting
+/// memory or the stack. Exceeding one stops the parse with
+/// [`ParseError::LimitExceeded`](crate::error::ParseError::LimitExceeded),
+/// also outside strict mode.
+///
+/// The defaults are far above what real models need.
+///
+/// ```
+/// use ifc_inspector::error::ParseError;
+/// use ifc_inspector::parser::{Limits, ParseOptions, StepFile};
+///
+/// let options = ParseOptions {
+///     limits: Limits { max_nesting: 3, ..Limits::default() },
+///     ..ParseOptions::default()
+/// };
+/// let content = "ISO-10303-21;HEADER;ENDSEC;DATA;#1=IFCX((((1))));ENDSEC;END-ISO-10303-21;";
+/// let err = StepFile::parse_with_options(content, options).unwrap_err();
+/// assert!(matches!(err, ParseError::LimitExceeded { .. }));
+/// assert_eq!(
+///     err.to_string(),
+///     "limit exceeded: line 1, column 43: parentheses nested deeper than 3"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Bytes of STEP or ifcXML text, counted after decompression
+    pub max_file_size: u64,
+    /// Entity instances in the DATA section
+    pub max_entities: usize,
+    /// Depth of nested parentheses in a STEP statement (the attribute
+    /// list counts as one), or of nested elements in ifcXML
+    pub max_nesting: usize,
+    /// Bytes in a single STEP string or binary literal
+    pub max_string_length: usize,
+    /// Bytes in a single STEP statement, counted from the end of the
+    /// previous one (so whitespace and comments before it are included)
+    pub max_statement_length: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_file_size: 64 << 30,
+            max_entities: 500_000_000,
+            max_nesting: 64,
+            max_string_length: 64 << 20,
+            max_statement_length: 256 << 20,
+        }
+    }
+}
+
+/// Location in the source file (line and column are 1-based, column counts characters).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub offset: u64,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+}
+
+impl Position {
+    /// Advance past one byte of input.
+    pub(crate) fn advance(&mut self, byte: u8) {
+        self.offset += 1;
+        if byte == b'\n' {
+            self.line += 1;
+            self.column = 1;
+        } else if byte & 0xC0 != 0x80 {
+            // Only count the first byte of each UTF-8 character
+            self.column += 1;
+        }
+    }
+}
+
+/// A malformed or unsupported construct that was skipped.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub offset: u64,
+    pub line: usize,
+    pub column: usize,
+    /// Entity the problem belongs to, if the statement got as far as `#123`
+    pub entity_id: Option<u64>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(position: Position, entity_id: Option<u64>, message: String) -> Self {
+        Self {
+            offset: position.offset,
+            line: position.line,
+            column: position.column,
+            entity_id,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)?;
+        if let Some(id) = self.entity_id {
+            write!(f, " (#{id})")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+    use crate::parser::StepFile;
+    use pretty_assertions::assert_eq;
+
+    const HEADER: &str = "ISO-10303-21;\nHEADER;\nFILE_SCHEMA(('IFC4'));\nENDSEC;\nDATA;\n";
+    const FOOTER: &str = "ENDSEC;\nEND-ISO-10303-21;\n";
+
+    fn position_after(text: &str) -> Position {
+        let mut position = Position::default();
+        for &b in text.as_bytes() {
+            position.advance(b);
+        }
+        position
+    }
+
+    #[test]
+    fn counts_lines_and_characters() {
+        assert_eq!(
+            position_after("ab\ncd"),
+            Position {
+                offset: 5,
+                line: 2,
+                column: 3,
+            }
+        );
+        // \r is an ordinary character, \n starts the next line
+        assert_eq!(position_after("a\r\n").line, 2);
+        assert_eq!(position_after("a\r\n").column, 1);
+        // Multi-byte characters are one column
+        let position = position_after("'Ściana 🚪'");
+        assert_eq!((position.offset, position.column), (14, 11));
+    }
+
+    #[test]
+    fn displays_position_entity_and_message() {
+        let position = Position {
+            offset: 40,
+            line: 3,
+            column: 7,
+        };
+        let with_id = Diagnostic::new(position, Some(12), "unbalanced parentheses".to_string());
+        assert_eq!(
+            with_id.to_string(),
+            "line 3, column 7 (#12): unbalanced parentheses"
+        );
+        let without_id = Diagnostic::new(position, None, "empty statement".to_string());
+        assert_eq!(without_id.to_string(), "line 3, column 7: empty statement");
+        assert_eq!(without_id.offset, 40);
+    }
+
+    #[test]
+    fn lenient_parse_keeps_the_valid_entities() {
+        let content = format!(
+            "{HEADER}#1=IFCWALL('a',$);\n#2=IFCWALL('b',$;\n#3=IFCSLAB(.T,$);\n#4=IFCSLAB('c',$);\n{FOOTER}"
+        );
+        let step_file = StepFile::parse(&content).unwrap();
+        assert_eq!(step_file.entities.len(), 2);
+        assert_eq!(step_file.skipped_entities, 2);
+        let found: Vec<(usize, usize, Option<u64>)> = step_file
+            .diagnostics
+            .iter()
+            .map(|d| (d.line, d.column, d.entity_id))
+            .collect();
+        assert_eq!(found, vec![(7, 1, Some(2)), (8, 12, Some(3))]);
+        assert_eq!(
+            step_file.diagnostics[1].offset,
+            content.find(".T,").unwrap() as u64
+        );
+    }
+
+    #[test]
+    fn strict_parse_fails_on_the_first_diagnostic() {
+        let options = ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        };
+        let valid = format!("{HEADER}#1=IFCWALL('a',$);\n{FOOTER}");
+        let step_file = StepFile::parse_with_options(&valid, options.clone()).unwrap();
+        assert_eq!(step_file.diagnostics, vec![]);
+
+        let truncated = format!("{HEADER}#1=IFCWALL('a',$);\n#2=IFCWALL('b',");
+        let err = StepFile::parse_with_options(&truncated, options).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidStep { .. }));
+        assert_eq!(
+            err.to_string(),
+            "invalid STEP format: line 7, column 1 (#2): \
+             unexpected end of file: statement is not terminated by ';'"
+        );
+
+        let lenient = StepFile::parse(&truncated).unwrap();
+        assert_eq!(lenient.entities.len(), 1);
+        assert_eq!(lenient.skipped_entities, 1);
+        assert_eq!(lenient.diagnostics.len(), 2);
+    }
+
+    #[test]
+    fn reports_a_truncated_file_without_data() {
+        let step_file = StepFile::parse("ISO-10303-21;\nHEADER;\nENDSEC;\n").unwrap();
+        assert_eq!(
+            step_file
+                .diagnostics
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["line 4, column 1: no DATA section found"]
+        );
+        assert_eq!(step_file.skipped_entities, 0);
+    }
+
+    #[test]
+    fn default_limits_allow_large_models() {
+        let limits = Limits::default();
+        assert_eq!(limits.max_file_size, 64 << 30);
+        assert!(limits.max_statement_length > limits.max_string_length);
+        assert!(limits.max_nesting >= 32);
+    }
+}
@@ -0,0 +1,671 @@
+//! Encoding and decoding of STEP string literals (ISO 10303-21, 6.4.3), and
+//! of raw file bytes that are not valid UTF-8.
+
+use std::fmt::{self, Write as _};
+use std::str::FromStr;
+
+/// Upper halves (0xA0-0xFF) of ISO 8859-1 … 8859-9, selected by `\PA\` … `\PI\`.
+/// Positions not defined by a part map to U+FFFD.
+#[rustfmt::skip]
+const ISO_8859_HIGH: [[u16; 96]; 9] = [
+    // ISO 8859-1: Latin-1
+    [
+        0x00A0, 0x00A1, 0x00A2, 0x00A3, 0x00A4, 0x00A5, 0x00A6, 0x00A7,
+        0x00A8, 0x00A9, 0x00AA, 0x00AB, 0x00AC, 0x00AD, 0x00AE, 0x00AF,
+        0x00B0, 0x00B1, 0x00B2, 0x00B3, 0x00B4, 0x00B5, 0x00B6, 0x00B7,
+        0x00B8, 0x00B9, 0x00BA, 0x00BB, 0x00BC, 0x00BD, 0x00BE, 0x00BF,
+        0x00C0, 0x00C1, 0x00C2, 0x00C3, 0x00C4, 0x00C5, 0x00C6, 0x00C7,
+        0x00C8, 0x00C9, 0x00CA, 0x00CB, 0x00CC, 0x00CD, 0x00CE, 0x00CF,
+        0x00D0, 0x00D1, 0x00D2, 0x00D3, 0x00D4, 0x00D5, 0x00D6, 0x00D7,
+        0x00D8, 0x00D9, 0x00DA, 0x00DB, 0x00DC, 0x00DD, 0x00DE, 0x00DF,
+        0x00E0, 0x00E1, 0x00E2, 0x00E3, 0x00E4, 0x00E5, 0x00E6, 0x00E7,
+        0x00E8, 0x00E9, 0x00EA, 0x00EB, 0x00EC, 0x00ED, 0x00EE, 0x00EF,
+        0x00F0, 0x00F1, 0x00F2, 0x00F3, 0x00F4, 0x00F5, 0x00F6, 0x00F7,
+        0x00F8, 0x00F9, 0x00FA, 0x00FB, 0x00FC, 0x00FD, 0x00FE, 0x00FF,
+    ],
+    // ISO 8859-2: Latin-2 (Central European)
+    [
+        0x00A0, 0x0104, 0x02D8, 0x0141, 0x00A4, 0x013D, 0x015A, 0x00A7,
+        0x00A8, 0x0160, 0x015E, 0x0164, 0x0179, 0x00AD, 0x017D, 0x017B,
+        0x00B0, 0x0105, 0x02DB, 0x0142, 0x00B4, 0x013E, 0x015B, 0x02C7,
+        0x00B8, 0x0161, 0x015F, 0x0165, 0x017A, 0x02DD, 0x017E, 0x017C,
+        0x0154, 0x00C1, 0x00C2, 0x0102, 0x00C4, 0x0139, 0x0106, 0x00C7,
+        0x010C, 0x00C9, 0x0118, 0x00CB, 0x011A, 0x00CD, 0x00CE, 0x010E,
+        0x0110, 0x0143, 0x0147, 0x00D3, 0x00D4, 0x0150, 0x00D6, 0x00D7,
+        0x0158, 0x016E, 0x00DA, 0x0170, 0x00DC, 0x00DD, 0x0162, 0x00DF,
+        0x0155, 0x00E1, 0x00E2, 0x0103, 0x00E4, 0x013A, 0x0107, 0x00E7,
+        0x010D, 0x00E9, 0x0119, 0x00EB, 0x011B, 0x00ED, 0x00EE, 0x010F,
+        0x0111, 0x0144, 0x0148, 0x00F3, 0x00F4, 0x0151, 0x00F6, 0x00F7,
+        0x0159, 0x016F, 0x00FA, 0x0171, 0x00FC, 0x00FD, 0x0163, 0x02D9,
+    ],
+    // ISO 8859-3: Latin-3 (South European)
+    [
+        0x00A0, 0x0126, 0x02D8, 0x00A3, 0x00A4, 0xFFFD, 0x0124, 0x00A7,
+        0x00A8, 0x0130, 0x015E, 0x011E, 0x0134, 0x00AD, 0xFFFD, 0x017B,
+        0x00B0, 0x0127, 0x00B2, 0x00B3, 0x00B4, 0x00B5, 0x0125, 0x00B7,
+        0x00B8, 0x0131, 0x015F, 0x011F, 0x0135, 0x00BD, 0xFFFD, 0x017C,
+        0x00C0, 0x00C1, 0x00C2, 0xFFFD, 0x00C4, 0x010A, 0x0108, 0x00C7,
+        0x00C8, 0x00C9, 0x00CA, 0x00CB, 0x00CC, 0x00CD, 0x00CE, 0x00CF,
+        0xFFFD, 0x00D1, 0x00D2, 0x00D3, 0x00D4, 0x0120, 0x00D6, 0x00D7,
+        0x011C, 0x00D9, 0x00DA, 0x00DB, 0x00DC, 0x016C, 0x015C, 0x00DF,
+        0x00E0, 0x00E1, 0x00E2, 0xFFFD, 0x00E4, 0x010B, 0x0109, 0x00E7,
+        0x00E8, 0x00E9, 0x00EA, 0x00EB, 0x00EC, 0x00ED, 0x00EE, 0x00EF,
+        0xFFFD, 0x00F1, 0x00F2, 0x00F3, 0x00F4, 0x0121, 0x00F6, 0x00F7,
+        0x011D, 0x00F9, 0x00FA, 0x00FB, 0x00FC, 0x016D, 0x015D, 0x02D9,
+    ],
+    // ISO 8859-4: Latin-4 (North European)
+    [
+        0x00A0, 0x0104, 0x0138, 0x0156, 0x00A4, 0x0128, 0x013B, 0x00A7,
+        0x00A8, 0x0160, 0x0112, 0x0122, 0x0166, 0x00AD, 0x017D, 0x00AF,
+        0x00B0, 0x0105, 0x02DB, 0x0157, 0x00B4, 0x0129, 0x013C, 0x02C7,
+        0x00B8, 0x0161, 0x0113, 0x0123, 0x0167, 0x014A, 0x017E, 0x014B,
+        0x0100, 0x00C1, 0x00C2, 0x00C3, 0x00C4, 0x00C5, 0x00C6, 0x012E,
+        0x010C, 0x00C9, 0x0118, 0x00CB, 0x0116, 0x00CD, 0x00CE, 0x012A,
+        0x0110, 0x0145, 0x014C, 0x0136, 0x00D4, 0x00D5, 0x00D6, 0x00D7,
+        0x00D8, 0x0172, 0x00DA, 0x00DB, 0x00DC, 0x0168, 0x016A, 0x00DF,
+        0x0101, 0x00E1, 0x00E2, 0x00E3, 0x00E4, 0x00E5, 0x00E6, 0x012F,
+        0x010D, 0x00E9, 0x0119, 0x00EB, 0x0117, 0x00ED, 0x00EE, 0x012B,
+        0x0111, 0x0146, 0x014D, 0x0137, 0x00F4, 0x00F5, 0x00F6, 0x00F7,
+        0x00F8, 0x0173, 0x00FA, 0x00FB, 0x00FC, 0x0169, 0x016B, 0x02D9,
+    ],
+    // ISO 8859-5: Cyrillic
+    [
+        0x00A0, 0x0401, 0x0402, 0x0403, 0x0404, 0x0405, 0x0406, 0x0407,
+        0x0408, 0x0409, 0x040A, 0x040B, 0x040C, 0x00AD, 0x040E, 0x040F,
+        0x0410, 0x0411, 0x0412, 0x0413, 0x0414, 0x0415, 0x0416, 0x0417,
+        0x0418, 0x0419, 0x041A, 0x041B, 0x041C, 0x041D, 0x041E, 0x041F,
+        0x0420, 0x0421, 0x0422, 0x0423, 0x0424, 0x0425, 0x0426, 0x0427,
+        0x0428, 0x0429, 0x042A, 0x042B, 0x042C, 0x042D, 0x042E, 0x042F,
+        0x0430, 0x0431, 0x0432, 0x0433, 0x0434, 0x0435, 0x0436, 0x0437,
+        0x0438, 0x0439, 0x043A, 0x043B, 0x043C, 0x043D, 0x043E, 0x043F,
+        0x0440, 0x0441, 0x0442, 0x0443, 0x0444, 0x0445, 0x0446, 0x0447,
+        0x0448, 0x0449, 0x044A, 0x044B, 0x044C, 0x044D, 0x044E, 0x044F,
+        0x2116, 0x0451, 0x0452, 0x0453, 0x0454, 0x0455, 0x0456, 0x0457,
+        0x0458, 0x0459, 0x045A, 0x045B, 0x045C, 0x00A7, 0x045E, 0x045F,
+    ],
+    // ISO 8859-6: Arabic
+    [
+        0x00A0, 0xFFFD, 0xFFFD, 0xFFFD, 0x00A4, 0xFFFD, 0xFFFD, 0xFFFD,
+        0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0x060C, 0x00AD, 0xFFFD, 0xFFFD,
+        0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD,
+        0xFFFD, 0xFFFD, 0xFFFD, 0x061B, 0xFFFD, 0xFFFD, 0xFFFD, 0x061F,
+        0xFFFD, 0x0621, 0x0622, 0x0623, 0x0624, 0x0625, 0x0626, 0x0627,
+        0x0628, 0x0629, 0x062A, 0x062B, 0x062C, 0x062D, 0x062E, 0x062F,
+        0x0630, 0x0631, 0x0632, 0x0633, 0x0634, 0x0635, 0x0636, 0x0637,
+        0x0638, 0x0639, 0x063A, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD,
+        0x0640, 0x0641, 0x0642, 0x0643, 0x0644, 0x0645, 0x0646, 0x0647,
+        0x0648, 0x0649, 0x064A, 0x064B, 0x064C, 0x064D, 0x064E, 0x064F,
+        0x0650, 0x0651, 0x0652, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD,
+        0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD,
+    ],
+    // ISO 8859-7: Greek
+    [
+        0x00A0, 0x2018, 0x2019, 0x00A3, 0x20AC, 0x20AF, 0x00A6, 0x00A7,
+        0x00A8, 0x00A9, 0x037A, 0x00AB, 0x00AC, 0x00AD, 0xFFFD, 0x2015,
+        0x00B0, 0x00B1, 0x00B2, 0x00B3, 0x0384, 0x0385, 0x0386, 0x00B7,
+        0x0388, 0x0389, 0x038A, 0x00BB, 0x038C, 0x00BD, 0x038E, 0x038F,
+        0x0390, 0x0391, 0x0392, 0x0393, 0x0394, 0x0395, 0x0396, 0x0397,
+        0x0398, 0x0399, 0x039A, 0x039B, 0x039C, 0x039D, 0x039E, 0x039F,
+        0x03A0, 0x03A1, 0xFFFD, 0x03A3, 0x03A4, 0x03A5, 0x03A6, 0x03A7,
+        0x03A8, 0x03A9, 0x03AA, 0x03AB, 0x03AC, 0x03AD, 0x03AE, 0x03AF,
+        0x03B0, 0x03B1, 0x03B2, 0x03B3, 0x03B4, 0x03B5, 0x03B6, 0x03B7,
+        0x03B8, 0x03B9, 0x03BA, 0x03BB, 0x03BC, 0x03BD, 0x03BE, 0x03BF,
+        0x03C0, 0x03C1, 0x03C2, 0x03C3, 0x03C4, 0x03C5, 0x03C6, 0x03C7,
+        0x03C8, 0x03C9, 0x03CA, 0x03CB, 0x03CC, 0x03CD, 0x03CE, 0xFFFD,
+    ],
+    // ISO 8859-8: Hebrew
+    [
+        0x00A0, 0xFFFD, 0x00A2, 0x00A3, 0x00A4, 0x00A5, 0x00A6, 0x00A7,
+        0x00A8, 0x00A9, 0x00D7, 0x00AB, 0x00AC, 0x00AD, 0x00AE, 0x00AF,
+        0x00B0, 0x00B1, 0x00B2, 0x00B3, 0x00B4, 0x00B5, 0x00B6, 0x00B7,
+        0x00B8, 0x00B9, 0x00F7, 0x00BB, 0x00BC, 0x00BD, 0x00BE, 0xFFFD,
+        0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD,
+        0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD,
+        0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD,
+        0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD, 0x2017,
+        0x05D0, 0x05D1, 0x05D2, 0x05D3, 0x05D4, 0x05D5, 0x05D6, 0x05D7,
+        0x05D8, 0x05D9, 0x05DA, 0x05DB, 0x05DC, 0x05DD, 0x05DE, 0x05DF,
+        0x05E0, 0x05E1, 0x05E2, 0x05E3, 0x05E4, 0x05E5, 0x05E6, 0x05E7,
+        0x05E8, 0x05E9, 0x05EA, 0xFFFD, 0xFFFD, 0x200E, 0x200F, 0xFFFD,
+    ],
+    // ISO 8859-9: Latin-5 (Turkish)
+    [
+        0x00A0, 0x00A1, 0x00A2, 0x00A3, 0x00A4, 0x00A5, 0x00A6, 0x00A7,
+        0x00A8, 0x00A9, 0x00AA, 0x00AB, 0x00AC, 0x00AD, 0x00AE, 0x00AF,
+        0x00B0, 0x00B1, 0x00B2, 0x00B3, 0x00B4, 0x00B5, 0x00B6, 0x00B7,
+        0x00B8, 0x00B9, 0x00BA, 0x00BB, 0x00BC, 0x00BD, 0x00BE, 0x00BF,
+        0x00C0, 0x00C1, 0x00C2, 0x00C3, 0x00C4, 0x00C5, 0x00C6, 0x00C7,
+        0x00C8, 0x00C9, 0x00CA, 0x00CB, 0x00CC, 0x00CD, 0x00CE, 0x00CF,
+        0x011E, 0x00D1, 0x00D2, 0x00D3, 0x00D4, 0x00D5, 0x00D6, 0x00D7,
+        0x00D8, 0x00D9, 0x00DA, 0x00DB, 0x00DC, 0x0130, 0x015E, 0x00DF,
+        0x00E0, 0x00E1, 0x00E2, 0x00E3, 0x00E4, 0x00E5, 0x00E6, 0x00E7,
+        0x00E8, 0x00E9, 0x00EA, 0x00EB, 0x00EC, 0x00ED, 0x00EE, 0x00EF,
+        0x011F, 0x00F1, 0x00F2, 0x00F3, 0x00F4, 0x00F5, 0x00F6, 0x00F7,
+        0x00F8, 0x00F9, 0x00FA, 0x00FB, 0x00FC, 0x0131, 0x015F, 0x00FF,
+    ],
+];
+
+/// Upper halves (0x80-0xFF) of the Windows code pages used as fallback for
+/// non-UTF-8 files. Bytes not defined by a code page map to U+FFFD.
+#[rustfmt::skip]
+const WINDOWS_HIGH: [[u16; 128]; 4] = [
+    // Windows-1250: Central European
+    [
+        0x20AC, 0xFFFD, 0x201A, 0xFFFD, 0x201E, 0x2026, 0x2020, 0x2021,
+        0xFFFD, 0x2030, 0x0160, 0x2039, 0x015A, 0x0164, 0x017D, 0x0179,
+        0xFFFD, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013, 0x2014,
+        0xFFFD, 0x2122, 0x0161, 0x203A, 0x015B, 0x0165, 0x017E, 0x017A,
+        0x00A0, 0x02C7, 0x02D8, 0x0141, 0x00A4, 0x0104, 0x00A6, 0x00A7,
+        0x00A8, 0x00A9, 0x015E, 0x00AB, 0x00AC, 0x00AD, 0x00AE, 0x017B,
+        0x00B0, 0x00B1, 0x02DB, 0x0142, 0x00B4, 0x00B5, 0x00B6, 0x00B7,
+        0x00B8, 0x0105, 0x015F, 0x00BB, 0x013D, 0x02DD, 0x013E, 0x017C,
+        0x0154, 0x00C1, 0x00C2, 0x0102, 0x00C4, 0x0139, 0x0106, 0x00C7,
+        0x010C, 0x00C9, 0x0118, 0x00CB, 0x011A, 0x00CD, 0x00CE, 0x010E,
+        0x0110, 0x0143, 0x0147, 0x00D3, 0x00D4, 0x0150, 0x00D6, 0x00D7,
+        0x0158, 0x016E, 0x00DA, 0x0170, 0x00DC, 0x00DD, 0x0162, 0x00DF,
+        0x0155, 0x00E1, 0x00E2, 0x0103, 0x00E4, 0x013A, 0x0107, 0x00E7,
+        0x010D, 0x00E9, 0x0119, 0x00EB, 0x011B, 0x00ED, 0x00EE, 0x010F,
+        0x0111, 0x0144, 0x0148, 0x00F3, 0x00F4, 0x0151, 0x00F6, 0x00F7,
+        0x0159, 0x016F, 0x00FA, 0x0171, 0x00FC, 0x00FD, 0x0163, 0x02D9,
+    ],
+    // Windows-1251: Cyrillic
+    [
+        0x0402, 0x0403, 0x201A, 0x0453, 0x201E, 0x2026, 0x2020, 0x2021,
+        0x20AC, 0x2030, 0x0409, 0x2039, 0x040A, 0x040C, 0x040B, 0x040F,
+        0x0452, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013, 0x2014,
+        0xFFFD, 0x2122, 0x0459, 0x203A, 0x045A, 0x045C, 0x045B, 0x045F,
+        0x00A0, 0x040E, 0x045E, 0x0408, 0x00A4, 0x0490, 0x00A6, 0x00A7,
+        0x0401, 0x00A9, 0x0404, 0x00AB, 0x00AC, 0x00AD, 0x00AE, 0x0407,
+        0x00B0, 0x00B1, 0x0406, 0x0456, 0x0491, 0x00B5, 0x00B6, 0x00B7,
+        0x0451, 0x2116, 0x0454, 0x00BB, 0x0458, 0x0405, 0x0455, 0x0457,
+        0x0410, 0x0411, 0x0412, 0x0413, 0x0414, 0x0415, 0x0416, 0x0417,
+        0x0418, 0x0419, 0x041A, 0x041B, 0x041C, 0x041D, 0x041E, 0x041F,
+        0x0420, 0x0421, 0x0422, 0x0423, 0x0424, 0x0425, 0x0426, 0x0427,
+        0x0428, 0x0429, 0x042A, 0x042B, 0x042C, 0x042D, 0x042E, 0x042F,
+        0x0430, 0x0431, 0x0432, 0x0433, 0x0434, 0x0435, 0x0436, 0x0437,
+        0x0438, 0x0439, 0x043A, 0x043B, 0x043C, 0x043D, 0x043E, 0x043F,
+        0x0440, 0x0441, 0x0442, 0x0443, 0x0444, 0x0445, 0x0446, 0x0447,
+        0x0448, 0x0449, 0x044A, 0x044B, 0x044C, 0x044D, 0x044E, 0x044F,
+    ],
+    // Windows-1252: Western European
+    [
+        0x20AC, 0xFFFD, 0x201A, 0x0192, 0x201E, 0x2026, 0x2020, 0x2021,
+        0x02C6, 0x2030, 0x0160, 0x2039, 0x0152, 0xFFFD, 0x017D, 0xFFFD,
+        0xFFFD, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013, 0x2014,
+        0x02DC, 0x2122, 0x0161, 0x203A, 0x0153, 0xFFFD, 0x017E, 0x0178,
+        0x00A0, 0x00A1, 0x00A2, 0x00A3, 0x00A4, 0x00A5, 0x00A6, 0x00A7,
+        0x00A8, 0x00A9, 0x00AA, 0x00AB, 0x00AC, 0x00AD, 0x00AE, 0x00AF,
+        0x00B0, 0x00B1, 0x00B2, 0x00B3, 0x00B4, 0x00B5, 0x00B6, 0x00B7,
+        0x00B8, 0x00B9, 0x00BA, 0x00BB, 0x00BC, 0x00BD, 0x00BE, 0x00BF,
+        0x00C0, 0x00C1, 0x00C2, 0x00C3, 0x00C4, 0x00C5, 0x00C6, 0x00C7,
+        0x00C8, 0x00C9, 0x00CA, 0x00CB, 0x00CC, 0x00CD, 0x00CE, 0x00CF,
+        0x00D0, 0x00D1, 0x00D2, 0x00D3, 0x00D4, 0x00D5, 0x00D6, 0x00D7,
+        0x00D8, 0x00D9, 0x00DA, 0x00DB, 0x00DC, 0x00DD, 0x00DE, 0x00DF,
+        0x00E0, 0x00E1, 0x00E2, 0x00E3, 0x00E4, 0x00E5, 0x00E6, 0x00E7,
+        0x00E8, 0x00E9, 0x00EA, 0x00EB, 0x00EC, 0x00ED, 0x00EE, 0x00EF,
+        0x00F0, 0x00F1, 0x00F2, 0x00F3, 0x00F4, 0x00F5, 0x00F6, 0x00F7,
+        0x00F8, 0x00F9, 0x00FA, 0x00FB, 0x00FC, 0x00FD, 0x00FE, 0x00FF,
+    ],
+    // Windows-1253: Greek
+    [
+        0x20AC, 0xFFFD, 0x201A, 0x0192, 0x201E, 0x2026, 0x2020, 0x2021,
+        0xFFFD, 0x2030, 0xFFFD, 0x2039, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD,
+        0xFFFD, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013, 0x2014,
+        0xFFFD, 0x2122, 0xFFFD, 0x203A, 0xFFFD, 0xFFFD, 0xFFFD, 0xFFFD,
+        0x00A0, 0x0385, 0x0386, 0x00A3, 0x00A4, 0x00A5, 0x00A6, 0x00A7,
+        0x00A8, 0x00A9, 0xFFFD, 0x00AB, 0x00AC, 0x00AD, 0x00AE, 0x2015,
+        0x00B0, 0x00B1, 0x00B2, 0x00B3, 0x0384, 0x00B5, 0x00B6, 0x00B7,
+        0x0388, 0x0389, 0x038A, 0x00BB, 0x038C, 0x00BD, 0x038E, 0x038F,
+        0x0390, 0x0391, 0x0392, 0x0393, 0x0394, 0x0395, 0x0396, 0x0397,
+        0x0398, 0x0399, 0x039A, 0x039B, 0x039C, 0x039D, 0x039E, 0x039F,
+        0x03A0, 0x03A1, 0xFFFD, 0x03A3, 0x03A4, 0x03A5, 0x03A6, 0x03A7,
+        0x03A8, 0x03A9, 0x03AA, 0x03AB, 0x03AC, 0x03AD, 0x03AE, 0x03AF,
+        0x03B0, 0x03B1, 0x03B2, 0x03B3, 0x03B4, 0x03B5, 0x03B6, 0x03B7,
+        0x03B8, 0x03B9, 0x03BA, 0x03BB, 0x03BC, 0x03BD, 0x03BE, 0x03BF,
+        0x03C0, 0x03C1, 0x03C2, 0x03C3, 0x03C4, 0x03C5, 0x03C6, 0x03C7,
+        0x03C8, 0x03C9, 0x03CA, 0x03CB, 0x03CC, 0x03CD, 0x03CE, 0xFFFD,
+    ],
+];
+
+/// Decode the content of a STEP string literal (without the surrounding quotes).
+///
+/// Supports:
+/// - `\X2\XXXX…\X0\` - UTF-16 code units (BMP, surrogate pairs are combined)
+/// - `\X4\XXXXXXXX…\X0\` - 32-bit code points
+/// - `\X\XX` - 1-byte ISO 8859-1
+/// - `\S\c` - character `c` + 128 in the current code page
+/// - `\PA\` … `\PI\` - select ISO 8859-1 … 8859-9 as code page for `\S\`
+/// - `\\` - escaped backslash
+/// - `''` - escaped apostrophe
+///
+/// Invalid code units (e.g. a lone surrogate) decode to U+FFFD instead of
+/// being dropped.
+///
+/// ```
+/// use ifc_inspector::parser::decode_step_string;
+///
+/// assert_eq!(decode_step_string("\\X2\\00D3\\X0\\w"), "Ów");
+/// assert_eq!(decode_step_string("\\X2\\D83DDEAA\\X0\\"), "🚪");
+/// assert_eq!(decode_step_string("\\X4\\0001F6AA\\X0\\"), "🚪");
+/// // ISO 8859-2: \S\1 = 0xB1 = 'ą'
+/// assert_eq!(decode_step_string("\\PB\\w\\S\\1s"), "wąs");
+/// // ISO 8859-7: \S\a = 0xE1 = 'α'
+/// assert_eq!(decode_step_string("\\PG\\\\S\\a"), "α");
+/// assert_eq!(decode_step_string("it''s"), "it's");
+/// ```
+#[must_use]
+pub fn decode_step_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    // Index into ISO_8859_HIGH; \PA\ (ISO 8859-1) is the default
+    let mut code_page = 0;
+    let mut rest = s;
+
+    while let Some(ch) = rest.chars().next() {
+        if ch == '\'' {
+            // '' is escaped apostrophe in STEP
+            result.push('\'');
+            rest = rest.strip_prefix("''").unwrap_or(&rest[1..]);
+            continue;
+        }
+        if ch != '\\' {
+            result.push(ch);
+            rest = &rest[ch.len_utf8()..];
+            continue;
+        }
+
+        if let Some(r) = rest.strip_prefix("\\\\") {
+            result.push('\\');
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("\\S\\") {
+            // \S\c - single char shift into the upper half of the code page
+            match r.chars().next() {
+                Some(c) => {
+                    result.push(shifted_char(code_page, c));
+                    rest = &r[c.len_utf8()..];
+                }
+                None => rest = r,
+            }
+        } else if let Some(page) = code_page_switch(rest) {
+            code_page = page;
+            rest = &rest[4..];
+        } else if let Some(r) = rest.strip_prefix("\\X2\\") {
+            let (hex, r) = split_hex_block(r);
+            decode_utf16_hex(hex, &mut result);
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("\\X4\\") {
+            let (hex, r) = split_hex_block(r);
+            decode_ucs4_hex(hex, &mut result);
+            rest = r;
+        } else if let Some(code) = rest
+            .strip_prefix("\\X\\")
+            .and_then(|r| r.get(..2))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            // \X\XX - ISO 8859-1
+            result.push(char::from(code));
+            rest = &rest[5..];
+        } else {
+            // Not a known escape - keep the backslash literally
+            result.push('\\');
+            rest = &rest[1..];
+        }
+    }
+
+    result
+}
+
+/// `\PA\` … `\PI\` at the start of `s` → code page index 0 … 8.
+fn code_page_switch(s: &str) -> Option<usize> {
+    match s.as_bytes() {
+        [b'\\', b'P', page @ b'A'..=b'I', b'\\', ..] => Some(usize::from(page - b'A')),
+        _ => None,
+    }
+}
+
+/// Character for `\S\c`: byte `c + 128` interpreted in the given code page.
+fn shifted_char(code_page: usize, c: char) -> char {
+    let Ok(byte) = u8::try_from(u32::from(c)) else {
+        // Not valid after \S\ (must be ASCII) - keep the character as-is
+        return c;
+    };
+    if byte >= 0x80 {
+        return c;
+    }
+    let high = byte + 0x80;
+    if high < 0xA0 {
+        // C1 control characters are the same in every part of ISO 8859
+        return char::from(high);
+    }
+    char::from_u32(u32::from(
+        ISO_8859_HIGH[code_page][usize::from(high - 0xA0)],
+    ))
+    .unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// Split `XXXX…\X0\rest` into the hex digits and what follows the `\X0\`.
+fn split_hex_block(s: &str) -> (&str, &str) {
+    let end = s.find('\\').unwrap_or(s.len());
+    let (hex, rest) = s.split_at(end);
+    (hex, rest.strip_prefix("\\X0\\").unwrap_or(rest))
+}
+
+fn decode_utf16_hex(hex: &str, result: &mut String) {
+    let units = hex
+        .as_bytes()
+        .chunks_exact(4)
+        .filter_map(|chunk| std::str::from_utf8(chunk).ok())
+        .filter_map(|digits| u16::from_str_radix(digits, 16).ok());
+    result.extend(char::decode_utf16(units).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)));
+}
+
+fn decode_ucs4_hex(hex: &str, result: &mut String) {
+    let chars = hex
+        .as_bytes()
+        .chunks_exact(8)
+        .filter_map(|chunk| std::str::from_utf8(chunk).ok())
+        .filter_map(|digits| u32::from_str_radix(digits, 16).ok())
+        .map(|code| char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+    result.extend(chars);
+}
+
+/// Encode a string for use between quotes in a STEP file.
+///
+/// - `'` → `''` and `\` → `\\`
+/// - printable ASCII is written as-is
+/// - everything else is written as `\X2\XXXX…\X0\` (BMP) or `\X4\XXXXXXXX…\X0\`
+///
+/// [`decode_step_string`] reverses it:
+///
+/// ```
+/// use ifc_inspector::parser::{decode_step_string, encode_step_string};
+///
+/// for s in ["Ściana 200", "Příčka", "Τοίχος", "it's \\ ok", "Room 🚪"] {
+///     assert_eq!(decode_step_string(&encode_step_string(s)), s);
+/// }
+/// assert_eq!(encode_step_string("Ó"), "\\X2\\00D3\\X0\\");
+/// ```
+#[must_use]
+pub fn encode_step_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\'' => out.push_str("''"),
+            '\\' => out.push_str("\\\\"),
+            ' '..='~' => out.push(ch),
+            _ => {
+                // Group consecutive characters of the same width into one block
+                let wide = u32::from(ch) > 0xFFFF;
+                out.push_str(if wide { "\\X4\\" } else { "\\X2\\" });
+                let mut current = Some(ch);
+                while let Some(c) = current {
+                    if wide {
+                        let _ = write!(out, "{:08X}", u32::from(c));
+                    } else {
+                        let _ = write!(out, "{:04X}", u32::from(c));
+                    }
+                    current = chars.next_if(|&next| {
+                        !matches!(next, ' '..='~') && (u32::from(next) > 0xFFFF) == wide
+                    });
+                }
+                out.push_str("\\X0\\");
+            }
+        }
+    }
+
+    out
+}
+
+/// How bytes that are not valid UTF-8 are decoded.
+///
+/// STEP files should be plain ASCII, but older exporters write Latin-1 or
+/// Windows code page bytes directly into strings. Statements that are valid
+/// UTF-8 are never affected by this setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FallbackEncoding {
+    /// Windows-1252, a superset of the printable ISO 8859-1 range
+    #[default]
+    Windows1252,
+    /// Windows-1250 (Central European: Polish, Czech, ...)
+    Windows1250,
+    /// Windows-1251 (Cyrillic)
+    Windows1251,
+    /// Windows-1253 (Greek)
+    Windows1253,
+    /// ISO 8859-1, every byte maps to the code point of the same value
+    Latin1,
+    /// Keep valid UTF-8 sequences, replace invalid bytes with U+FFFD
+    Lossy,
+}
+
+impl FallbackEncoding {
+    pub const ALL: [Self; 6] = [
+        Self::Windows1252,
+        Self::Windows1250,
+        Self::Windows1251,
+        Self::Windows1253,
+        Self::Latin1,
+        Self::Lossy,
+    ];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Windows1252 => "windows-1252",
+            Self::Windows1250 => "windows-1250",
+            Self::Windows1251 => "windows-1251",
+            Self::Windows1253 => "windows-1253",
+            Self::Latin1 => "iso-8859-1",
+            Self::Lossy => "lossy",
+        }
+    }
+}
+
+impl fmt::Display for FallbackEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for FallbackEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.to_ascii_lowercase().replace('_', "-");
+        let encoding = match normalized.as_str() {
+            "windows-1252" | "cp1252" => Self::Windows1252,
+            "windows-1250" | "cp1250" => Self::Windows1250,
+            "windows-1251" | "cp1251" => Self::Windows1251,
+            "windows-1253" | "cp1253" => Self::Windows1253,
+            "iso-8859-1" | "latin1" | "latin-1" => Self::Latin1,
+            "lossy" | "utf-8" | "utf8" => Self::Lossy,
+            _ => {
+                let names: Vec<&str> = Self::ALL.iter().map(|e| e.name()).collect();
+                return Err(format!(
+                    "unknown encoding '{s}' (expected one of: {})",
+                    names.join(", ")
+                ));
+            }
+        };
+        Ok(encoding)
+    }
+}
+
+/// Decode bytes that are not valid UTF-8 with the given fallback encoding.
+///
+/// Returns the text and the number of U+FFFD replacement characters that
+/// were inserted for bytes the encoding cannot represent.
+///
+/// ```
+/// use ifc_inspector::parser::{decode_bytes, FallbackEncoding};
+///
+/// // 'Ściana' with 0x8C = 'Ś' in Windows-1250
+/// let bytes = b"\x8Cciana";
+/// assert_eq!(decode_bytes(bytes, FallbackEncoding::Windows1250), ("Ściana".to_string(), 0));
+/// assert_eq!(decode_bytes(b"caf\xE9", FallbackEncoding::Latin1), ("café".to_string(), 0));
+/// assert_eq!(decode_bytes(b"caf\xE9", FallbackEncoding::Lossy), ("caf\u{FFFD}".to_string(), 1));
+/// ```
+#[must_use]
+pub fn decode_bytes(bytes: &[u8], encoding: FallbackEncoding) -> (String, usize) {
+    let table = match encoding {
+        FallbackEncoding::Windows1250 => &WINDOWS_HIGH[0],
+        FallbackEncoding::Windows1251 => &WINDOWS_HIGH[1],
+        FallbackEncoding::Windows1252 => &WINDOWS_HIGH[2],
+        FallbackEncoding::Windows1253 => &WINDOWS_HIGH[3],
+        FallbackEncoding::Latin1 => {
+            return (bytes.iter().copied().map(char::from).collect(), 0);
+        }
+        FallbackEncoding::Lossy => {
+            let mut text = String::with_capacity(bytes.len());
+            let mut replaced = 0;
+            for chunk in bytes.utf8_chunks() {
+                text.push_str(chunk.valid());
+                if !chunk.invalid().is_empty() {
+                    text.push(char::REPLACEMENT_CHARACTER);
+                    replaced += 1;
+                }
+            }
+            return (text, replaced);
+        }
+    };
+
+    let mut replaced = 0;
+    let text = bytes
+        .iter()
+        .map(|&b| {
+            if b < 0x80 {
+                return char::from(b);
+            }
+            let c = char::from_u32(u32::from(table[usize::from(b - 0x80)]))
+                .unwrap_or(char::REPLACEMENT_CHARACTER);
+            if c == char::REPLACEMENT_CHARACTER {
+                replaced += 1;
+            }
+            c
+        })
+        .collect();
+    (text, replaced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn decodes_x2_blocks() {
+        assert_eq!(decode_step_string("\\X2\\00D3\\X0\\w"), "Ów");
+        assert_eq!(decode_step_string("\\X2\\015A00630069\\X0\\ana"), "Ściana");
+        assert_eq!(decode_step_string("a\\X2\\\\X0\\b"), "ab");
+    }
+
+    #[test]
+    fn combines_surrogate_pairs_in_x2_blocks() {
+        assert_eq!(decode_step_string("\\X2\\D83DDEAA\\X0\\"), "🚪");
+        assert_eq!(
+            decode_step_string("Room \\X2\\D83DDEAA0020D83CDFE0\\X0\\"),
+            "Room 🚪 🏠"
+        );
+    }
+
+    #[test]
+    fn replaces_lone_surrogates() {
+        assert_eq!(decode_step_string("\\X2\\D83D\\X0\\x"), "\u{FFFD}x");
+        assert_eq!(decode_step_string("\\X2\\DEAA0041\\X0\\"), "\u{FFFD}A");
+        assert_eq!(decode_step_string("\\X2\\D83D0041\\X0\\"), "\u{FFFD}A");
+    }
+
+    #[test]
+    fn decodes_x4_blocks() {
+        assert_eq!(decode_step_string("\\X4\\0001F6AA\\X0\\"), "🚪");
+        assert_eq!(decode_step_string("\\X4\\000000C40001F3E0\\X0\\!"), "Ä🏠!");
+        // Not a Unicode scalar value
+        assert_eq!(decode_step_string("\\X4\\0000D800\\X0\\"), "\u{FFFD}");
+    }
+
+    #[test]
+    fn decodes_shifted_chars_in_every_code_page() {
+        let cases = [
+            // ISO 8859-1 is the default: 0xC4
+            ("\\S\\D", "Ä"),
+            ("\\PA\\\\S\\D", "Ä"),
+            // ISO 8859-2: 0xB1
+            ("\\PB\\\\S\\1", "ą"),
+            // ISO 8859-3: 0xA6
+            ("\\PC\\\\S\\&", "Ĥ"),
+            // ISO 8859-4: 0xA1
+            ("\\PD\\\\S\\!", "Ą"),
+            // ISO 8859-5: 0xB0
+            ("\\PE\\\\S\\0", "А"),
+            // ISO 8859-6: 0xC7
+            ("\\PF\\\\S\\G", "ا"),
+            // ISO 8859-7: 0xE1
+            ("\\PG\\\\S\\a", "α"),
+            // ISO 8859-8: 0xE0
+            ("\\PH\\\\S\\`", "א"),
+            // ISO 8859-9: 0xDD
+            ("\\PI\\\\S\\]", "İ"),
+        ];
+        for (encoded, expected) in cases {
+            assert_eq!(decode_step_string(encoded), expected, "{encoded}");
+        }
+    }
+
+    #[test]
+    fn keeps_the_code_page_until_the_next_switch() {
+        assert_eq!(
+            decode_step_string("\\PB\\w\\S\\1s \\S\\1\\PA\\\\S\\D"),
+            "wąs ąÄ"
+        );
+        // \X2\ blocks do not reset the code page
+        assert_eq!(
+            decode_step_string("\\PG\\\\S\\a\\X2\\00D3\\X0\\\\S\\b"),
+            "αÓβ"
+        );
+    }
+
+    #[test]
+    fn decodes_x_escapes_as_latin1() {
+        assert_eq!(decode_step_string("caf\\X\\E9"), "café");
+        assert_eq!(decode_step_string("\\X\\C4\\X\\D6"), "ÄÖ");
+        // \X\ ignores the current code page
+        assert_eq!(decode_step_string("\\PB\\\\X\\B1"), "±");
+    }
+
+    #[test]
+    fn decodes_apostrophes_and_backslashes() {
+        assert_eq!(decode_step_string("it''s"), "it's");
+        assert_eq!(decode_step_string("C:\\\\dir"), "C:\\dir");
+    }
+
+    #[test]
+    fn round_trips_non_latin1_text() {
+        let texts = [
+            "Ściana żelbetowa",
+            "Příčka tl. 100",
+            "Τοίχος",
+            "Стена наружная",
+            "墙体 200mm",
+            "壁・スラブ",
+            "Room 🚪 🏠",
+            "Mixed Ó🚪ą\u{FFFD}",
+            "it's a \\ path",
+        ];
+        for text in texts {
+            let encoded = encode_step_string(text);
+            assert!(encoded.is_ascii(), "{encoded}");
+            assert_eq!(decode_step_string(&encoded), text);
+        }
+    }
+
+    #[test]
+    fn encodes_runs_of_the_same_width_in_one_block() {
+        assert_eq!(encode_step_string("Ściana"), "\\X2\\015A\\X0\\ciana");
+        assert_eq!(encode_step_string("ΑΒ"), "\\X2\\03910392\\X0\\");
+        assert_eq!(
+            encode_step_string("Ó🚪"),
+            "\\X2\\00D3\\X0\\\\X4\\0001F6AA\\X0\\"
+        );
+    }
+}
@@ -1,45 +1,122 @@
 use crate::error::ParseError;
-use crate::model::{Category, ElementType, IfcProject, Storey};
-use crate::parser::step::{StepFile, StepValue};
-use std::collections::HashMap;
+use crate::model::{
+    Category, Discipline, ElementType, IfcProject, Space, SpatialKind, SpatialNode, Storey,
+};
+use crate::parser::container::{with_model_reader, ArchiveModels, Container};
+use crate::parser::diagnostic::ParseOptions;
+use crate::parser::mapped::MappedStepFile;
+use crate::parser::progress::{CancellationToken, ParsePhase, Progress, ProgressSink};
+use crate::parser::step::{EntitySource, StepEntity, StepFile, StepValue};
+use crate::schema::{validate, ExpressSchema, SchemaVersion, Violation};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::BufRead;
 use std::path::Path;
 
-const PRIORITY_CATEGORIES: &[(&str, &str)] = &[
-    ("IFCWALL", "Walls"),
-    ("IFCDOOR", "Doors"),
-    ("IFCWINDOW", "Windows"),
-    ("IFCFURNISHINGELEMENT", "Furniture"),
-    ("IFCFLOWFIXTURE", "Fixtures"),
-    ("IFCSANITARYTERMINAL", "Fixtures"),
-    ("IFCFLOWTERMINAL", "Fixtures"), // NOWE - urządzenia przepływowe
-];
+/// Categories listed first within their discipline
+const PRIORITY_CATEGORIES: &[&str] = &["Walls", "Doors", "Windows", "Furniture", "Fixtures"];
 
-// Maps element entity to its type entity (IFC4 and IFC2X3 compatible)
-const ELEMENT_TYPES: &[(&str, &[&str])] = &[
-    // Priority elements - IFC4 types first, then IFC2X3 styles
-    ("IFCWALL", &["IFCWALLTYPE"]),
-    ("IFCWALLSTANDARDCASE", &["IFCWALLTYPE"]),
-    ("IFCDOOR", &["IFCDOORTYPE", "IFCDOORSTYLE"]),
-    ("IFCWINDOW", &["IFCWINDOWTYPE", "IFCWINDOWSTYLE"]),
-    ("IFCFURNISHINGELEMENT", &["IFCFURNITURETYPE"]),
-    ("IFCFLOWFIXTURE", &["IFCFLOWTERMINALTYPE"]),
-    ("IFCSANITARYTERMINAL", &["IFCSANITARYTERMINALTYPE"]),
-    // Other elements
-    ("IFCSLAB", &["IFCSLABTYPE"]),
-    ("IFCCOLUMN", &["IFCCOLUMNTYPE"]),
-    ("IFCBEAM", &["IFCBEAMTYPE"]),
-    ("IFCSTAIR", &["IFCSTAIRTYPE", "IFCSTAIRFLIGHTTYPE"]),
-    ("IFCRAILING", &["IFCRAILINGTYPE"]),
-    ("IFCROOF", &["IFCROOFTYPE"]),
-    ("IFCCOVERING", &["IFCCOVERINGTYPE"]),
-    ("IFCCURTAINWALL", &["IFCCURTAINWALLTYPE"]),
+/// Element entities (IFC2x3, IFC4 and IFC4x3) → category and discipline.
+///
+/// Entities match their subtypes and the first matching row wins, so the
+/// more specific rows come first: `IFCJUNCTIONBOX` (a flow fitting) goes to
+/// electrical devices before `IFCFLOWFITTING` is reached. Type objects are
+/// named after their element with a `TYPE` suffix (`STYLE` for IFC2x3 doors
+/// and windows) and are matched the same way, so an IFC2x3 `IFCFLOWSEGMENT`
+/// typed by an `IFCDUCTSEGMENTTYPE` is listed under ducts.
+#[rustfmt::skip]
+const CATEGORIES: &[(&[&str], &str, Discipline)] = &[
+    // Architecture
+    (&["IFCCURTAINWALL"], "Curtain Walls", Discipline::Architecture),
+    (&["IFCWALL"], "Walls", Discipline::Architecture),
+    (&["IFCDOOR"], "Doors", Discipline::Architecture),
+    (&["IFCWINDOW"], "Windows", Discipline::Architecture),
+    (&["IFCFURNISHINGELEMENT"], "Furniture", Discipline::Architecture),
+    (&["IFCSLAB"], "Slabs", Discipline::Architecture),
+    (&["IFCROOF"], "Roofs", Discipline::Architecture),
+    (&["IFCSTAIR", "IFCSTAIRFLIGHT"], "Stairs", Discipline::Architecture),
+    (&["IFCRAMP", "IFCRAMPFLIGHT"], "Ramps", Discipline::Architecture),
+    (&["IFCRAILING"], "Railings", Discipline::Architecture),
+    (&["IFCCOVERING"], "Coverings", Discipline::Architecture),
+    (&["IFCSHADINGDEVICE"], "Shading Devices", Discipline::Architecture),
+    (&["IFCCHIMNEY"], "Chimneys", Discipline::Architecture),
+    (&["IFCBUILDINGELEMENTPART"], "Parts", Discipline::Architecture),
+    // Structure
+    (&["IFCCOLUMN"], "Columns", Discipline::Structure),
+    (&["IFCBEAM"], "Beams", Discipline::Structure),
+    (&["IFCMEMBER"], "Members", Discipline::Structure),
+    (&["IFCPLATE"], "Plates", Discipline::Structure),
+    (&["IFCFOOTING", "IFCPILE", "IFCDEEPFOUNDATION"], "Foundations", Discipline::Structure),
+    (&["IFCREINFORCINGELEMENT"], "Reinforcement", Discipline::Structure),
+    (&["IFCBEARING"], "Bearings", Discipline::Structure),
+    (&["IFCFASTENER", "IFCMECHANICALFASTENER"], "Fasteners", Discipline::Structure),
+    // MEP: terminals and devices before the flow element they specialise
+    (&["IFCSANITARYTERMINAL"], "Fixtures", Discipline::Mep),
+    (&["IFCLIGHTFIXTURE", "IFCLAMP"], "Lighting", Discipline::Mep),
+    (&["IFCAIRTERMINAL", "IFCAIRTERMINALBOX"], "Air Terminals", Discipline::Mep),
+    (&["IFCFIRESUPPRESSIONTERMINAL"], "Fire Protection", Discipline::Mep),
+    (&["IFCELECTRICAPPLIANCE", "IFCAUDIOVISUALAPPLIANCE", "IFCCOMMUNICATIONSAPPLIANCE", "IFCMOBILETELECOMMUNICATIONSAPPLIANCE", "IFCMEDICALDEVICE"], "Appliances", Discipline::Mep),
+    (&["IFCOUTLET", "IFCSWITCHINGDEVICE", "IFCPROTECTIVEDEVICE", "IFCPROTECTIVEDEVICETRIPPINGUNIT", "IFCELECTRICTIMECONTROL", "IFCJUNCTIONBOX"], "Electrical Devices", Discipline::Mep),
+    (&["IFCELECTRICDISTRIBUTIONBOARD", "IFCDISTRIBUTIONBOARD", "IFCELECTRICDISTRIBUTIONPOINT", "IFCTRANSFORMER", "IFCELECTRICGENERATOR", "IFCELECTRICMOTOR", "IFCMOTORCONNECTION", "IFCELECTRICFLOWSTORAGEDEVICE", "IFCELECTRICFLOWTREATMENTDEVICE", "IFCSOLARDEVICE", "IFCELECTRICALELEMENT"], "Electrical Equipment", Discipline::Mep),
+    (&["IFCSIGNAL"], "Signals", Discipline::Civil), // railway signals are flow terminals
+    (&["IFCENERGYCONVERSIONDEVICE", "IFCFLOWMOVINGDEVICE", "IFCFLOWSTORAGEDEVICE", "IFCFLOWTREATMENTDEVICE", "IFCSPACEHEATER", "IFCEQUIPMENTELEMENT"], "Mechanical Equipment", Discipline::Mep),
+    (&["IFCFLOWTERMINAL"], "Flow Terminals", Discipline::Mep),
+    (&["IFCDUCTSEGMENT"], "Ducts", Discipline::Mep),
+    (&["IFCDUCTFITTING"], "Duct Fittings", Discipline::Mep),
+    (&["IFCPIPESEGMENT"], "Pipes", Discipline::Mep),
+    (&["IFCPIPEFITTING"], "Pipe Fittings", Discipline::Mep),
+    (&["IFCCABLECARRIERSEGMENT", "IFCCABLECARRIERFITTING"], "Cable Trays", Discipline::Mep),
+    (&["IFCCABLESEGMENT", "IFCCABLEFITTING"], "Cables", Discipline::Mep),
+    (&["IFCTRANSPORTELEMENT", "IFCTRANSPORTATIONDEVICE", "IFCCONVEYORSEGMENT"], "Transport", Discipline::Mep),
+    (&["IFCVALVE"], "Valves", Discipline::Mep),
+    (&["IFCFLOWCONTROLLER"], "Flow Controllers", Discipline::Mep),
+    (&["IFCFLOWSEGMENT"], "Flow Segments", Discipline::Mep),
+    (&["IFCFLOWFITTING"], "Flow Fittings", Discipline::Mep),
+    (&["IFCDISTRIBUTIONCONTROLELEMENT"], "Controls", Discipline::Mep),
+    (&["IFCDISTRIBUTIONCHAMBERELEMENT"], "Distribution Chambers", Discipline::Mep),
+    (&["IFCDISTRIBUTIONELEMENT"], "Distribution Elements", Discipline::Mep),
+    // Civil (IFC4x3 infrastructure)
+    (&["IFCPAVEMENT", "IFCCOURSE"], "Pavements", Discipline::Civil),
+    (&["IFCKERB"], "Kerbs", Discipline::Civil),
+    (&["IFCRAIL", "IFCTRACKELEMENT"], "Tracks", Discipline::Civil),
+    (&["IFCMOORINGDEVICE", "IFCNAVIGATIONELEMENT"], "Marine Elements", Discipline::Civil),
+    (&["IFCEARTHWORKSELEMENT"], "Earthworks", Discipline::Civil),
+    (&["IFCGEOTECHNICALELEMENT"], "Geotechnical Elements", Discipline::Civil),
+    (&["IFCSIGN"], "Signs", Discipline::Civil),
+    (&["IFCIMPACTPROTECTIONDEVICE"], "Impact Protection", Discipline::Civil),
+    (&["IFCGEOGRAPHICELEMENT"], "Geographic Elements", Discipline::Civil),
+    (&["IFCCIVILELEMENT"], "Civil Elements", Discipline::Civil),
+    // Other, down to IFCELEMENT itself so that every element has a category
+    (&["IFCBUILDINGELEMENTPROXY"], "Proxies", Discipline::Other),
+    (&["IFCELEMENTASSEMBLY"], "Assemblies", Discipline::Other),
+    (&["IFCDISCRETEACCESSORY", "IFCVIBRATIONISOLATOR", "IFCVIBRATIONDAMPER", "IFCELEMENTCOMPONENT", "IFCBUILDINGELEMENTCOMPONENT"], "Accessories", Discipline::Other),
+    (&["IFCOPENINGELEMENT", "IFCFEATUREELEMENTSUBTRACTION"], "Openings", Discipline::Other),
+    (&["IFCFEATUREELEMENT"], "Features", Discipline::Other),
+    (&["IFCVIRTUALELEMENT"], "Virtual Elements", Discipline::Other),
+    (&["IFCBUILDINGELEMENT", "IFCBUILTELEMENT", "IFCELEMENT"], "Other", Discipline::Other),
 ];
 
+/// Suffix of the synthetic entries for instances without a type object
+const UNTYPED: &str = "(untyped)";
+
+/// Spatial structures are a handful of levels deep; nodes nested deeper
+/// (only in broken or hostile files) start a new root instead.
+const MAX_SPATIAL_DEPTH: usize = 32;
+
+/// Assemblies nest a few levels (curtain wall → panel, stair → flight);
+/// longer or cyclic chains of parts are not followed further.
+const MAX_DECOMPOSITION_DEPTH: usize = 32;
+
 /// Parses an IFC file and extracts project structure.
 ///
-/// Supports both IFC2x3 and IFC4 schemas. Extracts:
-/// - Project metadata (name, schema version)
-/// - Building storeys with elevations
+/// Supports both IFC2x3 and IFC4 schemas, in STEP or ifcXML format (picked
+/// from the content). `.ifczip` archives and gzip-compressed files are detected
+/// from their first bytes and decompressed transparently; for an archive the
+/// first model is read (see [`parse_ifc_model`]). Extracts:
+/// - Project metadata (name, schema version, file header)
+/// - The spatial structure (sites, buildings, storeys, spaces)
+/// - Building storeys with elevations, grouped by building
+/// - Spaces with area and volume, and the elements in or around them
 /// - Element types organized by category (Walls, Doors, Windows, etc.)
 /// - Type-to-instance relationships
 /// - Property sets
@@ -51,6 +128,7 @@ const ELEMENT_TYPES: &[(&str, &[&str])] = &[
 /// # Errors
 ///
 /// Returns [`ParseError::FileRead`] if the file cannot be read.
+/// Returns [`ParseError::Archive`] if a zip archive is corrupt.
 /// Returns [`ParseError::InvalidStep`] if the STEP format is malformed.
 ///
 /// # Example
@@ -65,25 +143,263 @@ const ELEMENT_TYPES: &[(&str, &[&str])] = &[
 /// # Ok::<(), ifc_inspector::error::ParseError>(())
 /// ```
 pub fn parse_ifc_file<P: AsRef<Path>>(path: P) -> Result<IfcProject, ParseError> {
-    let content = std::fs::read_to_string(&path).map_err(|source| ParseError::FileRead {
-        path: path.as_ref().to_path_buf(),
-        source,
-    })?;
+    parse_ifc_file_with_options(path, ParseOptions::default())
+}
 
-    let step_file = StepFile::parse(&content)?;
+/// Parses an IFC file like [`parse_ifc_file`], with explicit [`ParseOptions`].
+///
+/// Malformed entities are skipped and listed in [`IfcProject::diagnostics`].
+/// With `strict` set, the first one is returned as [`ParseError::InvalidStep`].
+pub fn parse_ifc_file_with_options<P: AsRef<Path>>(
+    path: P,
+    options: ParseOptions,
+) -> Result<IfcProject, ParseError> {
+    parse_ifc_model(path, None, options)
+}
 
-    let project_name = extract_project_name(&step_file);
-    let file_path = path.as_ref().to_string_lossy().to_string();
+/// Parses the model `model` of an `.ifczip` archive (the first one if `None`).
+///
+/// Plain and gzip-compressed files hold a single model and ignore `model`.
+/// The names of all models in the archive are listed in
+/// [`IfcProject::archive_models`].
+///
+/// # Errors
+///
+/// Returns [`ParseError::ModelNotFound`] if the archive has no model named
+/// `model`, and [`ParseError::EmptyArchive`] if it has no model at all.
+pub fn parse_ifc_model<P: AsRef<Path>>(
+    path: P,
+    model: Option<&str>,
+    options: ParseOptions,
+) -> Result<IfcProject, ParseError> {
+    parse_model(path.as_ref(), model, options, &ProgressSink::none())
+}
 
-    let mut project = IfcProject::new(project_name, step_file.schema.clone(), file_path);
+/// Parses like [`parse_ifc_model`], reporting progress and allowing the parse
+/// to be cancelled.
+///
+/// `progress` is called after each chunk of entities is read and when each
+/// extraction phase starts. It can be called from several threads at once.
+/// Once `cancel` is cancelled, the parse stops at the next chunk or phase.
+///
+/// # Errors
+///
+/// Returns [`ParseError::Cancelled`] if the parse was cancelled, and the
+/// errors of [`parse_ifc_model`] otherwise.
+///
+/// # Example
+///
+/// ```no_run
+/// use ifc_inspector::parser::{parse_ifc_model_with_progress, CancellationToken, ParseOptions};
+///
+/// let cancel = CancellationToken::new();
+/// let project = parse_ifc_model_with_progress(
+///     "model.ifc",
+///     None,
+///     ParseOptions::default(),
+///     |progress| eprintln!("{}: {:.0}%", progress.phase, progress.fraction() * 100.0),
+///     &cancel,
+/// )?;
+/// # Ok::<(), ifc_inspector::error::ParseError>(())
+/// ```
+pub fn parse_ifc_model_with_progress<P: AsRef<Path>>(
+    path: P,
+    model: Option<&str>,
+    options: ParseOptions,
+    progress: impl Fn(Progress) + Sync,
+    cancel: &CancellationToken,
+) -> Result<IfcProject, ParseError> {
+    let path = path.as_ref();
+    let total_bytes = std::fs::metadata(path)
+        .map_err(|source| ParseError::FileRead {
+            path: path.to_path_buf(),
+            source,
+        })?
+        .len();
+    let sink = ProgressSink::new(&progress, cancel, total_bytes);
+    parse_model(path, model, options, &sink)
+}
 
-    // Extract storeys
-    project.storeys = extract_storeys(&step_file);
+fn parse_model(
+    path: &Path,
+    model: Option<&str>,
+    options: ParseOptions,
+    progress: &ProgressSink<'_>,
+) -> Result<IfcProject, ParseError> {
+    if options.memory_map && is_plain_step(path)? {
+        let step_file = MappedStepFile::open_with_progress(path, options, progress)?;
+        return extract_project(&step_file, path, progress);
+    }
 
-    // Extract spatial containment (element → storey)
-    let element_to_storey = extract_spatial_containment(&step_file);
+    let (step_file, archive) = read_model(path, model, options, progress)?;
+    let mut project = extract_project(&step_file, path, progress)?;
+    if let Some(archive) = archive {
+        project.model = Some(archive.selected);
+        project.archive_models = archive.models;
+    }
+    Ok(project)
+}
 
-    // Count elements per storey
+/// Checks the model `model` of the file at `path` (opened like
+/// [`parse_ifc_model`]) against an EXPRESS schema, see [`validate`].
+///
+/// # Example
+///
+/// ```no_run
+/// use ifc_inspector::parser::{validate_ifc_model, ParseOptions};
+/// use ifc_inspector::schema::ExpressSchema;
+///
+/// let schema = ExpressSchema::from_file("IFC4.exp")?;
+/// let violations = validate_ifc_model("model.ifc", None, ParseOptions::default(), &schema)?;
+/// for violation in &violations {
+///     println!("{violation}");
+/// }
+/// # Ok::<(), ifc_inspector::error::ParseError>(())
+/// ```
+pub fn validate_ifc_model<P: AsRef<Path>>(
+    path: P,
+    model: Option<&str>,
+    options: ParseOptions,
+    schema: &ExpressSchema,
+) -> Result<Vec<Violation>, ParseError> {
+    let path = path.as_ref();
+    let progress = ProgressSink::none();
+    if options.memory_map && is_plain_step(path)? {
+        let step_file = MappedStepFile::open_with_progress(path, options, &progress)?;
+        return Ok(validate(&step_file, schema));
+    }
+    let (step_file, _) = read_model(path, model, options, &progress)?;
+    Ok(validate(&step_file, schema))
+}
+
+/// Read a model into memory, from STEP or ifcXML.
+fn read_model(
+    path: &Path,
+    model: Option<&str>,
+    options: ParseOptions,
+    progress: &ProgressSink<'_>,
+) -> Result<(StepFile, Option<ArchiveModels>), ParseError> {
+    // Stream the file statement by statement instead of holding the raw text
+    let bytes_read = progress.bytes_read();
+    with_model_reader(path, model, bytes_read, |reader, archive| {
+        // The format is decided by the content: ifcXML starts with '<'
+        let step_file = if is_xml(reader) {
+            let step_file = StepFile::from_xml_reader_with_options(reader, options)?;
+            progress.reading(step_file.entities.len())?;
+            step_file
+        } else {
+            StepFile::from_reader_with_progress(reader, options, progress)?
+        };
+        Ok((step_file, archive))
+    })
+    .map_err(|e| match e {
+        ParseError::Read { source } => ParseError::FileRead {
+            path: path.to_path_buf(),
+            source,
+        },
+        other => other,
+    })
+}
+
+/// Build the project from the parsed entities, held in memory or mapped.
+fn extract_project<S: EntitySource>(
+    step_file: &S,
+    path: &Path,
+    progress: &ProgressSink<'_>,
+) -> Result<IfcProject, ParseError> {
+    let project_name = extract_project_name(step_file);
+    let file_path = path.to_string_lossy().to_string();
+
+    let mut project = IfcProject::new(project_name, step_file.schema().to_string(), file_path);
+    project.header = step_file.header().clone();
+    project.diagnostics = step_file.diagnostics().to_vec();
+    project.skipped_entities = step_file.skipped_entities();
+
+    // The extraction passes only read the entities, so they run in parallel
+    let ((structure, (containment, space_data)), (type_to_instances, property_sets)) = rayon::join(
+        || {
+            rayon::join(
+                || {
+                    progress.phase(ParsePhase::Storeys, || {
+                        rayon::join(
+                            || extract_storeys(step_file),
+                            || extract_spatial_structure(step_file),
+                        )
+                    })
+                },
+                || {
+                    rayon::join(
+                        || {
+                            progress.phase(ParsePhase::SpatialContainment, || {
+                                rayon::join(
+                                    || extract_spatial_containment(step_file),
+                                    || extract_decomposition(step_file),
+                                )
+                            })
+                        },
+                        || {
+                            progress.phase(ParsePhase::Spaces, || {
+                                rayon::join(
+                                    || extract_spaces(step_file),
+                                    || extract_space_boundaries(step_file),
+                                )
+                            })
+                        },
+                    )
+                },
+            )
+        },
+        || {
+            rayon::join(
+                || {
+                    progress.phase(ParsePhase::TypeRelationships, || {
+                        extract_type_relationships(step_file)
+                    })
+                },
+                || {
+                    progress.phase(ParsePhase::PropertySets, || {
+                        extract_property_sets(step_file)
+                    })
+                },
+            )
+        },
+    );
+    let (
+        Some((storeys, spatial)),
+        Some((contained_in, parts_of)),
+        Some((spaces, space_boundaries)),
+        Some(type_to_instances),
+        Some((element_properties, property_measures)),
+    ) = (
+        structure,
+        containment,
+        space_data,
+        type_to_instances,
+        property_sets,
+    )
+    else {
+        return Err(ParseError::Cancelled);
+    };
+    project.storeys = storeys;
+    project.spatial = spatial;
+    project.property_measures = property_measures;
+
+    // Parts of assemblies and elements in spaces are on the storey above
+    // the structure they (or their assembly) are contained in
+    let node_storeys: HashMap<u64, Option<u64>> = spatial_order(&project.spatial)
+        .into_iter()
+        .map(|(node, _, storey)| (node.id, storey))
+        .collect();
+    let element_to_structure = resolve_containment(&contained_in, &parts_of, &node_storeys);
+    let element_to_storey: HashMap<u64, u64> = element_to_structure
+        .iter()
+        .map(|(&element_id, &structure_id)| {
+            let storey_id = node_storeys.get(&structure_id).copied().flatten();
+            (element_id, storey_id.unwrap_or(structure_id))
+        })
+        .collect();
+
+    // Count elements per storey (and per spatial node they are contained in)
     let mut storey_counts: HashMap<u64, usize> = HashMap::new();
     for storey_id in element_to_storey.values() {
         *storey_counts.entry(*storey_id).or_insert(0) += 1;
@@ -91,6 +407,36 @@ pub fn parse_ifc_file<P: AsRef<Path>>(path: P) -> Result<IfcProject, ParseError>
     for storey in &mut project.storeys {
         storey.element_count = storey_counts.get(&storey.id).copied().unwrap_or(0);
     }
+    let mut structure_counts: HashMap<u64, usize> = HashMap::new();
+    for structure_id in element_to_structure.values() {
+        *structure_counts.entry(*structure_id).or_insert(0) += 1;
+    }
+    for root in &mut project.spatial {
+        set_element_counts(root, &structure_counts);
+    }
+
+    // Link elements to the spaces they are contained in or bound
+    let mut element_to_spaces: HashMap<u64, Vec<u64>> = HashMap::new();
+    let space_ids: HashSet<u64> = spaces.iter().map(|space| space.id).collect();
+    for (&element_id, structure_id) in &element_to_structure {
+        if space_ids.contains(structure_id) {
+            element_to_spaces
+                .entry(element_id)
+                .or_default()
+                .push(*structure_id);
+        }
+    }
+    for (element_id, bounded) in space_boundaries {
+        let linked = element_to_spaces.entry(element_id).or_default();
+        for space_id in bounded {
+            if space_ids.contains(&space_id) && !linked.contains(&space_id) {
+                linked.push(space_id);
+            }
+        }
+    }
+    element_to_spaces.retain(|_, spaces| !spaces.is_empty());
+    project.spaces = spaces;
+    project.element_to_spaces = element_to_spaces;
 
     // Store element_to_storey map in project for UI filtering
     project.element_to_storey = element_to_storey;
@@ -101,30 +447,112 @@ pub fn parse_ifc_file<P: AsRef<Path>>(path: P) -> Result<IfcProject, ParseError>
             .partial_cmp(&a.elevation)
             .unwrap_or(std::cmp::Ordering::Equal)
     });
+    // ... and group them by building, in spatial tree order
+    let tree = spatial_order(&project.spatial);
+    let storey_order: HashMap<u64, (usize, Option<u64>)> = tree
+        .iter()
+        .filter(|(node, ..)| node.kind == SpatialKind::Storey)
+        .enumerate()
+        .map(|(order, &(node, building, _))| (node.id, (order, building)))
+        .collect();
+    for storey in &mut project.storeys {
+        storey.building_id = storey_order
+            .get(&storey.id)
+            .and_then(|&(_, building)| building);
+    }
+    project.storeys.sort_by_key(|storey| {
+        storey_order
+            .get(&storey.id)
+            .map_or(usize::MAX, |&(order, _)| order)
+    });
 
-    // Extract type-instance relationships
-    let type_to_instances = extract_type_relationships(&step_file);
-
-    // Extract property sets
-    let element_properties = extract_property_sets(&step_file);
+    // Spaces: parent storey, quantities missing from the quantity sets, and
+    // the number of linked elements
+    let mut space_counts: HashMap<u64, usize> = HashMap::new();
+    for space_id in project.element_to_spaces.values().flatten() {
+        *space_counts.entry(*space_id).or_insert(0) += 1;
+    }
+    for space in &mut project.spaces {
+        space.storey_id = node_storeys.get(&space.id).copied().flatten();
+        space.element_count = space_counts.get(&space.id).copied().unwrap_or(0);
+        if let Some(properties) = element_properties.get(&space.id) {
+            let property = |names: &[&str]| {
+                names
+                    .iter()
+                    .find_map(|name| properties.get(*name).and_then(|v| parse_number(v)))
+            };
+            space.area = space
+                .area
+                .or_else(|| property(&["NetFloorArea", "GrossFloorArea", "Area"]));
+            space.volume = space
+                .volume
+                .or_else(|| property(&["NetVolume", "GrossVolume", "Volume"]));
+        }
+    }
+    let storey_position: HashMap<u64, usize> = project
+        .storeys
+        .iter()
+        .enumerate()
+        .map(|(i, storey)| (storey.id, i))
+        .collect();
+    project.spaces.sort_by(|a, b| {
+        let position = |space: &Space| {
+            space
+                .storey_id
+                .and_then(|id| storey_position.get(&id).copied())
+                .unwrap_or(usize::MAX)
+        };
+        position(a)
+            .cmp(&position(b))
+            .then_with(|| a.number.cmp(&b.number))
+            .then(a.id.cmp(&b.id))
+    });
 
-    // Build categories
-    project.categories = build_categories(&step_file, &type_to_instances, &element_properties);
+    // Build categories and extract GlobalIds for all instances
+    progress.check()?;
+    project.categories = progress
+        .phase(ParsePhase::Categories, || {
+            build_categories(step_file, &type_to_instances, &element_properties)
+        })
+        .unwrap_or_default();
+    progress.check()?;
+    project.instance_global_ids = extract_instance_global_ids(step_file, &project.categories);
 
     // Store element properties for instance-level property lookup
     project.element_properties = element_properties;
 
-    // Extract GlobalIds for all instances
-    project.instance_global_ids = extract_instance_global_ids(&step_file, &type_to_instances);
-
     Ok(project)
 }
 
-fn extract_project_name(step_file: &StepFile) -> String {
+/// Whether the file is an uncompressed STEP file, which can be memory-mapped.
+fn is_plain_step(path: &Path) -> Result<bool, ParseError> {
+    let mut reader = std::io::BufReader::new(std::fs::File::open(path).map_err(|source| {
+        ParseError::FileRead {
+            path: path.to_path_buf(),
+            source,
+        }
+    })?);
+    let plain = reader
+        .fill_buf()
+        .is_ok_and(|magic| Container::detect(magic) == Container::Plain);
+    Ok(plain && !is_xml(&mut reader))
+}
+
+/// Whether the decompressed model is XML (ifcXML) rather than STEP.
+fn is_xml(reader: &mut dyn BufRead) -> bool {
+    reader
+        .fill_buf()
+        .ok()
+        .and_then(|buf| buf.iter().find(|b| !b.is_ascii_whitespace()))
+        .is_some_and(|&b| b == b'<')
+}
+
+fn extract_project_name<S: EntitySource>(step_file: &S) -> String {
     step_file
-        .get_entities_by_type("IFCPROJECT")
-        .first()
-        .and_then(|e| e.values.get(2))
+        .entities_by_type("IFCPROJECT")
+        .next()
+        .as_ref()
+        .and_then(|e| e.get_attr("Name"))
         .and_then(|v| match v {
             StepValue::String(s) => Some(s.clone()),
             _ => None,
@@ -132,14 +560,12 @@ fn extract_project_name(step_file: &StepFile) -> String {
         .unwrap_or_else(|| "Unknown Project".to_string())
 }
 
-fn extract_storeys(step_file: &StepFile) -> Vec<Storey> {
+fn extract_storeys<S: EntitySource>(step_file: &S) -> Vec<Storey> {
     step_file
-        .get_entities_by_type("IFCBUILDINGSTOREY")
-        .iter()
+        .entities_by_type("IFCBUILDINGSTOREY")
         .map(|e| {
             let name = e
-                .values
-                .get(2)
+                .get_attr("Name")
                 .and_then(|v| match v {
                     StepValue::String(s) => Some(s.clone()),
                     _ => None,
@@ -147,8 +573,7 @@ fn extract_storeys(step_file: &StepFile) -> Vec<Storey> {
                 .unwrap_or_else(|| format!("Storey #{}", e.id));
 
             let elevation = e
-                .values
-                .get(9)
+                .get_attr("Elevation")
                 .and_then(|v| match v {
                     StepValue::Real(f) => Some(*f),
                     _ => None,
@@ -160,21 +585,177 @@ fn extract_storeys(step_file: &StepFile) -> Vec<Storey> {
                 name,
                 elevation,
                 element_count: 0,
+                building_id: None,
             }
         })
         .collect()
 }
 
-/// Extract element → storey relationships from IFCRELCONTAINEDINSPATIALSTRUCTURE
-fn extract_spatial_containment(step_file: &StepFile) -> HashMap<u64, u64> {
-    let mut element_to_storey: HashMap<u64, u64> = HashMap::new();
+/// Build the spatial structure tree from IFCRELAGGREGATES: the project and
+/// every spatial structure element below it, storeys highest first.
+/// Elements whose parent is missing become roots of their own.
+fn extract_spatial_structure<S: EntitySource>(step_file: &S) -> Vec<SpatialNode> {
+    let version = step_file.schema_version();
+    let mut nodes: HashMap<u64, SpatialNode> = step_file
+        .entities_by_type("IFCPROJECT")
+        .chain(step_file.entities_by_supertype("IFCSPATIALSTRUCTUREELEMENT"))
+        .map(|e| {
+            let kind = spatial_kind(version, &e.entity_type);
+            let name = e
+                .get_attr("Name")
+                .and_then(|v| match v {
+                    StepValue::String(s) => Some(s.clone()),
+                    _ => None,
+                })
+                .unwrap_or_else(|| format!("{kind:?} #{}", e.id));
+            let elevation = e.get_attr("Elevation").and_then(|v| match v {
+                StepValue::Real(f) => Some(*f),
+                _ => None,
+            });
+            let node = SpatialNode {
+                id: e.id,
+                entity_type: e.entity_type.clone(),
+                kind,
+                name,
+                elevation: elevation.filter(|_| kind == SpatialKind::Storey),
+                element_count: 0,
+                children: Vec::new(),
+            };
+            (e.id, node)
+        })
+        .collect();
+
+    // Parent → children, between spatial nodes only (elements are aggregated too)
+    let mut children: HashMap<u64, Vec<u64>> = HashMap::new();
+    let mut has_parent: HashSet<u64> = HashSet::new();
+    for rel in step_file.entities_by_type("IFCRELAGGREGATES") {
+        let parent = match rel.get_attr("RelatingObject") {
+            Some(StepValue::Reference(id)) if nodes.contains_key(id) => *id,
+            _ => continue,
+        };
+        if let Some(StepValue::List(list)) = rel.get_attr("RelatedObjects") {
+            for item in list {
+                if let StepValue::Reference(id) = item {
+                    if nodes.contains_key(id) && *id != parent {
+                        children.entry(parent).or_default().push(*id);
+                        has_parent.insert(*id);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut pending: Vec<u64> = nodes
+        .keys()
+        .copied()
+        .filter(|id| !has_parent.contains(id))
+        .collect();
+    pending.sort_unstable();
+    let mut pending = VecDeque::from(pending);
+    let mut roots = Vec::new();
+    // Once the roots are placed, only nodes in an aggregation cycle are left
+    while let Some(id) = pending.pop_front().or_else(|| nodes.keys().min().copied()) {
+        if let Some(root) = build_spatial_node(id, 0, &mut nodes, &children, &mut pending) {
+            roots.push(root);
+        }
+    }
+    // The project first, then in file order
+    roots.sort_by_key(|root| (root.kind != SpatialKind::Project, root.id));
+    roots
+}
 
-    for rel in step_file.get_entities_by_type("IFCRELCONTAINEDINSPATIALSTRUCTURE") {
-        // Index 4 = RelatedElements (list of element refs)
-        // Index 5 = RelatingStructure (spatial element ref - storey)
+/// Take node `id` and its descendants out of `nodes`. Each node is placed
+/// once, under the first parent that reaches it; nodes below
+/// [`MAX_SPATIAL_DEPTH`] are queued in `pending` as new roots.
+fn build_spatial_node(
+    id: u64,
+    depth: usize,
+    nodes: &mut HashMap<u64, SpatialNode>,
+    children: &HashMap<u64, Vec<u64>>,
+    pending: &mut VecDeque<u64>,
+) -> Option<SpatialNode> {
+    let mut node = nodes.remove(&id)?;
+    for &child in children.get(&id).into_iter().flatten() {
+        if depth + 1 >= MAX_SPATIAL_DEPTH {
+            pending.push_back(child);
+        } else if let Some(child) = build_spatial_node(child, depth + 1, nodes, children, pending) {
+            node.children.push(child);
+        }
+    }
+    // Storeys highest first, then the rest in file order
+    node.children.sort_by(|a, b| {
+        a.elevation
+            .is_none()
+            .cmp(&b.elevation.is_none())
+            .then_with(|| {
+                b.elevation
+                    .unwrap_or(0.0)
+                    .total_cmp(&a.elevation.unwrap_or(0.0))
+            })
+            .then(a.id.cmp(&b.id))
+    });
+    Some(node)
+}
+
+fn spatial_kind(version: SchemaVersion, entity_type: &str) -> SpatialKind {
+    let is = |supertype| version.is_subtype_of(entity_type, supertype);
+    if is("IFCPROJECT") {
+        SpatialKind::Project
+    } else if is("IFCSITE") {
+        SpatialKind::Site
+    } else if is("IFCBUILDING") || is("IFCFACILITY") {
+        SpatialKind::Building
+    } else if is("IFCBUILDINGSTOREY") {
+        SpatialKind::Storey
+    } else if is("IFCSPACE") {
+        SpatialKind::Space
+    } else {
+        SpatialKind::Other
+    }
+}
+
+/// Set each node's count of directly contained elements.
+fn set_element_counts(node: &mut SpatialNode, counts: &HashMap<u64, usize>) {
+    node.element_count = counts.get(&node.id).copied().unwrap_or(0);
+    for child in &mut node.children {
+        set_element_counts(child, counts);
+    }
+}
+
+/// Spatial nodes in tree order, each with the building and storey it is in
+/// (itself, for a building or storey).
+fn spatial_order(roots: &[SpatialNode]) -> Vec<(&SpatialNode, Option<u64>, Option<u64>)> {
+    let mut nodes = Vec::new();
+    let mut stack: Vec<(&SpatialNode, Option<u64>, Option<u64>)> =
+        roots.iter().rev().map(|root| (root, None, None)).collect();
+    while let Some((node, building, storey)) = stack.pop() {
+        let building = match node.kind {
+            SpatialKind::Building => Some(node.id),
+            _ => building,
+        };
+        let storey = match node.kind {
+            SpatialKind::Storey => Some(node.id),
+            _ => storey,
+        };
+        nodes.push((node, building, storey));
+        stack.extend(
+            node.children
+                .iter()
+                .rev()
+                .map(|child| (child, building, storey)),
+        );
+    }
+    nodes
+}
+
+/// Extract element → spatial structure (storey, space, ...) relationships
+/// from IFCRELCONTAINEDINSPATIALSTRUCTURE
+fn extract_spatial_containment<S: EntitySource>(step_file: &S) -> HashMap<u64, u64> {
+    let mut element_to_structure: HashMap<u64, u64> = HashMap::new();
+
+    for rel in step_file.entities_by_type("IFCRELCONTAINEDINSPATIALSTRUCTURE") {
         let elements: Vec<u64> = rel
-            .values
-            .get(4)
+            .get_attr("RelatedElements")
             .and_then(|v| match v {
                 StepValue::List(list) => Some(
                     list.iter()
@@ -188,51 +769,242 @@ fn extract_spatial_containment(step_file: &StepFile) -> HashMap<u64, u64> {
             })
             .unwrap_or_default();
 
-        let storey_id = rel.values.get(5).and_then(|v| match v {
+        let structure_id = rel.get_attr("RelatingStructure").and_then(|v| match v {
             StepValue::Reference(id) => Some(*id),
             _ => None,
         });
 
-        if let Some(sid) = storey_id {
+        if let Some(sid) = structure_id {
             for elem_id in elements {
-                element_to_storey.insert(elem_id, sid);
+                element_to_structure.insert(elem_id, sid);
             }
         }
     }
 
-    element_to_storey
+    element_to_structure
 }
 
-fn extract_instance_global_ids(
-    step_file: &StepFile,
-    type_to_instances: &HashMap<u64, Vec<u64>>,
-) -> HashMap<u64, String> {
-    let mut global_ids = HashMap::new();
-
-    // Collect all instance IDs
-    for instances in type_to_instances.values() {
-        for &instance_id in instances {
-            if let Some(entity) = step_file.get_entity(instance_id) {
-                // GlobalId is always the first attribute (index 0) in IFC entities
-                if let Some(StepValue::String(global_id)) = entity.values.first() {
-                    global_ids.insert(instance_id, global_id.clone());
+/// Extract part → whole relationships from IFCRELAGGREGATES and IFCRELNESTS:
+/// curtain wall panels, stair flights, railings of a stair and so on.
+/// A part aggregated or nested twice keeps its first parent.
+fn extract_decomposition<S: EntitySource>(step_file: &S) -> HashMap<u64, u64> {
+    let mut part_to_whole: HashMap<u64, u64> = HashMap::new();
+
+    for rel in step_file
+        .entities_by_type("IFCRELAGGREGATES")
+        .chain(step_file.entities_by_type("IFCRELNESTS"))
+    {
+        let whole = match rel.get_attr("RelatingObject") {
+            Some(StepValue::Reference(id)) => *id,
+            _ => continue,
+        };
+        if let Some(StepValue::List(list)) = rel.get_attr("RelatedObjects") {
+            for item in list {
+                if let StepValue::Reference(id) = item {
+                    if *id != whole {
+                        part_to_whole.entry(*id).or_insert(whole);
+                    }
                 }
             }
         }
     }
 
-    global_ids
+    part_to_whole
+}
+
+/// The spatial structure each element is effectively in: where it is
+/// contained itself, else where the assembly it is part of is contained (or
+/// the spatial node that aggregates it directly). `spatial_nodes` maps the
+/// spatial structure to its storeys; its ids are not resolved as elements.
+fn resolve_containment(
+    contained_in: &HashMap<u64, u64>,
+    part_to_whole: &HashMap<u64, u64>,
+    spatial_nodes: &HashMap<u64, Option<u64>>,
+) -> HashMap<u64, u64> {
+    let mut resolved = contained_in.clone();
+    for &part in part_to_whole.keys() {
+        if resolved.contains_key(&part) || spatial_nodes.contains_key(&part) {
+            continue;
+        }
+        let mut current = part;
+        for _ in 0..MAX_DECOMPOSITION_DEPTH {
+            let Some(&whole) = part_to_whole.get(&current) else {
+                break;
+            };
+            if let Some(&structure) = contained_in.get(&whole) {
+                resolved.insert(part, structure);
+                break;
+            }
+            if spatial_nodes.contains_key(&whole) {
+                resolved.insert(part, whole);
+                break;
+            }
+            current = whole;
+        }
+    }
+    resolved
+}
+
+/// Extract spaces with their area and volume from IFCELEMENTQUANTITY sets
+/// (net values preferred over gross).
+fn extract_spaces<S: EntitySource>(step_file: &S) -> Vec<Space> {
+    let mut spaces: HashMap<u64, Space> = step_file
+        .entities_by_supertype("IFCSPACE")
+        .map(|e| {
+            let text = |name| match e.get_attr(name) {
+                Some(StepValue::String(s)) if !s.is_empty() => Some(s.clone()),
+                _ => None,
+            };
+            let space = Space {
+                id: e.id,
+                number: text("Name").unwrap_or_else(|| format!("Space #{}", e.id)),
+                long_name: text("LongName"),
+                area: None,
+                volume: None,
+                storey_id: None,
+                element_count: 0,
+            };
+            (e.id, space)
+        })
+        .collect();
+    if spaces.is_empty() {
+        return Vec::new();
+    }
+
+    for rel in step_file.entities_by_type("IFCRELDEFINESBYPROPERTIES") {
+        let Some(StepValue::Reference(set_id)) = rel.get_attr("RelatingPropertyDefinition") else {
+            continue;
+        };
+        let Some(StepValue::List(related)) = rel.get_attr("RelatedObjects") else {
+            continue;
+        };
+        let related: Vec<u64> = related
+            .iter()
+            .filter_map(|item| match item {
+                StepValue::Reference(id) if spaces.contains_key(id) => Some(*id),
+                _ => None,
+            })
+            .collect();
+        if related.is_empty() {
+            continue;
+        }
+        let Some(set) = step_file
+            .entity(*set_id)
+            .filter(|set| set.entity_type == "IFCELEMENTQUANTITY")
+        else {
+            continue;
+        };
+        let (area, volume) = space_quantities(step_file, &set);
+        for id in related {
+            if let Some(space) = spaces.get_mut(&id) {
+                space.area = area.or(space.area);
+                space.volume = volume.or(space.volume);
+            }
+        }
+    }
+
+    // Entity order, so spaces are listed the same way on every run
+    let mut spaces: Vec<Space> = spaces.into_values().collect();
+    spaces.sort_unstable_by_key(|space| space.id);
+    spaces
 }
 
-fn extract_type_relationships(step_file: &StepFile) -> HashMap<u64, Vec<u64>> {
+/// Floor area and volume from one quantity set.
+fn space_quantities<S: EntitySource>(
+    step_file: &S,
+    set: &StepEntity,
+) -> (Option<f64>, Option<f64>) {
+    let mut areas: Vec<(String, f64)> = Vec::new();
+    let mut volumes: Vec<(String, f64)> = Vec::new();
+    if let Some(StepValue::List(quantities)) = set.get_attr("Quantities") {
+        for quantity in quantities {
+            let StepValue::Reference(id) = quantity else {
+                continue;
+            };
+            let Some(quantity) = step_file.entity(*id) else {
+                continue;
+            };
+            let name = match quantity.get_attr("Name") {
+                Some(StepValue::String(s)) => s.clone(),
+                _ => String::new(),
+            };
+            let (list, value) = match quantity.entity_type.as_str() {
+                "IFCQUANTITYAREA" => (&mut areas, quantity.get_attr("AreaValue")),
+                "IFCQUANTITYVOLUME" => (&mut volumes, quantity.get_attr("VolumeValue")),
+                _ => continue,
+            };
+            match value {
+                Some(StepValue::Real(f)) => list.push((name, *f)),
+                Some(StepValue::Integer(i)) => list.push((name, *i as f64)),
+                _ => {}
+            }
+        }
+    }
+    let pick = |values: &[(String, f64)], preferred: &[&str]| {
+        preferred
+            .iter()
+            .find_map(|name| values.iter().find(|(n, _)| n == name))
+            .or_else(|| values.first())
+            .map(|&(_, value)| value)
+    };
+    (
+        pick(&areas, &["NetFloorArea", "GrossFloorArea"]),
+        pick(&volumes, &["NetVolume", "GrossVolume"]),
+    )
+}
+
+/// Extract element → bounded spaces from IFCRELSPACEBOUNDARY
+fn extract_space_boundaries<S: EntitySource>(step_file: &S) -> HashMap<u64, Vec<u64>> {
+    let mut element_to_spaces: HashMap<u64, Vec<u64>> = HashMap::new();
+
+    for rel in step_file.entities_by_supertype("IFCRELSPACEBOUNDARY") {
+        let space = rel.get_attr("RelatingSpace").and_then(|v| match v {
+            StepValue::Reference(id) => Some(*id),
+            _ => None,
+        });
+        let element = rel
+            .get_attr("RelatedBuildingElement")
+            .and_then(|v| match v {
+                StepValue::Reference(id) => Some(*id),
+                _ => None,
+            });
+
+        if let (Some(space_id), Some(element_id)) = (space, element) {
+            let spaces = element_to_spaces.entry(element_id).or_default();
+            if !spaces.contains(&space_id) {
+                spaces.push(space_id);
+            }
+        }
+    }
+
+    element_to_spaces
+}
+
+/// Extract the GlobalIds of all instances listed in the categories, typed or not
+fn extract_instance_global_ids<S: EntitySource>(
+    step_file: &S,
+    categories: &[Category],
+) -> HashMap<u64, String> {
+    categories
+        .par_iter()
+        .flat_map_iter(|category| &category.types)
+        .flat_map_iter(|element_type| element_type.instance_ids.iter().copied())
+        .filter_map(|instance_id| {
+            let entity = step_file.entity(instance_id)?;
+            match entity.get_attr("GlobalId") {
+                Some(StepValue::String(global_id)) => Some((instance_id, global_id.clone())),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn extract_type_relationships<S: EntitySource>(step_file: &S) -> HashMap<u64, Vec<u64>> {
     let mut type_to_instances: HashMap<u64, Vec<u64>> = HashMap::new();
 
-    for rel in step_file.get_entities_by_type("IFCRELDEFINESBYTYPE") {
-        // Index 4 = RelatedObjects (list of element refs)
-        // Index 5 = RelatingType (type ref)
+    for rel in step_file.entities_by_type("IFCRELDEFINESBYTYPE") {
         let instances: Vec<u64> = rel
-            .values
-            .get(4)
+            .get_attr("RelatedObjects")
             .and_then(|v| match v {
                 StepValue::List(list) => Some(
                     list.iter()
@@ -246,7 +1018,7 @@ fn extract_type_relationships(step_file: &StepFile) -> HashMap<u64, Vec<u64>> {
             })
             .unwrap_or_default();
 
-        let type_id = rel.values.get(5).and_then(|v| match v {
+        let type_id = rel.get_attr("RelatingType").and_then(|v| match v {
             StepValue::Reference(id) => Some(*id),
             _ => None,
         });
@@ -259,52 +1031,68 @@ fn extract_type_relationships(step_file: &StepFile) -> HashMap<u64, Vec<u64>> {
     type_to_instances
 }
 
-fn extract_property_sets(step_file: &StepFile) -> HashMap<u64, HashMap<String, String>> {
-    let mut element_properties: HashMap<u64, HashMap<String, String>> = HashMap::new();
-
-    // Build property set id -> properties map
-    let mut pset_props: HashMap<u64, HashMap<String, String>> = HashMap::new();
-
-    for pset in step_file.get_entities_by_type("IFCPROPERTYSET") {
-        let mut props = HashMap::new();
-
-        if let Some(StepValue::List(prop_refs)) = pset.values.get(4) {
-            for prop_ref in prop_refs {
-                if let StepValue::Reference(prop_id) = prop_ref {
-                    if let Some(prop) = step_file.get_entity(*prop_id) {
-                        if prop.entity_type == "IFCPROPERTYSINGLEVALUE" {
-                            let name = prop
-                                .values
-                                .first()
-                                .and_then(|v| match v {
-                                    StepValue::String(s) => Some(s.clone()),
-                                    _ => None,
-                                })
-                                .unwrap_or_default();
-
-                            let value = prop
-                                .values
-                                .get(2)
-                                .map(format_step_value)
-                                .unwrap_or_default();
-
-                            if !name.is_empty() {
-                                props.insert(name, value);
-                            }
+/// Element ID → property name → value (or measure type).
+type PropertyMap = HashMap<u64, HashMap<String, String>>;
+
+/// Properties per element, and the measure type of each of its properties
+/// (e.g. "NetArea" → "IFCAREAMEASURE").
+///
+/// Property values are flattened to strings for display, so the measures
+/// keep whether a number is a length, area, volume, ratio or count. They are
+/// merged like the values, so two property sets using the same name with
+/// different types do not mix, and read from the same entities, so a mapped
+/// file parses each property once.
+fn extract_property_sets<S: EntitySource>(step_file: &S) -> (PropertyMap, PropertyMap) {
+    // Build property set id -> properties and their measures
+    let pset_props: HashMap<u64, _> = step_file
+        .entity_ids_by_type("IFCPROPERTYSET")
+        .par_iter()
+        .filter_map(|&id| step_file.entity(id))
+        .map(|pset| {
+            let mut props = HashMap::new();
+            let mut measures = HashMap::new();
+
+            if let Some(StepValue::List(prop_refs)) = pset.get_attr("HasProperties") {
+                for prop_ref in prop_refs {
+                    let StepValue::Reference(prop_id) = prop_ref else {
+                        continue;
+                    };
+                    let Some(prop) = step_file.entity(*prop_id) else {
+                        continue;
+                    };
+                    if prop.entity_type != "IFCPROPERTYSINGLEVALUE" {
+                        continue;
+                    }
+
+                    let name = prop
+                        .get_attr("Name")
+                        .and_then(|v| match v {
+                            StepValue::String(s) => Some(s.clone()),
+                            _ => None,
+                        })
+                        .unwrap_or_default();
+
+                    let nominal_value = prop.get_attr("NominalValue");
+                    let value = nominal_value.map(format_step_value).unwrap_or_default();
+
+                    if !name.is_empty() {
+                        if let Some(type_name) = nominal_value.and_then(StepValue::type_name) {
+                            measures.insert(name.clone(), type_name.to_string());
                         }
+                        props.insert(name, value);
                     }
                 }
             }
-        }
 
-        pset_props.insert(pset.id, props);
-    }
+            (pset.id, (props, measures))
+        })
+        .collect();
 
-    // Link properties to elements via IFCRELDEFINESBYPROPERTIES
-    for rel in step_file.get_entities_by_type("IFCRELDEFINESBYPROPERTIES") {
+    // Link property sets to elements via IFCRELDEFINESBYPROPERTIES, in file order
+    let mut element_psets: HashMap<u64, Vec<u64>> = HashMap::new();
+    for rel in step_file.entities_by_type("IFCRELDEFINESBYPROPERTIES") {
         let elements: Vec<u64> = rel
-            .values
-            .get(4)
+            .get_attr("RelatedObjects")
             .and_then(|v| match v {
                 StepValue::List(list) => Some(
                     list.iter()
@@ -318,76 +1106,118 @@ fn extract_property_sets(step_file: &StepFile) -> HashMap<u64, HashMap<String, S
             })
             .unwrap_or_default();
 
-        let pset_id = rel.values.get(5).and_then(|v| match v {
-            StepValue::Reference(id) => Some(*id),
-            _ => None,
-        });
+        let pset_id = rel
+            .get_attr("RelatingPropertyDefinition")
+            .and_then(|v| match v {
+                StepValue::Reference(id) => Some(*id),
+                _ => None,
+            });
 
-        if let Some(pid) = pset_id {
-            if let Some(props) = pset_props.get(&pid) {
-                for elem_id in elements {
-                    element_properties
-                        .entry(elem_id)
-                        .or_default()
-                        .extend(props.clone());
-                }
+        if let Some(pid) = pset_id.filter(|pid| pset_props.contains_key(pid)) {
+            for elem_id in elements {
+                element_psets.entry(elem_id).or_default().push(pid);
             }
         }
     }
 
-    element_properties
+    // Merge each element's property sets (later sets override earlier ones,
+    // measure included)
+    let merged: Vec<_> = element_psets
+        .into_par_iter()
+        .map(|(elem_id, pset_ids)| {
+            let mut properties = HashMap::new();
+            let mut measures = HashMap::new();
+            for pid in pset_ids {
+                let (pset_values, pset_measures) = &pset_props[&pid];
+                for (name, value) in pset_values {
+                    properties.insert(name.clone(), value.clone());
+                    match pset_measures.get(name) {
+                        Some(measure) => measures.insert(name.clone(), measure.clone()),
+                        None => measures.remove(name),
+                    };
+                }
+            }
+            (elem_id, properties, measures)
+        })
+        .collect();
+
+    let mut element_properties = HashMap::with_capacity(merged.len());
+    let mut element_measures = HashMap::new();
+    for (elem_id, properties, measures) in merged {
+        element_properties.insert(elem_id, properties);
+        if !measures.is_empty() {
+            element_measures.insert(elem_id, measures);
+        }
+    }
+    (element_properties, element_measures)
 }
 
 fn format_step_value(value: &StepValue) -> String {
     match value {
-        StepValue::String(s) => s.clone(),
+        StepValue::String(s) | StepValue::Binary(s) => s.clone(),
         StepValue::Real(f) => format!("{f:.2}"),
         StepValue::Integer(i) => i.to_string(),
         StepValue::Boolean(b) => if *b { "Yes" } else { "No" }.to_string(),
         StepValue::Enum(e) => e.clone(),
         StepValue::Reference(id) => format!("#{id}"),
-        StepValue::List(list) => list
+        StepValue::List(list) | StepValue::Parameters(list) => list
             .iter()
             .map(format_step_value)
             .collect::<Vec<_>>()
             .join(", "),
+        StepValue::Typed { type_name, value } => match (type_name.as_str(), value.as_ref()) {
+            // Counts are sometimes written as reals (IFCCOUNTMEASURE(4.))
+            ("IFCCOUNTMEASURE", StepValue::Real(f)) => format!("{f:.0}"),
+            ("IFCLOGICAL", StepValue::Enum(e)) if e == "U" => "Unknown".to_string(),
+            _ => format_step_value(value),
+        },
         StepValue::Null => "-".to_string(),
         StepValue::Derived => "*".to_string(),
     }
 }
 
-fn build_categories(
-    step_file: &StepFile,
+/// The number at the start of a formatted property value ("12.50", "4500 mm").
+fn parse_number(value: &str) -> Option<f64> {
+    value.split_whitespace().next()?.parse().ok()
+}
+
+fn build_categories<S: EntitySource>(
+    step_file: &S,
     type_to_instances: &HashMap<u64, Vec<u64>>,
     element_properties: &HashMap<u64, HashMap<String, String>>,
 ) -> Vec<Category> {
     let mut categories: HashMap<String, Category> = HashMap::new();
-    let mut processed_type_ids: std::collections::HashSet<u64> = std::collections::HashSet::new();
-
-    // Process each element type mapping
-    for (element_entity, type_entities) in ELEMENT_TYPES {
-        let is_priority = PRIORITY_CATEGORIES.iter().any(|(e, _)| e == element_entity);
-
-        let category_name = PRIORITY_CATEGORIES
-            .iter()
-            .find(|(e, _)| e == element_entity)
-            .map_or_else(|| "Other".to_string(), |(_, name)| name.to_string());
-
+    // IFC2X3 space and other spatial types derive from IfcElementType but
+    // type no elements: treat them as processed so "Other" skips them
+    let mut processed_type_ids: HashSet<u64> = step_file
+        .entity_ids_by_supertype("IFCSPATIALSTRUCTUREELEMENTTYPE")
+        .into_iter()
+        .collect();
+
+    // Process each category
+    for &(elements, category_name, discipline) in CATEGORIES {
         // Deduplicate types by name within category
         let mut types_by_name: HashMap<String, ElementType> = HashMap::new();
+        let has_dimensions = elements
+            .iter()
+            .any(|e| matches!(*e, "IFCDOOR" | "IFCWINDOW"));
 
-        // Try each type entity (IFC4 types, then IFC2X3 styles)
-        for type_entity in *type_entities {
-            for type_entity_instance in step_file.get_entities_by_type(type_entity) {
-                // Skip if already processed (prevents duplicates in Other)
+        // Try each type entity (IFC4 types, then the IFC2X3 door and window
+        // styles)
+        let type_entities = elements.iter().flat_map(|e| {
+            let style = matches!(*e, "IFCDOOR" | "IFCWINDOW").then(|| format!("{e}STYLE"));
+            std::iter::once(format!("{e}TYPE")).chain(style)
+        });
+        for type_entity in type_entities {
+            for type_entity_instance in step_file.entities_by_supertype(&type_entity) {
+                // Skip if already processed (by a more specific category)
                 if processed_type_ids.contains(&type_entity_instance.id) {
                     continue;
                 }
                 processed_type_ids.insert(type_entity_instance.id);
 
                 let type_name = type_entity_instance
-                    .values
-                    .get(2)
+                    .get_attr("Name")
                     .and_then(|v| match v {
                         StepValue::String(s) => Some(s.clone()),
                         _ => None,
@@ -395,8 +1225,7 @@ fn build_categories(
                     .unwrap_or_else(|| format!("Type #{}", type_entity_instance.id));
 
                 let global_id = type_entity_instance
-                    .values
-                    .first()
+                    .get_attr("GlobalId")
                     .and_then(|v| match v {
                         StepValue::String(s) => Some(s.clone()),
                         _ => None,
@@ -426,23 +1255,10 @@ fn build_categories(
                 }
 
                 // Add dimension properties for doors/windows
-                let is_door_or_window = *type_entity == "IFCDOORTYPE"
-                    || *type_entity == "IFCDOORSTYLE"
-                    || *type_entity == "IFCWINDOWTYPE"
-                    || *type_entity == "IFCWINDOWSTYLE";
-
-                if is_door_or_window {
+                if has_dimensions {
                     // Get dimensions from first instance
                     if let Some(&first_instance) = instance_ids.first() {
-                        if let Some(instance) = step_file.get_entity(first_instance) {
-                            // For doors/windows: index 8 = height, index 9 = width
-                            if let Some(StepValue::Real(h)) = instance.values.get(8) {
-                                properties.insert("Height".to_string(), format!("{h:.0} mm"));
-                            }
-                            if let Some(StepValue::Real(w)) = instance.values.get(9) {
-                                properties.insert("Width".to_string(), format!("{w:.0} mm"));
-                            }
-                        }
+                        add_dimensions(step_file, first_instance, &mut properties);
                     }
                 }
 
@@ -459,10 +1275,11 @@ fn build_categories(
                         id: type_entity_instance.id,
                         global_id,
                         name: type_name.clone(),
-                        category: category_name.clone(),
+                        category: category_name.to_string(),
                         instance_count,
                         instance_ids,
                         properties,
+                        untyped: false,
                     };
                     types_by_name.insert(type_name, element_type);
                 }
@@ -470,35 +1287,298 @@ fn build_categories(
         }
 
         // Add deduplicated types to category
-        if !types_by_name.is_empty() {
-            let category = categories
-                .entry(category_name.clone())
-                .or_insert_with(|| Category {
-                    name: category_name.clone(),
-                    is_priority,
-                    types: Vec::new(),
-                    total_count: 0,
+        add_types(&mut categories, category_name, discipline, types_by_name);
+    }
+
+    // Instances without a type object, grouped by ObjectType (or Name) in a
+    // synthetic "(untyped)" entry, so every element of the file is counted
+    let mut seen: HashSet<u64> = categories
+        .values()
+        .flat_map(|category| &category.types)
+        .flat_map(|element_type| element_type.instance_ids.iter().copied())
+        .collect();
+    for &(elements, category_name, discipline) in CATEGORIES {
+        let mut untyped_by_name: HashMap<String, ElementType> = HashMap::new();
+
+        // Only the unseen IDs are parsed, which matters for mapped files
+        let ids: Vec<u64> = elements
+            .iter()
+            .flat_map(|e| step_file.entity_ids_by_supertype(e))
+            .filter(|&id| seen.insert(id))
+            .collect();
+        for instance in ids.into_iter().filter_map(|id| step_file.entity(id)) {
+            let label =
+                ["ObjectType", "Name"]
+                    .iter()
+                    .find_map(|attr| match instance.get_attr(attr) {
+                        Some(StepValue::String(s)) if !s.trim().is_empty() => Some(s.clone()),
+                        _ => None,
+                    });
+            let name = label.map_or_else(|| UNTYPED.to_string(), |l| format!("{l} {UNTYPED}"));
+            let element_type = untyped_by_name
+                .entry(name.clone())
+                .or_insert_with(|| ElementType {
+                    id: 0,
+                    global_id: String::new(),
+                    name,
+                    category: category_name.to_string(),
+                    instance_count: 0,
+                    instance_ids: Vec::new(),
+                    properties: HashMap::new(),
+                    untyped: true,
                 });
+            element_type.instance_count += 1;
+            element_type.instance_ids.push(instance.id);
+        }
 
-            for element_type in types_by_name.into_values() {
-                category.total_count += element_type.instance_count;
-                category.types.push(element_type);
+        // Properties of the first instance, as for typed instances
+        for element_type in untyped_by_name.values_mut() {
+            let first_instance = element_type.instance_ids[0];
+            if let Some(instance_props) = element_properties.get(&first_instance) {
+                element_type.properties.clone_from(instance_props);
+            }
+            if elements
+                .iter()
+                .any(|e| matches!(*e, "IFCDOOR" | "IFCWINDOW"))
+            {
+                add_dimensions(step_file, first_instance, &mut element_type.properties);
             }
         }
+
+        add_types(&mut categories, category_name, discipline, untyped_by_name);
     }
 
-    // Sort: priority categories first, then alphabetically
+    // Sort: by discipline, priority categories first within each, then alphabetically
     let mut result: Vec<Category> = categories.into_values().collect();
-    result.sort_by(|a, b| match (a.is_priority, b.is_priority) {
-        (true, false) => std::cmp::Ordering::Less,
-        (false, true) => std::cmp::Ordering::Greater,
-        _ => a.name.cmp(&b.name),
+    result.sort_by(|a, b| {
+        a.discipline
+            .cmp(&b.discipline)
+            .then(b.is_priority.cmp(&a.is_priority))
+            .then_with(|| a.name.cmp(&b.name))
     });
 
-    // Sort types within each category by name
+    // Sort types within each category by name, untyped entries last
     for category in &mut result {
-        category.types.sort_by(|a, b| a.name.cmp(&b.name));
+        category
+            .types
+            .sort_by(|a, b| a.untyped.cmp(&b.untyped).then_with(|| a.name.cmp(&b.name)));
     }
 
     result
 }
+
+/// Add types to their category, creating it on first use
+fn add_types(
+    categories: &mut HashMap<String, Category>,
+    category_name: &str,
+    discipline: Discipline,
+    types: HashMap<String, ElementType>,
+) {
+    if types.is_empty() {
+        return;
+    }
+    let category = categories
+        .entry(category_name.to_string())
+        .or_insert_with(|| Category {
+            name: category_name.to_string(),
+            discipline,
+            is_priority: PRIORITY_CATEGORIES.contains(&category_name),
+            types: Vec::new(),
+            total_count: 0,
+        });
+    for element_type in types.into_values() {
+        category.total_count += element_type.instance_count;
+        category.types.push(element_type);
+    }
+}
+
+/// Add the overall height and width of a door or window instance
+fn add_dimensions<S: EntitySource>(
+    step_file: &S,
+    instance_id: u64,
+    properties: &mut HashMap<String, String>,
+) {
+    if let Some(instance) = step_file.entity(instance_id) {
+        if let Some(StepValue::Real(h)) = instance.get_attr("OverallHeight") {
+            properties.insert("Height".to_string(), format!("{h:.0} mm"));
+        }
+        if let Some(StepValue::Real(w)) = instance.get_attr("OverallWidth") {
+            properties.insert("Width".to_string(), format!("{w:.0} mm"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{export_csv, export_json};
+    use pretty_assertions::assert_eq;
+    use std::fmt::Write as _;
+    use std::path::PathBuf;
+
+    /// A model large enough to be parsed in several chunks, with spaces that
+    /// share a number and walls that bound several spaces.
+    fn fixture() -> String {
+        let mut content = String::from(
+            "ISO-10303-21;\nHEADER;\nFILE_DESCRIPTION((''),'2;1');\n\
+             FILE_NAME('fixture.ifc','',(''),(''),'','','');\nFILE_SCHEMA(('IFC4'));\nENDSEC;\nDATA;\n\
+             #1=IFCPROJECT('p',$,'Determinism',$,$,$,$,$,$);\n\
+             #2=IFCSITE('s',$,'Site',$,$,$,$,$,.ELEMENT.,$,$,$,$,$);\n\
+             #3=IFCBUILDING('b',$,'Main',$,$,$,$,$,.ELEMENT.,$,$,$);\n\
+             #4=IFCBUILDINGSTOREY('s0',$,'Level 0',$,$,$,$,$,.ELEMENT.,0.);\n\
+             #5=IFCBUILDINGSTOREY('s1',$,'Level 1',$,$,$,$,$,.ELEMENT.,3000.);\n\
+             #6=IFCRELAGGREGATES('a1',$,$,$,#1,(#2));\n\
+             #7=IFCRELAGGREGATES('a2',$,$,$,#2,(#3));\n\
+             #8=IFCRELAGGREGATES('a3',$,$,$,#3,(#5,#4));\n\
+             #10=IFCSPACE('sp0',$,'101',$,$,$,$,'Office',.ELEMENT.,.INTERNAL.,$);\n\
+             #11=IFCSPACE('sp1',$,'101',$,$,$,$,'Office',.ELEMENT.,.INTERNAL.,$);\n\
+             #12=IFCSPACE('sp2',$,'101',$,$,$,$,'Kitchen',.ELEMENT.,.INTERNAL.,$);\n\
+             #13=IFCSPACE('sp3',$,'102',$,$,$,$,'Meeting',.ELEMENT.,.INTERNAL.,$);\n\
+             #14=IFCSPACE('sp4',$,'101',$,$,$,$,$,.ELEMENT.,.INTERNAL.,$);\n\
+             #15=IFCRELAGGREGATES('a4',$,$,$,#4,(#11,#10));\n\
+             #16=IFCRELAGGREGATES('a5',$,$,$,#5,(#14,#13,#12));\n\
+             #20=IFCWALLTYPE('wt0',$,'Basic Wall 200',$,$,$,$,$,$,.STANDARD.);\n\
+             #21=IFCWALLTYPE('wt1',$,'Basic Wall 100',$,$,$,$,$,$,.STANDARD.);\n\
+             #22=IFCWALLTYPE('wt2',$,'Curtain',$,$,$,$,$,$,.STANDARD.);\n",
+        );
+        let walls = 4000;
+        let first = 1000;
+        let mut by_type: [Vec<String>; 3] = Default::default();
+        let mut by_storey: [Vec<String>; 2] = Default::default();
+        for i in 0..walls {
+            let wall = first + 4 * i;
+            let _ = writeln!(
+                content,
+                "#{wall}=IFCWALL('w{i}',$,'Wall {i}',$,$,$,$,$,$);\n\
+                 #{}=IFCPROPERTYSINGLEVALUE('Width',$,IFCLENGTHMEASURE({}.),$);\n\
+                 #{}=IFCPROPERTYSET('ps{i}',$,'Pset_WallCommon',$,(#{}));\n\
+                 #{}=IFCRELDEFINESBYPROPERTIES('d{i}',$,$,$,(#{wall}),#{});",
+                wall + 1,
+                100 * (i % 3 + 1),
+                wall + 2,
+                wall + 1,
+                wall + 3,
+                wall + 2,
+            );
+            by_type[i % 3].push(format!("#{wall}"));
+            by_storey[i % 2].push(format!("#{wall}"));
+        }
+        let mut id = first + 4 * walls;
+        for (i, instances) in by_type.iter().enumerate() {
+            let _ = writeln!(
+                content,
+                "#{id}=IFCRELDEFINESBYTYPE('t{i}',$,$,$,({}),#{});",
+                instances.join(","),
+                20 + i
+            );
+            id += 1;
+        }
+        for (i, instances) in by_storey.iter().enumerate() {
+            let _ = writeln!(
+                content,
+                "#{id}=IFCRELCONTAINEDINSPATIALSTRUCTURE('c{i}',$,$,$,({}),#{});",
+                instances.join(","),
+                4 + i
+            );
+            id += 1;
+        }
+        // Every wall bounds one space, every third one a second space too
+        for i in 0..walls {
+            let wall = first + 4 * i;
+            let spaces = if i % 3 == 0 {
+                vec![10 + i % 5, 10 + (i + 1) % 5]
+            } else {
+                vec![10 + i % 5]
+            };
+            for space in spaces {
+                let _ = writeln!(
+                    content,
+                    "#{id}=IFCRELSPACEBOUNDARY('sb{id}',$,$,$,#{space},#{wall},$,.PHYSICAL.,.INTERNAL.);"
+                );
+                id += 1;
+            }
+        }
+        content.push_str("ENDSEC;\nEND-ISO-10303-21;\n");
+        content
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("ifc-inspector-{}-{name}", std::process::id()))
+    }
+
+    /// Parse `path` on a pool of `threads` threads and return the JSON and
+    /// CSV exports.
+    fn exports(path: &Path, threads: usize, memory_map: bool) -> (String, String) {
+        let options = ParseOptions {
+            memory_map,
+            ..ParseOptions::default()
+        };
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        let project = pool
+            .install(|| parse_ifc_file_with_options(path, options))
+            .unwrap();
+        let suffix = format!("{threads}-{memory_map}");
+        let json_path = temp_path(&format!("determinism-{suffix}.json"));
+        let csv_path = temp_path(&format!("determinism-{suffix}.csv"));
+        export_json(&project, &json_path).unwrap();
+        export_csv(&project, &csv_path).unwrap();
+        let json = std::fs::read_to_string(&json_path).unwrap();
+        let csv = std::fs::read_to_string(&csv_path).unwrap();
+        let _ = std::fs::remove_file(json_path);
+        let _ = std::fs::remove_file(csv_path);
+        (json, csv)
+    }
+
+    #[test]
+    fn exports_are_identical_across_runs_and_thread_counts() {
+        let path = temp_path("determinism.ifc");
+        std::fs::write(&path, fixture()).unwrap();
+
+        let (json, csv) = exports(&path, 1, false);
+        let runs = [(2, false), (4, false), (8, false), (8, false), (4, true)];
+        let results: Vec<(String, String)> = runs
+            .iter()
+            .map(|&(threads, memory_map)| exports(&path, threads, memory_map))
+            .collect();
+        let _ = std::fs::remove_file(&path);
+
+        for ((threads, memory_map), (other_json, other_csv)) in runs.iter().zip(&results) {
+            assert!(
+                json == *other_json,
+                "JSON differs with {threads} threads (memory_map: {memory_map})"
+            );
+            assert!(
+                csv == *other_csv,
+                "CSV differs with {threads} threads (memory_map: {memory_map})"
+            );
+        }
+
+        // Spaces are listed by storey (top down), number and entity id
+        let project: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let spaces: Vec<(u64, &str)> = project["spaces"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|space| {
+                (
+                    space["id"].as_u64().unwrap(),
+                    space["number"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            spaces,
+            vec![
+                (12, "101"),
+                (14, "101"),
+                (13, "102"),
+                (10, "101"),
+                (11, "101")
+            ]
+        );
+        assert!(csv.contains("Walls,Basic Wall 200,"));
+    }
+}
@@ -0,0 +1,394 @@
+//! Tokenizer for the ISO-10303-21 (STEP) exchange structure.
+//!
+//! Works on the raw text independently of line breaks, so statements that
+//! are wrapped across many lines tokenize the same as single-line ones.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token<'a> {
+    /// `#123` - entity instance name
+    EntityName(u64),
+    /// `IFCWALL`, `DATA`, `END-ISO-10303-21`, ...
+    Keyword(&'a str),
+    /// Raw string content between the quotes (escapes not decoded)
+    String(&'a str),
+    Integer(i64),
+    Real(f64),
+    /// `.ELEMENT.` - content between the dots
+    Enumeration(&'a str),
+    /// `"0123"` - raw binary content between the quotes
+    Binary(&'a str),
+    /// `$`
+    Dollar,
+    /// `*`
+    Asterisk,
+    LParen,
+    RParen,
+    Comma,
+    Equals,
+    Semicolon,
+}
+
+/// A token together with the byte offset where it starts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<'a> {
+    pub token: Token<'a>,
+    pub offset: usize,
+}
+
+/// A character sequence that is not valid STEP syntax.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexError {
+    pub offset: usize,
+    pub message: String,
+}
+
+pub struct Lexer<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    #[must_use]
+    pub fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    /// Current byte offset into the input.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+
+    fn peek_byte(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        let bytes = self.input.as_bytes();
+        loop {
+            while self.pos < bytes.len() && bytes[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            }
+            if bytes[self.pos..].starts_with(b"/*") {
+                match self.input[self.pos + 2..].find("*/") {
+                    Some(end) => self.pos += 2 + end + 2,
+                    None => self.pos = bytes.len(),
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Consume bytes while `pred` holds and return the consumed slice.
+    fn take_while(&mut self, pred: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        let bytes = self.input.as_bytes();
+        while self.pos < bytes.len() && pred(bytes[self.pos]) {
+            self.pos += 1;
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn lex_string(&mut self, start: usize) -> Result<Token<'a>, LexError> {
+        // Opening quote already consumed; '' is an escaped apostrophe
+        let bytes = self.input.as_bytes();
+        let content_start = self.pos;
+        while self.pos < bytes.len() {
+            if bytes[self.pos] == b'\'' {
+                if bytes.get(self.pos + 1) == Some(&b'\'') {
+                    self.pos += 2;
+                    continue;
+                }
+                let content = &self.input[content_start..self.pos];
+                self.pos += 1;
+                return Ok(Token::String(content));
+            }
+            self.pos += 1;
+        }
+        Err(LexError {
+            offset: start,
+            message: "unterminated string".to_string(),
+        })
+    }
+
+    fn lex_number(&mut self, start: usize) -> Result<Token<'a>, LexError> {
+        if matches!(self.peek_byte(), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        self.take_while(|b| b.is_ascii_digit());
+        let mut is_real = false;
+        if self.peek_byte() == Some(b'.') {
+            is_real = true;
+            self.pos += 1;
+            self.take_while(|b| b.is_ascii_digit());
+        }
+        if matches!(self.peek_byte(), Some(b'E' | b'e')) {
+            is_real = true;
+            self.pos += 1;
+            if matches!(self.peek_byte(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            self.take_while(|b| b.is_ascii_digit());
+        }
+
+        let text = &self.input[start..self.pos];
+        if !is_real {
+            if let Ok(i) = text.parse::<i64>() {
+                return Ok(Token::Integer(i));
+            }
+        }
+        // Integers that overflow i64 are still usable as reals
+        text.parse::<f64>().map(Token::Real).map_err(|_| LexError {
+            offset: start,
+            message: format!("invalid number '{text}'"),
+        })
+    }
+
+    fn lex_enumeration(&mut self, start: usize) -> Result<Token<'a>, LexError> {
+        // Leading dot already consumed
+        let name = self.take_while(|b| b.is_ascii_alphanumeric() || b == b'_');
+        if self.peek_byte() == Some(b'.') {
+            self.pos += 1;
+            Ok(Token::Enumeration(name))
+        } else {
+            Err(LexError {
+                offset: start,
+                message: "unterminated enumeration".to_string(),
+            })
+        }
+    }
+
+    fn lex_binary(&mut self, start: usize) -> Result<Token<'a>, LexError> {
+        // Opening quote already consumed
+        let content = self.take_while(|b| b != b'"');
+        if self.peek_byte() == Some(b'"') {
+            self.pos += 1;
+            Ok(Token::Binary(content))
+        } else {
+            Err(LexError {
+                offset: start,
+                message: "unterminated binary".to_string(),
+            })
+        }
+    }
+
+    fn lex_entity_name(&mut self, start: usize) -> Result<Token<'a>, LexError> {
+        // '#' already consumed
+        let digits = self.take_while(|b| b.is_ascii_digit());
+        digits
+            .parse::<u64>()
+            .map(Token::EntityName)
+            .map_err(|_| LexError {
+                offset: start,
+                message: "invalid entity instance name".to_string(),
+            })
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Spanned<'a>, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.skip_whitespace_and_comments();
+        let start = self.pos;
+        let byte = self.peek_byte()?;
+
+        let token = match byte {
+            b'(' | b')' | b',' | b'=' | b';' | b'$' | b'*' => {
+                self.pos += 1;
+                Ok(match byte {
+                    b'(' => Token::LParen,
+                    b')' => Token::RParen,
+                    b',' => Token::Comma,
+                    b'=' => Token::Equals,
+                    b';' => Token::Semicolon,
+                    b'$' => Token::Dollar,
+                    _ => Token::Asterisk,
+                })
+            }
+            b'\'' => {
+                self.pos += 1;
+                self.lex_string(start)
+            }
+            b'"' => {
+                self.pos += 1;
+                self.lex_binary(start)
+            }
+            b'#' => {
+                self.pos += 1;
+                self.lex_entity_name(start)
+            }
+            b'.' if self
+                .input
+                .as_bytes()
+                .get(self.pos + 1)
+                .is_some_and(u8::is_ascii_alphabetic) =>
+            {
+                self.pos += 1;
+                self.lex_enumeration(start)
+            }
+            b'+' | b'-' | b'.' | b'0'..=b'9' => self.lex_number(start),
+            b if b.is_ascii_alphabetic() || b == b'!' || b == b'_' => {
+                self.pos += 1;
+                let rest = self.take_while(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-');
+                Ok(Token::Keyword(&self.input[start..start + 1 + rest.len()]))
+            }
+            _ => {
+                // Skip the whole (possibly multi-byte) character
+                let len = self.input[start..].chars().next().map_or(1, char::len_utf8);
+                self.pos += len;
+                Err(LexError {
+                    offset: start,
+                    message: format!("unexpected character '{}'", &self.input[start..start + len]),
+                })
+            }
+        };
+
+        Some(token.map(|token| Spanned {
+            token,
+            offset: start,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn tokens(input: &str) -> Vec<Token<'_>> {
+        Lexer::new(input)
+            .map(|spanned| spanned.unwrap().token)
+            .collect()
+    }
+
+    fn first_error(input: &str) -> LexError {
+        Lexer::new(input)
+            .find_map(Result::err)
+            .expect("expected a lex error")
+    }
+
+    #[test]
+    fn tokenizes_an_entity_statement() {
+        assert_eq!(
+            tokens("#12=IFCWALL('2O2Fr$t4X7Zf8NOew3FL9r',#5,$,*,.T.,(1,-2.5E3),\"0F\");"),
+            vec![
+                Token::EntityName(12),
+                Token::Equals,
+                Token::Keyword("IFCWALL"),
+                Token::LParen,
+                Token::String("2O2Fr$t4X7Zf8NOew3FL9r"),
+                Token::Comma,
+                Token::EntityName(5),
+                Token::Comma,
+                Token::Dollar,
+                Token::Comma,
+                Token::Asterisk,
+                Token::Comma,
+                Token::Enumeration("T"),
+                Token::Comma,
+                Token::LParen,
+                Token::Integer(1),
+                Token::Comma,
+                Token::Real(-2500.0),
+                Token::RParen,
+                Token::Comma,
+                Token::Binary("0F"),
+                Token::RParen,
+                Token::Semicolon,
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_line_breaks_and_comments() {
+        let wrapped = "#1=\n  IFCWALL(\r\n'a', /* note; with 'quotes' */\n  $);";
+        assert_eq!(tokens(wrapped), tokens("#1=IFCWALL('a',$);"));
+        assert_eq!(tokens("/* only a comment */"), vec![]);
+    }
+
+    #[test]
+    fn reports_offsets() {
+        let offsets: Vec<usize> = Lexer::new("#1 = X('a');")
+            .map(|spanned| spanned.unwrap().offset)
+            .collect();
+        assert_eq!(offsets, vec![0, 3, 5, 6, 7, 10, 11]);
+    }
+
+    #[test]
+    fn keeps_escaped_apostrophes_in_strings() {
+        assert_eq!(tokens("'it''s'"), vec![Token::String("it''s")]);
+        assert_eq!(tokens("''"), vec![Token::String("")]);
+    }
+
+    #[test]
+    fn reports_unterminated_strings() {
+        let err = first_error("#1=IFCWALL('open,$);");
+        assert_eq!(err.offset, 11);
+        assert_eq!(err.message, "unterminated string");
+        // An escaped apostrophe at the end does not close the string
+        assert_eq!(first_error("'it''").message, "unterminated string");
+    }
+
+    #[test]
+    fn skips_unterminated_comments_to_the_end() {
+        assert_eq!(tokens("#1=X(1); /* never closed ;"), tokens("#1=X(1);"));
+    }
+
+    #[test]
+    fn reports_unterminated_binaries_and_enumerations() {
+        let err = first_error("X(\"0FF);");
+        assert_eq!(
+            (err.offset, err.message.as_str()),
+            (2, "unterminated binary")
+        );
+        let err = first_error("X(.T,1);");
+        assert_eq!(
+            (err.offset, err.message.as_str()),
+            (2, "unterminated enumeration")
+        );
+    }
+
+    #[test]
+    fn reports_unexpected_characters_and_continues() {
+        let results: Vec<_> = Lexer::new("X(ä)").collect();
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[2],
+            Err(LexError {
+                offset: 2,
+                message: "unexpected character 'ä'".to_string(),
+            })
+        );
+        assert_eq!(results[3].as_ref().unwrap().token, Token::RParen);
+    }
+
+    #[test]
+    fn reads_numbers() {
+        assert_eq!(
+            tokens("0 -7 +3 1. .5 2.5e-1 1E3"),
+            vec![
+                Token::Integer(0),
+                Token::Integer(-7),
+                Token::Integer(3),
+                Token::Real(1.0),
+                Token::Real(0.5),
+                Token::Real(0.25),
+                Token::Real(1000.0),
+            ]
+        );
+        // Too large for i64
+        assert_eq!(tokens("99999999999999999999"), vec![Token::Real(1e20)]);
+        assert_eq!(first_error("-").message, "invalid number '-'");
+    }
+
+    #[test]
+    fn reads_section_keywords() {
+        assert_eq!(
+            tokens("END-ISO-10303-21;"),
+            vec![Token::Keyword("END-ISO-10303-21"), Token::Semicolon]
+        );
+        assert_eq!(first_error("#=X;").message, "invalid entity instance name");
+    }
+}
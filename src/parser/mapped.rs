@@ -0,0 +1,425 @@
+//! Memory-mapped STEP files with entities parsed on demand.
+//!
+//! [`StepFile`](crate::parser::StepFile) holds every entity as owned values,
+//! which for a 500 MB model takes several times the file size.
+//! [`MappedStepFile`] maps the file into memory instead and only keeps, per
+//! entity, where its statement is and its interned type name. Entities are
+//! parsed from the mapped bytes when they are asked for, so memory use stays
+//! close to the file size.
+//!
+//! The trade-off is parse time. Opening the file parses every statement to
+//! find its type and report the same diagnostics as a `StepFile`, and the
+//! result is dropped, so an entity that is used later is parsed at least
+//! twice. Returned entities are owned [`StepEntity`] values: strings are
+//! decoded into new `String`s on every lookup rather than borrowed from the
+//! map or interned, and nothing is cached between lookups.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+
+use memmap2::Mmap;
+use rayon::prelude::*;
+
+use crate::error::ParseError;
+use crate::parser::container::Container;
+use crate::parser::diagnostic::{Diagnostic, ParseOptions};
+use crate::parser::encoding::FallbackEncoding;
+use crate::parser::progress::ProgressSink;
+use crate::parser::reader::{parse_entity_statement, ChunkEntity, StepReader};
+use crate::parser::step::{
+    EntitySource, StepEntity, StepHeader, TypeInterner, TypeSymbol, CHUNK_STATEMENTS,
+};
+use crate::schema::SchemaVersion;
+
+/// Where the statement of an entity is in the mapped file.
+#[derive(Debug, Clone, Copy)]
+struct EntitySpan {
+    id: u64,
+    start: u64,
+    len: u32,
+    symbol: TypeSymbol,
+}
+
+/// Entity spans by ID and by type.
+#[derive(Debug, Default)]
+struct EntityIndex {
+    types: TypeInterner,
+    // Entity IDs per interned type, in file order
+    type_index: Vec<Vec<u64>>,
+    // Entities whose ID is higher than all before them (the usual case), sorted by ID
+    ordered: Vec<EntitySpan>,
+    // Entities that appeared out of ID order
+    unordered: HashMap<u64, EntitySpan>,
+}
+
+impl EntityIndex {
+    /// Add an entity, replacing an earlier one with the same ID.
+    /// Returns `true` if one was replaced.
+    fn insert(&mut self, span: EntitySpan) -> bool {
+        let replaced = if self.ordered.last().is_none_or(|last| span.id > last.id) {
+            self.ordered.push(span);
+            None
+        } else if let Ok(i) = self.ordered.binary_search_by_key(&span.id, |s| s.id) {
+            Some(std::mem::replace(&mut self.ordered[i], span))
+        } else {
+            self.unordered.insert(span.id, span)
+        };
+
+        if let Some(old) = replaced {
+            self.type_index[old.symbol.index()].retain(|&i| i != span.id);
+        }
+        let slot = span.symbol.index();
+        if slot == self.type_index.len() {
+            self.type_index.push(Vec::new());
+        }
+        self.type_index[slot].push(span.id);
+        replaced.is_some()
+    }
+
+    fn get(&self, id: u64) -> Option<&EntitySpan> {
+        match self.ordered.binary_search_by_key(&id, |s| s.id) {
+            Ok(i) => Some(&self.ordered[i]),
+            Err(_) => self.unordered.get(&id),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.ordered.len() + self.unordered.len()
+    }
+}
+
+/// A STEP file that is memory-mapped and parsed lazily.
+///
+/// Opening the file parses every statement once (in parallel, like
+/// [`StepFile::from_reader_with_options`](crate::parser::StepFile::from_reader_with_options)),
+/// so the header, diagnostics and skipped count are the same as for a
+/// [`StepFile`](crate::parser::StepFile). The parsed values are then dropped;
+/// [`MappedStepFile::get_entity`] parses an entity again from the mapped
+/// bytes each time it is called, so every repeated lookup costs a parse.
+/// The IFC extraction reads each entity it needs once, which keeps a mapped
+/// load at about the parse time of an in-memory one.
+///
+/// Only uncompressed STEP files can be mapped. The file must not be modified
+/// while it is open.
+///
+/// # Example
+///
+/// ```no_run
+/// use ifc_inspector::parser::MappedStepFile;
+///
+/// let step_file = MappedStepFile::open("model.ifc")?;
+/// for &id in step_file.entity_ids_by_type("IFCWALL") {
+///     if let Some(wall) = step_file.get_entity(id) {
+///         println!("#{id}: {:?}", wall.get_attr("Name"));
+///     }
+/// }
+/// # Ok::<(), ifc_inspector::error::ParseError>(())
+/// ```
+#[derive(Debug)]
+pub struct MappedStepFile {
+    pub header: StepHeader,
+    pub schema: String,
+    /// Problems found while parsing (skipped statements, truncation, ...)
+    pub diagnostics: Vec<Diagnostic>,
+    /// Number of DATA statements that could not be parsed
+    pub skipped_entities: usize,
+    mmap: Mmap,
+    encoding: FallbackEncoding,
+    version: SchemaVersion,
+    index: EntityIndex,
+    // Built on the first call to `referenced_by`
+    inverse_index: OnceLock<HashMap<u64, Vec<u64>>>,
+}
+
+impl MappedStepFile {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        Self::open_with_options(path, ParseOptions::default())
+    }
+
+    /// Map and index the file at `path` with explicit options (e.g. strict
+    /// mode, which fails on the first diagnostic).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::NotMappable`] for compressed files and ifcXML.
+    pub fn open_with_options<P: AsRef<Path>>(
+        path: P,
+        options: ParseOptions,
+    ) -> Result<Self, ParseError> {
+        Self::open_with_progress(path.as_ref(), options, &ProgressSink::none())
+    }
+
+    /// Open like [`MappedStepFile::open_with_options`], reporting the bytes
+    /// indexed and the number of entities after each chunk.
+    pub(crate) fn open_with_progress(
+        path: &Path,
+        options: ParseOptions,
+        progress: &ProgressSink<'_>,
+    ) -> Result<Self, ParseError> {
+        let file_error = |source| ParseError::FileRead {
+            path: path.to_path_buf(),
+            source,
+        };
+        let file = File::open(path).map_err(file_error)?;
+        // SAFETY: the map is only read. Changing the file while it is mapped
+        // is not supported (see the type documentation).
+        #[allow(unsafe_code)]
+        let mmap = unsafe { Mmap::map(&file) }.map_err(file_error)?;
+
+        let is_xml = mmap
+            .iter()
+            .find(|b| !b.is_ascii_whitespace())
+            .is_some_and(|&b| b == b'<');
+        if is_xml || Container::detect(&mmap) != Container::Plain {
+            return Err(ParseError::NotMappable {
+                path: path.to_path_buf(),
+            });
+        }
+
+        let encoding = options.encoding;
+        // Strict mode stops at the first problem in file order, duplicates included
+        let chunk_statements = if options.strict { 1 } else { CHUNK_STATEMENTS };
+        let mut reader = StepReader::with_options(&mmap[..], options);
+        let mut index = EntityIndex::default();
+
+        while let Some(chunk) = reader.next_chunk(chunk_statements) {
+            let chunk = chunk?;
+            if let Some(last) = chunk.last() {
+                progress.bytes_read().store(last.end, Ordering::Relaxed);
+            }
+            for ChunkEntity {
+                position,
+                end,
+                entity,
+            } in chunk
+            {
+                let Ok(len) = u32::try_from(end - position.offset) else {
+                    let diagnostic = Diagnostic::new(
+                        position,
+                        Some(entity.id),
+                        "statement is too long to be memory-mapped".to_string(),
+                    );
+                    if let Some(err) = reader.report(diagnostic) {
+                        return Err(err);
+                    }
+                    continue;
+                };
+                let span = EntitySpan {
+                    id: entity.id,
+                    start: position.offset,
+                    len,
+                    symbol: index.types.intern(&entity.entity_type),
+                };
+                if index.insert(span) {
+                    let diagnostic = Diagnostic::new(
+                        position,
+                        Some(entity.id),
+                        "duplicate entity instance name; the later definition wins".to_string(),
+                    );
+                    if let Some(err) = reader.report(diagnostic) {
+                        return Err(err);
+                    }
+                }
+            }
+            progress.reading(index.len())?;
+        }
+
+        progress
+            .bytes_read()
+            .store(mmap.len() as u64, Ordering::Relaxed);
+        let (header, mut diagnostics, skipped_entities) = reader.finish();
+        // Duplicates are found after their chunk; keep everything in file order
+        diagnostics.sort_by_key(|d| d.offset);
+        let schema = header.schemas.first().cloned().unwrap_or_default();
+        let version = SchemaVersion::from_schemas(&header.schemas);
+        index.ordered.shrink_to_fit();
+        index.type_index.iter_mut().for_each(Vec::shrink_to_fit);
+
+        Ok(Self {
+            header,
+            schema,
+            diagnostics,
+            skipped_entities,
+            mmap,
+            encoding,
+            version,
+            index,
+            inverse_index: OnceLock::new(),
+        })
+    }
+
+    /// Number of entities in the DATA section.
+    #[must_use]
+    pub fn entity_count(&self) -> usize {
+        self.index.len()
+    }
+
+    #[must_use]
+    pub fn contains(&self, id: u64) -> bool {
+        self.index.get(id).is_some()
+    }
+
+    /// Type name of an entity, without parsing it.
+    #[must_use]
+    pub fn entity_type(&self, id: u64) -> Option<&str> {
+        self.index
+            .get(id)
+            .map(|span| self.index.types.resolve(span.symbol))
+    }
+
+    /// The raw bytes of an entity's statement, borrowed from the map.
+    #[must_use]
+    pub fn raw_statement(&self, id: u64) -> Option<&[u8]> {
+        self.index.get(id).map(|span| self.statement(span))
+    }
+
+    /// Parse an entity from the mapped bytes.
+    #[must_use]
+    pub fn get_entity(&self, id: u64) -> Option<StepEntity> {
+        self.index.get(id).and_then(|span| self.parse(span))
+    }
+
+    /// IDs of all entities of a given type, in file order.
+    #[must_use]
+    pub fn entity_ids_by_type(&self, entity_type: &str) -> &[u64] {
+        self.index
+            .types
+            .get(entity_type)
+            .map_or(&[], |symbol| &self.index.type_index[symbol.index()])
+    }
+
+    /// Distinct entity types with their instance counts, in order of first appearance.
+    ///
+    /// Types whose only instances were replaced by a later duplicate ID are
+    /// left out.
+    #[must_use]
+    pub fn entity_type_counts(&self) -> Vec<(&str, usize)> {
+        self.index
+            .type_index
+            .iter()
+            .enumerate()
+            .filter(|(_, ids)| !ids.is_empty())
+            .map(|(i, ids)| {
+                (
+                    self.index.types.resolve(TypeSymbol::from_index(i)),
+                    ids.len(),
+                )
+            })
+            .collect()
+    }
+
+    /// IDs of all entities that reference `id`, in file order.
+    ///
+    /// The first call parses every entity to build the inverse index.
+    #[must_use]
+    pub fn referenced_by(&self, id: u64) -> &[u64] {
+        self.inverse_index
+            .get_or_init(|| self.build_inverse_index())
+            .get(&id)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn build_inverse_index(&self) -> HashMap<u64, Vec<u64>> {
+        let mut spans: Vec<&EntitySpan> = self
+            .index
+            .ordered
+            .iter()
+            .chain(self.index.unordered.values())
+            .collect();
+        spans.sort_unstable_by_key(|span| span.start);
+
+        let mut inverse: HashMap<u64, Vec<u64>> = HashMap::new();
+        for chunk in spans.chunks(CHUNK_STATEMENTS) {
+            let references: Vec<(u64, Vec<u64>)> = chunk
+                .par_iter()
+                .map(|span| {
+                    let targets = self.parse(span).map(|e| e.references());
+                    (span.id, targets.unwrap_or_default())
+                })
+                .collect();
+            for (id, targets) in references {
+                for target in targets {
+                    inverse.entry(target).or_default().push(id);
+                }
+            }
+        }
+        inverse
+    }
+
+    fn statement(&self, span: &EntitySpan) -> &[u8] {
+        // Spans come from the reader's offsets into this map
+        let start = span.start as usize;
+        &self.mmap[start..start + span.len as usize]
+    }
+
+    fn parse(&self, span: &EntitySpan) -> Option<StepEntity> {
+        parse_entity_statement(self.statement(span), self.encoding, self.version)
+    }
+}
+
+impl EntitySource for MappedStepFile {
+    fn header(&self) -> &StepHeader {
+        &self.header
+    }
+
+    fn schema(&self) -> &str {
+        &self.schema
+    }
+
+    fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    fn skipped_entities(&self) -> usize {
+        self.skipped_entities
+    }
+
+    fn entity(&self, id: u64) -> Option<Cow<'_, StepEntity>> {
+        self.get_entity(id).map(Cow::Owned)
+    }
+
+    fn entity_type(&self, id: u64) -> Option<&str> {
+        MappedStepFile::entity_type(self, id)
+    }
+
+    fn entity_ids_by_type(&self, entity_type: &str) -> &[u64] {
+        MappedStepFile::entity_ids_by_type(self, entity_type)
+    }
+
+    fn entity_type_counts(&self) -> Vec<(&str, usize)> {
+        MappedStepFile::entity_type_counts(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn type_counts_leave_out_types_without_instances() {
+        let path =
+            std::env::temp_dir().join(format!("ifc-inspector-{}-mapped.ifc", std::process::id()));
+        std::fs::write(
+            &path,
+            "ISO-10303-21;HEADER;FILE_SCHEMA(('IFC4'));ENDSEC;DATA;\
+             #1=IFCWALL('a',$);#2=IFCWALL('b',$);#3=IFCSLAB('c',$);\
+             #1=IFCDOOR('d',$);#3=IFCDOOR('e',$);ENDSEC;END-ISO-10303-21;",
+        )
+        .unwrap();
+        let step_file = MappedStepFile::open(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            step_file.entity_type_counts(),
+            vec![("IFCWALL", 1), ("IFCDOOR", 2)]
+        );
+        assert_eq!(step_file.entity_type(3), Some("IFCDOOR"));
+        assert_eq!(step_file.get_entity(3).unwrap().entity_type, "IFCDOOR");
+        assert_eq!(step_file.diagnostics.len(), 2);
+    }
+}
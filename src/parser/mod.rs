@@ -1,6 +1,27 @@
+pub mod container;
+pub mod diagnostic;
+pub mod encoding;
 pub mod ifc;
+pub mod lexer;
+pub mod mapped;
+pub mod progress;
+pub mod reader;
 pub mod step;
+pub mod writer;
+pub mod xml;
 
 pub use crate::error::ParseError;
-pub use ifc::parse_ifc_file;
-pub use step::{StepEntity, StepFile, StepValue};
+pub use container::{list_models, Container};
+pub use diagnostic::{Diagnostic, Limits, ParseOptions, Position};
+pub use encoding::{decode_bytes, decode_step_string, encode_step_string, FallbackEncoding};
+pub use ifc::{
+    parse_ifc_file, parse_ifc_file_with_options, parse_ifc_model, parse_ifc_model_with_progress,
+    validate_ifc_model,
+};
+pub use mapped::MappedStepFile;
+pub use progress::{CancellationToken, ParsePhase, Progress};
+pub use reader::StepReader;
+pub use step::{
+    EntitySource, ReferenceDirection, ReferenceStep, StepEntity, StepFile, StepHeader, StepValue,
+};
+pub use writer::format_step_real;
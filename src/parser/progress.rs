@@ -0,0 +1,225 @@
+//! Progress reporting and cancellation for long parses.
+//!
+//! [`parse_ifc_model_with_progress`](crate::parser::parse_ifc_model_with_progress)
+//! calls a callback with a [`Progress`] snapshot every few thousand
+//! entities read and at the start of each extraction phase, and stops with
+//! [`ParseError::Cancelled`] once its [`CancellationToken`] is cancelled.
+
+use std::fmt;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::error::ParseError;
+
+/// What the parser is working on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParsePhase {
+    /// Reading the file and parsing entities
+    Reading,
+    Storeys,
+    SpatialContainment,
+    Spaces,
+    TypeRelationships,
+    PropertySets,
+    Categories,
+}
+
+impl fmt::Display for ParsePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Reading => "reading entities",
+            Self::Storeys => "storeys",
+            Self::SpatialContainment => "spatial containment",
+            Self::Spaces => "spaces",
+            Self::TypeRelationships => "type relationships",
+            Self::PropertySets => "property sets",
+            Self::Categories => "categories",
+        })
+    }
+}
+
+/// A snapshot of how far a parse has got.
+///
+/// The extraction phases run in parallel, so a callback may see them start
+/// in any order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub phase: ParsePhase,
+    /// Bytes of the file read so far (compressed bytes for `.ifczip` and `.gz`)
+    pub bytes_read: u64,
+    /// Size of the file on disk
+    pub total_bytes: u64,
+    /// Entities parsed so far
+    pub entities: usize,
+}
+
+impl Progress {
+    /// Fraction of the file read, from 0.0 to 1.0.
+    #[must_use]
+    pub fn fraction(&self) -> f64 {
+        if self.total_bytes == 0 {
+            return 1.0;
+        }
+        (self.bytes_read as f64 / self.total_bytes as f64).min(1.0)
+    }
+}
+
+/// Shared flag that asks a running parse to stop.
+///
+/// Clones refer to the same flag, so one can be handed to the parse and
+/// another kept to cancel it from a different thread.
+///
+/// ```
+/// use ifc_inspector::parser::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let handle = token.clone();
+/// handle.cancel();
+/// assert!(token.is_cancelled());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Entities parsed between two reports while reading; strict parses read
+/// one statement per chunk and would otherwise report after each.
+const REPORT_EVERY_ENTITIES: usize = 4096;
+
+/// Where the parse reports progress to and checks for cancellation.
+pub(crate) struct ProgressSink<'a> {
+    callback: Option<&'a (dyn Fn(Progress) + Sync)>,
+    cancel: Option<&'a CancellationToken>,
+    total_bytes: u64,
+    bytes_read: AtomicU64,
+    entities: AtomicUsize,
+    reported_entities: AtomicUsize,
+}
+
+impl<'a> ProgressSink<'a> {
+    pub(crate) fn new(
+        callback: &'a (dyn Fn(Progress) + Sync),
+        cancel: &'a CancellationToken,
+        total_bytes: u64,
+    ) -> Self {
+        Self {
+            callback: Some(callback),
+            cancel: Some(cancel),
+            total_bytes,
+            bytes_read: AtomicU64::new(0),
+            entities: AtomicUsize::new(0),
+            reported_entities: AtomicUsize::new(0),
+        }
+    }
+
+    /// A sink that reports nothing and is never cancelled.
+    pub(crate) fn none() -> Self {
+        Self {
+            callback: None,
+            cancel: None,
+            total_bytes: 0,
+            bytes_read: AtomicU64::new(0),
+            entities: AtomicUsize::new(0),
+            reported_entities: AtomicUsize::new(0),
+        }
+    }
+
+    /// Counter for [`CountingReader`], kept up to date while the file is read.
+    pub(crate) fn bytes_read(&self) -> &AtomicU64 {
+        &self.bytes_read
+    }
+
+    /// Report the number of entities parsed so far, at most once every
+    /// [`REPORT_EVERY_ENTITIES`]; cancellation is checked on every call.
+    pub(crate) fn reading(&self, entities: usize) -> Result<(), ParseError> {
+        self.entities.store(entities, Ordering::Relaxed);
+        let reported = self.reported_entities.load(Ordering::Relaxed);
+        if entities >= reported + REPORT_EVERY_ENTITIES {
+            self.reported_entities.store(entities, Ordering::Relaxed);
+            self.report(ParsePhase::Reading);
+        }
+        self.check()
+    }
+
+    /// Run one extraction phase, or return `None` without running it if the
+    /// parse was cancelled.
+    pub(crate) fn phase<T>(&self, phase: ParsePhase, f: impl FnOnce() -> T) -> Option<T> {
+        if self.is_cancelled() {
+            return None;
+        }
+        self.report(phase);
+        Some(f())
+    }
+
+    pub(crate) fn check(&self) -> Result<(), ParseError> {
+        if self.is_cancelled() {
+            return Err(ParseError::Cancelled);
+        }
+        Ok(())
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.is_some_and(CancellationToken::is_cancelled)
+    }
+
+    fn report(&self, phase: ParsePhase) {
+        if let Some(callback) = self.callback {
+            callback(Progress {
+                phase,
+                bytes_read: self.bytes_read.load(Ordering::Relaxed),
+                total_bytes: self.total_bytes,
+                entities: self.entities.load(Ordering::Relaxed),
+            });
+        }
+    }
+}
+
+/// Reader that publishes its position, so progress can be reported in file
+/// bytes even when the parser sees decompressed data.
+pub(crate) struct CountingReader<'a, R> {
+    inner: R,
+    position: u64,
+    counter: &'a AtomicU64,
+}
+
+impl<'a, R> CountingReader<'a, R> {
+    pub(crate) fn new(inner: R, counter: &'a AtomicU64) -> Self {
+        Self {
+            inner,
+            position: 0,
+            counter,
+        }
+    }
+}
+
+impl<R: Read> Read for CountingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.position += n as u64;
+        self.counter.store(self.position, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for CountingReader<'_, R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.position = self.inner.seek(pos)?;
+        self.counter.store(self.position, Ordering::Relaxed);
+        Ok(self.position)
+    }
+}
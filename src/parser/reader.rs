@@ -0,0 +1,1010 @@
+//! Streaming reader for STEP files.
+//!
+//! [`StepReader`] reads one statement (up to its terminating `;`) at a time
+//! from any [`BufRead`] source and yields DATA entities as they are parsed,
+//! so memory use does not grow with the file size.
+//!
+//! Splitting the input into statements is a cheap sequential byte scan; the
+//! expensive part (tokenizing and building entities) only depends on the
+//! statement itself, so [`StepReader::next_chunk`] hands a chunk of DATA
+//! statements to rayon and applies the results in file order.
+
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::io::BufRead;
+
+use rayon::prelude::*;
+
+use crate::error::ParseError;
+use crate::parser::diagnostic::{Diagnostic, ParseOptions, Position};
+use crate::parser::encoding::{decode_bytes, FallbackEncoding};
+use crate::parser::lexer::{LexError, Lexer, Token};
+use crate::parser::step::{StepEntity, StepFile, StepHeader};
+use crate::schema::SchemaVersion;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// A DATA statement that has been read but not parsed yet.
+struct RawStatement {
+    bytes: Vec<u8>,
+    start: Option<(Position, usize)>,
+}
+
+/// What one statement produced. Diagnostics are collected instead of
+/// reported so that statements parsed on worker threads can be applied to the
+/// reader in file order.
+struct ParsedStatement {
+    position: Position,
+    entity: Option<StepEntity>,
+    diagnostics: Vec<Diagnostic>,
+    /// A DATA statement that had to be dropped
+    skipped: bool,
+}
+
+impl ParsedStatement {
+    fn new(position: Position) -> Self {
+        Self {
+            position,
+            entity: None,
+            diagnostics: Vec::new(),
+            skipped: false,
+        }
+    }
+}
+
+enum Statement {
+    /// An entity instance (`#id=...`) inside DATA, still to be parsed
+    Data,
+    /// Any other statement, already handled
+    Parsed(ParsedStatement),
+}
+
+enum Pending {
+    Raw(RawStatement),
+    Parsed(ParsedStatement),
+}
+
+/// An entity returned by [`StepReader::next_chunk`], with where its statement
+/// is in the input.
+pub(crate) struct ChunkEntity {
+    /// Position of the first byte of the statement
+    pub position: Position,
+    /// Offset just past the terminating `;`
+    pub end: u64,
+    pub entity: StepEntity,
+}
+
+/// Where the statement scanner is inside the raw bytes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScanState {
+    Normal,
+    String,
+    Binary,
+    Comment,
+}
+
+/// Iterator over the entities of a STEP file read from any `BufRead` source.
+///
+/// HEADER records are collected into [`StepReader::header`] as they are read;
+/// the header is complete once the first entity has been returned.
+///
+/// Statements that cannot be parsed are skipped and recorded as
+/// [`Diagnostic`]s, unless [`ParseOptions::strict`] is set, in which case the
+/// first one is returned as [`ParseError::InvalidStep`] and iteration stops.
+///
+/// Statements that are not valid UTF-8 are decoded with
+/// [`ParseOptions::encoding`] and reported as a diagnostic, but not dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::fs::File;
+/// use std::io::BufReader;
+/// use ifc_inspector::parser::StepReader;
+///
+/// let file = File::open("model.ifc").expect("Failed to open");
+/// let walls = StepReader::new(BufReader::new(file))
+///     .filter_map(Result::ok)
+///     .filter(|e| e.entity_type == "IFCWALL")
+///     .count();
+/// println!("Walls: {walls}");
+/// ```
+pub struct StepReader<R> {
+    reader: R,
+    options: ParseOptions,
+    header: StepHeader,
+    diagnostics: Vec<Diagnostic>,
+    skipped_entities: usize,
+    statement: Vec<u8>,
+    // Where the current statement starts (first non-whitespace byte)
+    statement_start: Option<(Position, usize)>,
+    position: Position,
+    state: ScanState,
+    prev_byte: u8,
+    // Open parentheses in the current statement
+    depth: usize,
+    // Bytes in the current string or binary literal
+    literal_len: usize,
+    // Entities returned so far, for Limits::max_entities
+    entities: usize,
+    in_data: bool,
+    seen_data: bool,
+    finished: bool,
+    // From FILE_SCHEMA, once the DATA section starts
+    schema: SchemaVersion,
+}
+
+impl<R: BufRead> StepReader<R> {
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ParseOptions::default())
+    }
+
+    #[must_use]
+    pub fn with_options(reader: R, options: ParseOptions) -> Self {
+        Self {
+            reader,
+            options,
+            header: StepHeader::default(),
+            diagnostics: Vec::new(),
+            skipped_entities: 0,
+            statement: Vec::new(),
+            statement_start: None,
+            position: Position::default(),
+            state: ScanState::Normal,
+            prev_byte: 0,
+            depth: 0,
+            literal_len: 0,
+            entities: 0,
+            in_data: false,
+            seen_data: false,
+            finished: false,
+            schema: SchemaVersion::default(),
+        }
+    }
+
+    /// Header records read so far.
+    #[must_use]
+    pub fn header(&self) -> &StepHeader {
+        &self.header
+    }
+
+    #[must_use]
+    pub fn into_header(self) -> StepHeader {
+        self.header
+    }
+
+    /// Problems found so far.
+    #[must_use]
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Number of DATA statements that were dropped so far.
+    #[must_use]
+    pub fn skipped_entities(&self) -> usize {
+        self.skipped_entities
+    }
+
+    /// Consume the reader, returning the header, diagnostics and skipped entity count.
+    #[must_use]
+    pub fn finish(self) -> (StepHeader, Vec<Diagnostic>, usize) {
+        (self.header, self.diagnostics, self.skipped_entities)
+    }
+
+    /// Position where the most recently read statement starts.
+    #[must_use]
+    pub fn statement_position(&self) -> Position {
+        self.statement_start.map_or(self.position, |(p, _)| p)
+    }
+
+    /// Record a diagnostic; in strict mode it is turned into an error instead.
+    pub(crate) fn report(&mut self, diagnostic: Diagnostic) -> Option<ParseError> {
+        if self.options.strict {
+            self.finished = true;
+            return Some(ParseError::InvalidStep {
+                message: diagnostic.to_string(),
+            });
+        }
+        self.diagnostics.push(diagnostic);
+        None
+    }
+
+    /// Apply a parsed statement: record its diagnostics (turning the first
+    /// one into an error in strict mode) and count it if it was dropped.
+    fn apply(&mut self, parsed: ParsedStatement) -> Result<Option<StepEntity>, ParseError> {
+        for diagnostic in parsed.diagnostics {
+            if let Some(err) = self.report(diagnostic) {
+                return Err(err);
+            }
+        }
+        if parsed.skipped {
+            self.skipped_entities += 1;
+        }
+        if parsed.entity.is_some() {
+            self.entities += 1;
+            let max = self.options.limits.max_entities;
+            if self.entities > max {
+                self.finished = true;
+                return Err(limit_exceeded(
+                    parsed.position,
+                    format!("more than {max} entities"),
+                ));
+            }
+        }
+        Ok(parsed.entity)
+    }
+
+    /// Read the next statement. Entity instances inside DATA are left in the
+    /// statement buffer for the caller to parse; everything else is handled here.
+    fn next_statement(&mut self) -> Result<Option<Statement>, ParseError> {
+        if !self.read_statement()? {
+            return Ok(None);
+        }
+        if self.in_data && starts_with_entity_name(&self.statement) {
+            return Ok(Some(Statement::Data));
+        }
+        Ok(Some(Statement::Parsed(self.parse_control_statement())))
+    }
+
+    /// Handle a section keyword, header record or anything unexpected.
+    fn parse_control_statement(&mut self) -> ParsedStatement {
+        let position = self.statement_position();
+        let entity_id = statement_entity_id(&self.statement);
+        let mut parsed = ParsedStatement::new(position);
+        let skipped = self.in_data;
+        let skip = |parsed: &mut ParsedStatement, diagnostic| {
+            parsed.diagnostics.push(diagnostic);
+            parsed.skipped = skipped;
+        };
+
+        let (text, decoded) = decode_statement(&self.statement, self.options.encoding);
+        if let Some(message) = decoded {
+            parsed
+                .diagnostics
+                .push(Diagnostic::new(position, entity_id, message));
+        }
+        let tokens = match tokenize_statement(&text) {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                let at = position_in(text.as_bytes(), self.statement_start, e.offset);
+                skip(&mut parsed, Diagnostic::new(at, entity_id, e.message));
+                return parsed;
+            }
+        };
+
+        let problem = match tokens.first() {
+            Some(Token::Keyword("DATA")) => {
+                self.in_data = true;
+                self.seen_data = true;
+                self.schema = SchemaVersion::from_schemas(&self.header.schemas);
+                None
+            }
+            Some(Token::Keyword("ENDSEC")) => {
+                self.in_data = false;
+                None
+            }
+            Some(Token::Keyword("END-ISO-10303-21")) => {
+                self.finished = true;
+                None
+            }
+            Some(Token::Keyword("ISO-10303-21" | "HEADER")) if !self.in_data => None,
+            Some(Token::Keyword(keyword)) if !self.in_data => {
+                match StepFile::parse_record(&tokens) {
+                    Some(values) => {
+                        self.header.apply_record(keyword, &values);
+                        None
+                    }
+                    None => Some(format!("malformed header record {keyword}")),
+                }
+            }
+            // An entity instance preceded by a comment
+            Some(Token::EntityName(_)) if self.in_data => {
+                match StepFile::parse_entity(&tokens, self.schema) {
+                    Some(entity) => {
+                        parsed.entity = Some(entity);
+                        None
+                    }
+                    None => Some(entity_problem(&tokens)),
+                }
+            }
+            Some(_) if self.in_data => Some("expected an entity instance".to_string()),
+            Some(_) => Some("unexpected statement outside the DATA section".to_string()),
+            None => Some("empty statement".to_string()),
+        };
+
+        if let Some(message) = problem {
+            skip(&mut parsed, Diagnostic::new(position, entity_id, message));
+        }
+        parsed
+    }
+
+    /// Read up to `max_statements` statements and return the entities among
+    /// them, parsing the DATA statements in parallel.
+    ///
+    /// Entities, diagnostics and the skipped count come out in the same order
+    /// as from the iterator, regardless of the number of threads. Each entity
+    /// is paired with the location of its statement.
+    pub(crate) fn next_chunk(
+        &mut self,
+        max_statements: usize,
+    ) -> Option<Result<Vec<ChunkEntity>, ParseError>> {
+        if self.finished {
+            return None;
+        }
+
+        let mut pending = Vec::new();
+        let mut end_of_input = false;
+        while pending.len() < max_statements && !self.finished {
+            match self.next_statement() {
+                Ok(Some(Statement::Data)) => pending.push((
+                    self.position.offset,
+                    Pending::Raw(RawStatement {
+                        bytes: std::mem::take(&mut self.statement),
+                        start: self.statement_start,
+                    }),
+                )),
+                Ok(Some(Statement::Parsed(parsed))) => {
+                    pending.push((self.position.offset, Pending::Parsed(parsed)));
+                }
+                Ok(None) => {
+                    end_of_input = true;
+                    break;
+                }
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+
+        let encoding = self.options.encoding;
+        let schema = self.schema;
+        let parsed: Vec<(u64, ParsedStatement)> = pending
+            .into_par_iter()
+            .map(|(end, statement)| match statement {
+                Pending::Raw(raw) => (
+                    end,
+                    parse_data_statement(&raw.bytes, raw.start, encoding, schema),
+                ),
+                Pending::Parsed(parsed) => (end, parsed),
+            })
+            .collect();
+
+        let mut entities = Vec::with_capacity(parsed.len());
+        for (end, statement) in parsed {
+            let position = statement.position;
+            match self.apply(statement) {
+                Ok(Some(entity)) => entities.push(ChunkEntity {
+                    position,
+                    end,
+                    entity,
+                }),
+                Ok(None) => {}
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        if end_of_input {
+            self.finished = true;
+            if let Some(err) = self.check_end_of_input() {
+                return Some(Err(err));
+            }
+        }
+        Some(Ok(entities))
+    }
+
+    /// Read raw bytes up to and including the next `;` that is outside
+    /// strings, binaries and comments. Returns `false` at end of input.
+    ///
+    /// Enforces the size, nesting, literal and statement length
+    /// [`Limits`](crate::parser::Limits) while scanning, so no statement that
+    /// breaks them is ever buffered whole or parsed.
+    fn read_statement(&mut self) -> Result<bool, ParseError> {
+        let read_error = |source| ParseError::Read { source };
+        let limits = self.options.limits;
+        self.statement.clear();
+        self.statement_start = None;
+        self.depth = 0;
+
+        if self.position.offset == 0
+            && self
+                .reader
+                .fill_buf()
+                .map_err(read_error)?
+                .starts_with(UTF8_BOM)
+        {
+            self.reader.consume(UTF8_BOM.len());
+            self.position.offset = UTF8_BOM.len() as u64;
+        }
+
+        loop {
+            let buf = self.reader.fill_buf().map_err(read_error)?;
+            if buf.is_empty() {
+                // Trailing bytes without a terminating ';' are not a statement
+                return Ok(false);
+            }
+
+            let mut consumed = buf.len();
+            let mut complete = false;
+            for (i, &b) in buf.iter().enumerate() {
+                if self.statement_start.is_none() && !b.is_ascii_whitespace() {
+                    self.statement_start = Some((self.position, self.statement.len() + i));
+                }
+                let at = self.position;
+                self.position.advance(b);
+                if self.position.offset > limits.max_file_size {
+                    return Err(limit_exceeded(
+                        at,
+                        format!("input is larger than {} bytes", limits.max_file_size),
+                    ));
+                }
+                if self.statement.len() + i >= limits.max_statement_length {
+                    return Err(limit_exceeded(
+                        at,
+                        format!(
+                            "statement longer than {} bytes",
+                            limits.max_statement_length
+                        ),
+                    ));
+                }
+
+                let mut prev = b;
+                match self.state {
+                    ScanState::Normal => match b {
+                        b'\'' => {
+                            self.state = ScanState::String;
+                            // '' inside a string continues the same literal
+                            if self.prev_byte != b'\'' {
+                                self.literal_len = 0;
+                            }
+                        }
+                        b'"' => {
+                            self.state = ScanState::Binary;
+                            self.literal_len = 0;
+                        }
+                        b'(' => {
+                            self.depth += 1;
+                            if self.depth > limits.max_nesting {
+                                return Err(limit_exceeded(
+                                    at,
+                                    format!(
+                                        "parentheses nested deeper than {}",
+                                        limits.max_nesting
+                                    ),
+                                ));
+                            }
+                        }
+                        b')' => self.depth = self.depth.saturating_sub(1),
+                        b'*' if self.prev_byte == b'/' => {
+                            self.state = ScanState::Comment;
+                            // So that "/*/" does not close the comment
+                            prev = 0;
+                        }
+                        b';' => {
+                            consumed = i + 1;
+                            complete = true;
+                        }
+                        _ => {}
+                    },
+                    ScanState::String if b == b'\'' => self.state = ScanState::Normal,
+                    ScanState::Binary if b == b'"' => self.state = ScanState::Normal,
+                    ScanState::String | ScanState::Binary => {
+                        self.literal_len += 1;
+                        if self.literal_len > limits.max_string_length {
+                            return Err(limit_exceeded(
+                                at,
+                                format!("string longer than {} bytes", limits.max_string_length),
+                            ));
+                        }
+                    }
+                    ScanState::Comment if b == b'/' && self.prev_byte == b'*' => {
+                        self.state = ScanState::Normal;
+                        prev = 0;
+                    }
+                    ScanState::Comment => {}
+                }
+                self.prev_byte = prev;
+                if complete {
+                    break;
+                }
+            }
+
+            self.statement.extend_from_slice(&buf[..consumed]);
+            self.reader.consume(consumed);
+            if complete {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for StepReader<R> {
+    type Item = Result<StepEntity, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            let parsed = match self.next_statement() {
+                Ok(Some(Statement::Data)) => parse_data_statement(
+                    &self.statement,
+                    self.statement_start,
+                    self.options.encoding,
+                    self.schema,
+                ),
+                Ok(Some(Statement::Parsed(parsed))) => parsed,
+                Ok(None) => {
+                    self.finished = true;
+                    if let Some(err) = self.check_end_of_input() {
+                        return Some(Err(err));
+                    }
+                    break;
+                }
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+            };
+
+            match self.apply(parsed) {
+                Ok(Some(entity)) => return Some(Ok(entity)),
+                Ok(None) => {}
+                Err(err) => return Some(Err(err)),
+            }
+        }
+
+        None
+    }
+}
+
+impl<R: BufRead> StepReader<R> {
+    /// Report a truncated file: an unterminated statement or unclosed DATA section.
+    fn check_end_of_input(&mut self) -> Option<ParseError> {
+        // Trailing whitespace and comments are fine
+        let trailing_tokens = std::str::from_utf8(&self.statement)
+            .ok()
+            .and_then(|text| tokenize_statement(text).ok())
+            .is_none_or(|tokens| !tokens.is_empty());
+        if let (Some((position, _)), true) = (self.statement_start, trailing_tokens) {
+            let entity_id = statement_entity_id(&self.statement);
+            let message = "unexpected end of file: statement is not terminated by ';'";
+            if self.in_data {
+                self.skipped_entities += 1;
+            }
+            let diagnostic = Diagnostic::new(position, entity_id, message.to_string());
+            if let Some(err) = self.report(diagnostic) {
+                return Some(err);
+            }
+        }
+        if self.in_data {
+            let diagnostic = Diagnostic::new(
+                self.position,
+                None,
+                "unexpected end of file: DATA section is not closed by ENDSEC".to_string(),
+            );
+            return self.report(diagnostic);
+        }
+        if !self.seen_data {
+            let diagnostic =
+                Diagnostic::new(self.position, None, "no DATA section found".to_string());
+            return self.report(diagnostic);
+        }
+        None
+    }
+}
+
+/// Parse an entity instance statement (`#id=TYPE(...);`).
+///
+/// Depends only on its arguments, so it can run on any thread.
+fn parse_data_statement(
+    statement: &[u8],
+    start: Option<(Position, usize)>,
+    encoding: FallbackEncoding,
+    schema: SchemaVersion,
+) -> ParsedStatement {
+    let position = start.map(|(p, _)| p).unwrap_or_default();
+    let entity_id = statement_entity_id(statement);
+    let mut parsed = ParsedStatement::new(position);
+
+    let (text, decoded) = decode_statement(statement, encoding);
+    if let Some(message) = decoded {
+        parsed
+            .diagnostics
+            .push(Diagnostic::new(position, entity_id, message));
+    }
+    let (at, message) = match tokenize_statement(&text) {
+        Ok(tokens) => match StepFile::parse_entity(&tokens, schema) {
+            Some(entity) => {
+                parsed.entity = Some(entity);
+                return parsed;
+            }
+            None => (position, entity_problem(&tokens)),
+        },
+        Err(e) => (position_in(text.as_bytes(), start, e.offset), e.message),
+    };
+    parsed
+        .diagnostics
+        .push(Diagnostic::new(at, entity_id, message));
+    parsed.skipped = true;
+    parsed
+}
+
+/// Parse a complete entity statement again, e.g. one that was located
+/// earlier and is now needed. Problems were reported the first time, so they
+/// are not reported again.
+pub(crate) fn parse_entity_statement(
+    statement: &[u8],
+    encoding: FallbackEncoding,
+    schema: SchemaVersion,
+) -> Option<StepEntity> {
+    let (text, _) = decode_statement(statement, encoding);
+    StepFile::parse_entity(&tokenize_statement(&text).ok()?, schema)
+}
+
+/// The statement as text, decoding it with `encoding` if it is not valid
+/// UTF-8 (with a message saying so).
+fn decode_statement(
+    statement: &[u8],
+    encoding: FallbackEncoding,
+) -> (Cow<'_, str>, Option<String>) {
+    if let Ok(text) = std::str::from_utf8(statement) {
+        return (Cow::Borrowed(text), None);
+    }
+    let (text, replaced) = decode_bytes(statement, encoding);
+    let mut message = "statement is not valid UTF-8".to_string();
+    if encoding != FallbackEncoding::Lossy {
+        let _ = write!(message, ", decoded as {encoding}");
+    }
+    if replaced > 0 {
+        let _ = write!(message, " ({replaced} invalid bytes replaced with U+FFFD)");
+    }
+    (Cow::Owned(text), Some(message))
+}
+
+fn limit_exceeded(position: Position, message: String) -> ParseError {
+    ParseError::LimitExceeded {
+        message: Diagnostic::new(position, None, message).to_string(),
+    }
+}
+
+/// Position of byte `offset` of a statement that starts at `start`.
+fn position_in(statement: &[u8], start: Option<(Position, usize)>, offset: usize) -> Position {
+    let Some((mut position, first)) = start else {
+        return Position::default();
+    };
+    for &b in statement.get(first..offset).unwrap_or_default() {
+        position.advance(b);
+    }
+    position
+}
+
+/// Whether the statement starts with an entity instance name (`#123`).
+fn starts_with_entity_name(statement: &[u8]) -> bool {
+    statement
+        .iter()
+        .find(|b| !b.is_ascii_whitespace())
+        .is_some_and(|&b| b == b'#')
+}
+
+/// Explain why an entity statement could not be parsed.
+fn entity_problem(tokens: &[Token]) -> String {
+    let open = tokens.iter().filter(|t| **t == Token::LParen).count();
+    let close = tokens.iter().filter(|t| **t == Token::RParen).count();
+    if open != close {
+        return "unbalanced parentheses".to_string();
+    }
+    match (tokens.get(1), tokens.get(2)) {
+        (Some(Token::Equals), Some(Token::LParen)) => {
+            "complex entity instances are not supported".to_string()
+        }
+        (Some(Token::Equals), Some(Token::Keyword(_))) => "malformed attribute list".to_string(),
+        _ => "expected '#id=TYPE(...)'".to_string(),
+    }
+}
+
+/// Entity ID of a statement that starts with `#123`, even if the rest is malformed.
+fn statement_entity_id(statement: &[u8]) -> Option<u64> {
+    let text = String::from_utf8_lossy(statement);
+    let rest = text.trim_start().strip_prefix('#')?;
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+/// Tokenize a single statement, dropping its terminating `;`.
+fn tokenize_statement(text: &str) -> Result<Vec<Token<'_>>, LexError> {
+    let mut tokens = Vec::new();
+    for spanned in Lexer::new(text) {
+        let spanned = spanned?;
+        if spanned.token == Token::Semicolon {
+            break;
+        }
+        tokens.push(spanned.token);
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::step::StepValue;
+    use crate::parser::Limits;
+    use pretty_assertions::assert_eq;
+    use std::io::BufReader;
+
+    const HEADER: &str = "ISO-10303-21;\nHEADER;\nFILE_SCHEMA(('IFC4'));\nENDSEC;\nDATA;\n";
+    const FOOTER: &str = "ENDSEC;\nEND-ISO-10303-21;\n";
+
+    fn options(limits: Limits) -> ParseOptions {
+        ParseOptions {
+            limits,
+            ..ParseOptions::default()
+        }
+    }
+
+    fn strict() -> ParseOptions {
+        ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        }
+    }
+
+    fn read_all(data: &str, options: ParseOptions) -> Result<Vec<StepEntity>, ParseError> {
+        let content = format!("{HEADER}{data}{FOOTER}");
+        StepReader::with_options(content.as_bytes(), options).collect()
+    }
+
+    /// Read leniently and return the entity ids, diagnostics and skipped count.
+    fn read_lenient(content: &str) -> (Vec<u64>, Vec<Diagnostic>, usize) {
+        let mut reader = StepReader::new(content.as_bytes());
+        let ids = reader.by_ref().map(|entity| entity.unwrap().id).collect();
+        let (_, diagnostics, skipped) = reader.finish();
+        (ids, diagnostics, skipped)
+    }
+
+    fn messages(diagnostics: &[Diagnostic]) -> Vec<String> {
+        diagnostics.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn reads_header_and_entities() {
+        let content = format!("{HEADER}#1=IFCWALL('a',$);\n#2=IFCSLAB('b',$);\n{FOOTER}");
+        let mut reader = StepReader::new(content.as_bytes());
+        let entities: Vec<StepEntity> = reader.by_ref().map(Result::unwrap).collect();
+        assert_eq!(
+            entities
+                .iter()
+                .map(|e| (e.id, e.entity_type.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, "IFCWALL"), (2, "IFCSLAB")]
+        );
+        assert_eq!(reader.header().schemas, vec!["IFC4".to_string()]);
+        let (_, diagnostics, skipped) = reader.finish();
+        assert_eq!((diagnostics, skipped), (vec![], 0));
+    }
+
+    #[test]
+    fn reports_unbalanced_parentheses_with_line_and_id() {
+        let data =
+            "#1=IFCWALL('a',$);\n#2=IFCWALL(('a',$);\n  #3=IFCSLAB('b',$));\n#4=IFCSLAB('c',$);\n";
+        let (ids, diagnostics, skipped) = read_lenient(&format!("{HEADER}{data}{FOOTER}"));
+        assert_eq!(ids, vec![1, 4]);
+        assert_eq!(skipped, 2);
+        assert_eq!(
+            messages(&diagnostics),
+            vec![
+                "line 7, column 1 (#2): unbalanced parentheses",
+                "line 8, column 3 (#3): unbalanced parentheses",
+            ]
+        );
+        assert_eq!(diagnostics[1].entity_id, Some(3));
+    }
+
+    #[test]
+    fn reports_a_statement_cut_off_by_an_unterminated_string() {
+        let content = format!("{HEADER}#1=IFCWALL('a',$);\n#2=IFCWALL('open,$);\n");
+        let (ids, diagnostics, skipped) = read_lenient(&content);
+        assert_eq!(ids, vec![1]);
+        assert_eq!(skipped, 1);
+        assert_eq!(
+            messages(&diagnostics),
+            vec![
+                "line 7, column 1 (#2): unexpected end of file: statement is not terminated by ';'",
+                "line 8, column 1: unexpected end of file: DATA section is not closed by ENDSEC",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_a_statement_cut_off_by_an_unterminated_comment() {
+        let content = format!("{HEADER}#1=IFCWALL('a',$);\n#2=IFCWALL('b' /* $); {FOOTER}");
+        let (ids, diagnostics, skipped) = read_lenient(&content);
+        assert_eq!(ids, vec![1]);
+        assert_eq!(skipped, 1);
+        assert_eq!(diagnostics[0].entity_id, Some(2));
+        assert_eq!(
+            diagnostics[0].message,
+            "unexpected end of file: statement is not terminated by ';'"
+        );
+    }
+
+    #[test]
+    fn ignores_semicolons_in_strings_and_comments() {
+        let data = "#1=IFCWALL('a;b' /* ; */,$);\n/* #2=IFCWALL('x',$); */#3=IFCSLAB('c',$);\n";
+        let entities = read_all(data, ParseOptions::default()).unwrap();
+        assert_eq!(
+            entities.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(entities[0].values[0], StepValue::String("a;b".to_string()));
+    }
+
+    #[test]
+    fn strict_mode_stops_at_the_first_problem() {
+        let data = "#1=IFCWALL('a',$);\n#2=IFCWALL(('a',$);\n#3=IFCSLAB('b',$);\n";
+        let content = format!("{HEADER}{data}{FOOTER}");
+        let mut reader = StepReader::with_options(content.as_bytes(), strict());
+        assert_eq!(reader.next().unwrap().unwrap().id, 1);
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(matches!(err, ParseError::InvalidStep { .. }));
+        assert_eq!(
+            err.to_string(),
+            "invalid STEP format: line 7, column 1 (#2): unbalanced parentheses"
+        );
+        assert!(reader.next().is_none());
+
+        // Leniently the same input is read without an error
+        let entities = read_all(data, ParseOptions::default()).unwrap();
+        assert_eq!(entities.len(), 2);
+    }
+
+    #[test]
+    fn strict_mode_rejects_a_truncated_file() {
+        let content = format!("{HEADER}#1=IFCWALL('a',$);\n");
+        let results: Vec<_> = StepReader::with_options(content.as_bytes(), strict()).collect();
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[1].as_ref().unwrap_err().to_string(),
+            "invalid STEP format: line 7, column 1: unexpected end of file: DATA section is not closed by ENDSEC"
+        );
+    }
+
+    #[test]
+    fn stops_at_parentheses_nested_deeper_than_the_limit() {
+        let limits = Limits {
+            max_nesting: 3,
+            ..Limits::default()
+        };
+        assert_eq!(
+            read_all("#1=X(((1)));\n", options(limits)).unwrap().len(),
+            1
+        );
+        let err = read_all("#1=X((((1))));\n", options(limits)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "limit exceeded: line 6, column 8: parentheses nested deeper than 3"
+        );
+    }
+
+    #[test]
+    fn stops_at_a_string_longer_than_the_limit() {
+        let limits = Limits {
+            max_string_length: 4,
+            ..Limits::default()
+        };
+        // '' counts as two bytes of the same literal
+        assert_eq!(
+            read_all("#1=X('ab''');\n", options(limits)).unwrap().len(),
+            1
+        );
+        let err = read_all("#1=X('abcde');\n", options(limits)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "limit exceeded: line 6, column 11: string longer than 4 bytes"
+        );
+        let err = read_all("#1=X(\"0123456789\");\n", options(limits)).unwrap_err();
+        assert!(matches!(err, ParseError::LimitExceeded { .. }));
+    }
+
+    #[test]
+    fn stops_after_more_entities_than_the_limit() {
+        let limits = Limits {
+            max_entities: 2,
+            ..Limits::default()
+        };
+        let data = "#1=X(1);\n#2=X(2);\n#3=X(3);\n";
+        let content = format!("{HEADER}{data}{FOOTER}");
+        let results: Vec<_> =
+            StepReader::with_options(content.as_bytes(), options(limits)).collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[2].as_ref().unwrap_err().to_string(),
+            "limit exceeded: line 8, column 1: more than 2 entities"
+        );
+        assert_eq!(
+            read_all("#1=X(1);\n#2=X(2);\n", options(limits))
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn stops_at_a_statement_longer_than_the_limit() {
+        let limits = Limits {
+            max_statement_length: 40,
+            ..Limits::default()
+        };
+        let short = "#1=IFCWALL('a',$,$,'Wall',$,$,$,$,$);\n";
+        assert_eq!(read_all(short, options(limits)).unwrap().len(), 1);
+
+        let long = format!("#1=IFCWALL('{}',$,$,$,$,$,$,$,$);\n", "x".repeat(40));
+        let err = read_all(&long, options(limits)).unwrap_err();
+        assert!(matches!(err, ParseError::LimitExceeded { .. }));
+        assert_eq!(
+            err.to_string(),
+            "limit exceeded: line 6, column 40: statement longer than 40 bytes"
+        );
+    }
+
+    /// Every way a statement can be split between two reads of the buffer.
+    #[test]
+    fn reads_statements_split_across_buffer_boundaries() {
+        let data = "#1=IFCWALL('it''s; a \\X2\\00D3\\X0\\',$);\n\
+                    /* a ; comment */#2=IFCSLAB(\"0F\",(1.5,-2),.T.);\n\
+                    #3=IFCDOOR(('a'),'\u{e9}l\u{e9}ment');\n";
+        let content = format!("{HEADER}{data}{FOOTER}");
+        let expected: Vec<String> = StepReader::new(content.as_bytes())
+            .map(|entity| format!("{:?}", entity.unwrap()))
+            .collect();
+        assert_eq!(expected.len(), 3);
+
+        for capacity in 1..=16 {
+            let buffered = BufReader::with_capacity(capacity, content.as_bytes());
+            let mut reader = StepReader::new(buffered);
+            let entities: Vec<String> = reader
+                .by_ref()
+                .map(|entity| format!("{:?}", entity.unwrap()))
+                .collect();
+            assert_eq!(entities, expected, "buffer of {capacity} bytes");
+            assert_eq!(reader.diagnostics(), &[], "buffer of {capacity} bytes");
+        }
+    }
+
+    #[test]
+    fn chunks_match_the_iterator() {
+        let data: String = (1..=10)
+            .map(|id| {
+                if id == 4 {
+                    "#4=IFCWALL(('a',$);\n".to_string()
+                } else {
+                    format!("#{id}=IFCWALL('w{id}',$);\n")
+                }
+            })
+            .collect();
+        let content = format!("{HEADER}{data}{FOOTER}");
+        let (ids, diagnostics, skipped) = read_lenient(&content);
+        assert_eq!(skipped, 1);
+        assert_eq!(
+            messages(&diagnostics),
+            vec!["line 9, column 1 (#4): unbalanced parentheses"]
+        );
+
+        // Chunks that end before, on and after the malformed statement
+        for max_statements in [1, 2, 3, 7, 100] {
+            let mut reader = StepReader::new(content.as_bytes());
+            let mut entities = Vec::new();
+            while let Some(chunk) = reader.next_chunk(max_statements) {
+                entities.extend(chunk.unwrap());
+            }
+            let chunk_ids: Vec<u64> = entities.iter().map(|c| c.entity.id).collect();
+            assert_eq!(chunk_ids, ids, "chunks of {max_statements}");
+            let first = &entities[0];
+            assert_eq!(first.position.line, 6);
+            assert_eq!(
+                first.end,
+                (HEADER.len() + "#1=IFCWALL('w1',$);".len()) as u64
+            );
+            let (_, chunk_diagnostics, chunk_skipped) = reader.finish();
+            assert_eq!(chunk_diagnostics, diagnostics);
+            assert_eq!(chunk_skipped, skipped);
+        }
+    }
+}
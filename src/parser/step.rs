@@ -1,281 +1,712 @@
-use std::collections::HashMap;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
 
 use crate::error::ParseError;
+use crate::parser::diagnostic::{Diagnostic, ParseOptions};
+use crate::parser::encoding::decode_step_string;
+use crate::parser::lexer::Token;
+use crate::parser::progress::ProgressSink;
+use crate::parser::reader::{ChunkEntity, StepReader};
+use crate::parser::writer::step_record;
+use crate::schema::{Schema, SchemaVersion};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum StepValue {
     String(String),
+    /// Hex digits of a `"0123"` binary value, including the leading unused-bits digit
+    Binary(String),
     Real(f64),
     Integer(i64),
     Boolean(bool),
     Enum(String),
     Reference(u64),
     List(Vec<StepValue>),
+    /// Typed value such as `IFCLENGTHMEASURE(3.5)` or `IFCLABEL('x')`
+    Typed {
+        type_name: String,
+        value: Box<StepValue>,
+    },
+    /// The parameters of a typed value that has none or several, as in
+    /// `T(1,2)`; unlike `T((1,2))` they are not a list. Only appears as the
+    /// `value` of [`StepValue::Typed`].
+    Parameters(Vec<StepValue>),
     Null,
     Derived,
 }
 
+impl StepValue {
+    /// The value with any typed wrapper removed.
+    #[must_use]
+    pub fn untyped(&self) -> &StepValue {
+        match self {
+            StepValue::Typed { value, .. } => value.untyped(),
+            other => other,
+        }
+    }
+
+    /// The defined type name (e.g. `IFCAREAMEASURE`) if the value is typed.
+    #[must_use]
+    pub fn type_name(&self) -> Option<&str> {
+        match self {
+            StepValue::Typed { type_name, .. } => Some(type_name),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StepEntity {
     pub id: u64,
     pub entity_type: String,
     pub values: Vec<StepValue>,
+    /// Schema version whose attribute order `values` follows
+    pub schema: SchemaVersion,
 }
 
-#[derive(Debug)]
+impl StepEntity {
+    /// Value of an attribute by name, e.g. `Elevation` of an `IFCBUILDINGSTOREY`,
+    /// wherever the entity's schema version puts it.
+    ///
+    /// Returns `None` for attributes the entity does not have and for
+    /// entity types that are not in the schema definitions.
+    ///
+    /// ```
+    /// use ifc_inspector::parser::{StepFile, StepValue};
+    ///
+    /// let step_file = StepFile::parse(
+    ///     "ISO-10303-21;HEADER;FILE_SCHEMA(('IFC2X3'));ENDSEC;DATA;\
+    ///      #1=IFCBUILDINGSTOREY('0gTDr5XGL0dhHgxvMXprD3',$,'Level 1',$,$,$,$,$,.ELEMENT.,3000.);\
+    ///      ENDSEC;END-ISO-10303-21;",
+    /// )?;
+    /// let storey = step_file.get_entity(1).unwrap();
+    /// assert_eq!(storey.get_attr("Elevation"), Some(&StepValue::Real(3000.0)));
+    /// # Ok::<(), ifc_inspector::error::ParseError>(())
+    /// ```
+    #[must_use]
+    pub fn get_attr(&self, name: &str) -> Option<&StepValue> {
+        let index = self.schema.attribute_index(&self.entity_type, name)?;
+        self.values.get(index)
+    }
+
+    /// Value of an attribute by name, laid out as in `schema` instead of
+    /// the entity's own schema version, e.g. one loaded from EXPRESS with
+    /// [`Schema::from_express`].
+    #[must_use]
+    pub fn get_attr_in(&self, schema: &Schema, name: &str) -> Option<&StepValue> {
+        let index = schema.attribute_index(&self.entity_type, name)?;
+        self.values.get(index)
+    }
+
+    /// IDs of all entities this entity references, in attribute order
+    /// (nested lists and typed values included, duplicates removed).
+    #[must_use]
+    pub fn references(&self) -> Vec<u64> {
+        fn collect(value: &StepValue, out: &mut Vec<u64>) {
+            match value {
+                StepValue::Reference(id) => out.push(*id),
+                StepValue::List(list) | StepValue::Parameters(list) => {
+                    for v in list {
+                        collect(v, out);
+                    }
+                }
+                StepValue::Typed { value, .. } => collect(value, out),
+                _ => {}
+            }
+        }
+
+        let mut out = Vec::new();
+        for value in &self.values {
+            collect(value, &mut out);
+        }
+        let mut seen = HashSet::with_capacity(out.len());
+        out.retain(|id| seen.insert(*id));
+        out
+    }
+}
+
+/// Which way [`StepFile::walk_references`] follows references.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceDirection {
+    /// From an entity to the entities it references (`#1=IFCWALL(..., #2)` → `#2`)
+    Forward,
+    /// From an entity to the entities that reference it
+    Inverse,
+}
+
+/// One entity reached while walking the reference graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReferenceStep {
+    pub id: u64,
+    /// Number of hops from the start entity (0 = the start entity itself)
+    pub depth: usize,
+    /// The entity this one was reached from
+    pub parent: Option<u64>,
+}
+
+/// Contents of the HEADER section (`FILE_DESCRIPTION`, `FILE_NAME`, `FILE_SCHEMA`).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StepHeader {
+    pub description: Vec<String>,
+    /// Model view definition from `ViewDefinition [...]` in the description
+    pub view_definition: String,
+    pub implementation_level: String,
+    pub name: String,
+    pub time_stamp: String,
+    pub author: Vec<String>,
+    pub organization: Vec<String>,
+    pub preprocessor_version: String,
+    pub originating_system: String,
+    pub authorization: String,
+    pub schemas: Vec<String>,
+    /// Other records such as `FILE_POPULATION(...)`, as STEP text, so they
+    /// are written back unchanged
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub other_records: Vec<String>,
+}
+
+impl StepHeader {
+    pub(crate) fn apply_record(&mut self, keyword: &str, values: &[StepValue]) {
+        match keyword {
+            "FILE_DESCRIPTION" => {
+                self.description = header_strings(values.first());
+                self.implementation_level = header_string(values.get(1));
+                self.view_definition = self
+                    .description
+                    .iter()
+                    .find_map(|d| {
+                        let rest = d.trim().strip_prefix("ViewDefinition")?;
+                        let start = rest.find('[')?;
+                        let end = rest.rfind(']')?;
+                        (start < end).then(|| rest[start + 1..end].trim().to_string())
+                    })
+                    .unwrap_or_default();
+            }
+            "FILE_NAME" => {
+                self.name = header_string(values.first());
+                self.time_stamp = header_string(values.get(1));
+                self.author = header_strings(values.get(2));
+                self.organization = header_strings(values.get(3));
+                self.preprocessor_version = header_string(values.get(4));
+                self.originating_system = header_string(values.get(5));
+                self.authorization = header_string(values.get(6));
+            }
+            "FILE_SCHEMA" => self.schemas = header_strings(values.first()),
+            _ => self.other_records.push(step_record(keyword, values)),
+        }
+    }
+}
+
+fn header_string(value: Option<&StepValue>) -> String {
+    match value {
+        Some(StepValue::String(s)) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+fn header_strings(value: Option<&StepValue>) -> Vec<String> {
+    match value {
+        Some(StepValue::List(list)) => list
+            .iter()
+            .map(|v| header_string(Some(v)))
+            .filter(|s| !s.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Interned entity type name (index into [`TypeInterner`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct TypeSymbol(u32);
+
+impl TypeSymbol {
+    pub(crate) fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub(crate) fn from_index(index: usize) -> Self {
+        Self(index as u32)
+    }
+}
+
+/// Stores each distinct entity type name once and hands out small symbols.
+#[derive(Debug, Default)]
+pub(crate) struct TypeInterner {
+    symbols: HashMap<Box<str>, TypeSymbol>,
+    names: Vec<Box<str>>,
+}
+
+impl TypeInterner {
+    pub(crate) fn intern(&mut self, name: &str) -> TypeSymbol {
+        if let Some(&symbol) = self.symbols.get(name) {
+            return symbol;
+        }
+        let symbol = TypeSymbol(self.names.len() as u32);
+        self.names.push(name.into());
+        self.symbols.insert(name.into(), symbol);
+        symbol
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<TypeSymbol> {
+        self.symbols.get(name).copied()
+    }
+
+    pub(crate) fn resolve(&self, symbol: TypeSymbol) -> &str {
+        &self.names[symbol.0 as usize]
+    }
+}
+
+/// Number of statements read before a chunk is parsed in parallel.
+pub(crate) const CHUNK_STATEMENTS: usize = 16 * 1024;
+
+#[derive(Debug, Default)]
 pub struct StepFile {
+    pub header: StepHeader,
     pub entities: HashMap<u64, StepEntity>,
     pub schema: String,
+    /// Problems found while parsing (skipped statements, truncation, ...)
+    pub diagnostics: Vec<Diagnostic>,
+    /// Number of DATA statements that could not be parsed
+    pub skipped_entities: usize,
+    types: TypeInterner,
+    // Entity IDs per interned type, in file order
+    type_index: Vec<Vec<u64>>,
+    // Referenced ID → IDs of entities referencing it, in file order
+    inverse_index: HashMap<u64, Vec<u64>>,
 }
 
 impl StepFile {
     pub fn parse(content: &str) -> Result<Self, ParseError> {
-        let mut entities = HashMap::new();
-        let mut schema = String::new();
-        let mut in_data = false;
-
-        for line in content.lines() {
-            let line = line.trim();
-
-            // Parse schema
-            if line.starts_with("FILE_SCHEMA") {
-                if let Some(start) = line.find("('") {
-                    if let Some(end) = line[start + 2..].find('\'') {
-                        schema = line[start + 2..start + 2 + end].to_string();
+        Self::from_reader(content.as_bytes())
+    }
+
+    pub fn parse_with_options(content: &str, options: ParseOptions) -> Result<Self, ParseError> {
+        Self::from_reader_with_options(content.as_bytes(), options)
+    }
+
+    /// Parse a STEP file from any buffered reader without loading the raw
+    /// text into memory first.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, ParseError> {
+        Self::from_reader_with_options(reader, ParseOptions::default())
+    }
+
+    /// Parse a STEP file from any buffered reader with explicit options
+    /// (e.g. strict mode, which fails on the first diagnostic).
+    ///
+    /// DATA statements are parsed in parallel, a chunk at a time; the result
+    /// is the same as parsing them one by one.
+    pub fn from_reader_with_options<R: BufRead>(
+        reader: R,
+        options: ParseOptions,
+    ) -> Result<Self, ParseError> {
+        Self::from_reader_with_progress(reader, options, &ProgressSink::none())
+    }
+
+    /// Parse like [`StepFile::from_reader_with_options`], reporting the number
+    /// of entities after each chunk.
+    pub(crate) fn from_reader_with_progress<R: BufRead>(
+        reader: R,
+        options: ParseOptions,
+        progress: &ProgressSink<'_>,
+    ) -> Result<Self, ParseError> {
+        // Strict mode stops at the first problem in file order, duplicates included
+        let chunk_statements = if options.strict { 1 } else { CHUNK_STATEMENTS };
+        let mut reader = StepReader::with_options(reader, options);
+        let mut step_file = StepFile::default();
+
+        while let Some(chunk) = reader.next_chunk(chunk_statements) {
+            for ChunkEntity {
+                position, entity, ..
+            } in chunk?
+            {
+                if step_file.entities.contains_key(&entity.id) {
+                    let diagnostic = Diagnostic::new(
+                        position,
+                        Some(entity.id),
+                        "duplicate entity instance name; the later definition wins".to_string(),
+                    );
+                    if let Some(err) = reader.report(diagnostic) {
+                        return Err(err);
                     }
                 }
-                continue;
+                step_file.insert(entity);
             }
+            progress.reading(step_file.entities.len())?;
+        }
 
-            if line == "DATA;" {
-                in_data = true;
-                continue;
-            }
-            if line == "ENDSEC;" {
-                in_data = false;
-                continue;
-            }
+        (
+            step_file.header,
+            step_file.diagnostics,
+            step_file.skipped_entities,
+        ) = reader.finish();
+        // Duplicates are found after their chunk; keep everything in file order
+        step_file.diagnostics.sort_by_key(|d| d.offset);
+        step_file.schema = step_file
+            .header
+            .schemas
+            .first()
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(step_file)
+    }
 
-            if in_data && line.starts_with('#') {
-                if let Some(entity) = Self::parse_entity_line(line) {
-                    entities.insert(entity.id, entity);
+    /// Add an entity, keeping the per-type index up to date.
+    ///
+    /// A later entity with the same ID replaces the earlier one.
+    pub(crate) fn insert(&mut self, entity: StepEntity) {
+        let symbol = self.types.intern(&entity.entity_type);
+        let id = entity.id;
+
+        for target in entity.references() {
+            self.inverse_index.entry(target).or_default().push(id);
+        }
+
+        if let Some(old) = self.entities.insert(id, entity) {
+            if let Some(old_symbol) = self.types.get(&old.entity_type) {
+                self.type_index[old_symbol.0 as usize].retain(|&i| i != id);
+            }
+            for target in old.references() {
+                if let Some(referrers) = self.inverse_index.get_mut(&target) {
+                    // Drop only the entry pushed for the replaced entity
+                    if let Some(pos) = referrers.iter().position(|&i| i == id) {
+                        referrers.remove(pos);
+                    }
                 }
             }
         }
 
-        Ok(StepFile { entities, schema })
+        let slot = symbol.0 as usize;
+        if slot == self.type_index.len() {
+            self.type_index.push(Vec::new());
+        }
+        self.type_index[slot].push(id);
     }
 
-    fn parse_entity_line(line: &str) -> Option<StepEntity> {
-        // Format: #123=IFCWALL('guid',#ref,'name',...);
-        let line = line.trim_end_matches(';');
+    pub(crate) fn parse_entity(tokens: &[Token], schema: SchemaVersion) -> Option<StepEntity> {
+        // Format: #123=IFCWALL('guid',#ref,'name',...)
+        let (Token::EntityName(id), Token::Equals, Token::Keyword(entity_type)) =
+            (tokens.first()?, tokens.get(1)?, tokens.get(2)?)
+        else {
+            // Complex entity instances (#1=(A()B());) are not supported
+            return None;
+        };
 
-        let eq_pos = line.find('=')?;
-        let id: u64 = line[1..eq_pos].parse().ok()?;
-
-        let rest = &line[eq_pos + 1..];
-        let paren_pos = rest.find('(')?;
-        let entity_type = rest[..paren_pos].to_string();
-
-        let values_str = &rest[paren_pos + 1..rest.len() - 1];
-        let values = Self::parse_values(values_str);
+        let mut pos = 3;
+        let values = Self::parse_list(tokens, &mut pos)?;
+        if pos != tokens.len() {
+            return None;
+        }
 
         Some(StepEntity {
-            id,
-            entity_type,
+            id: *id,
+            entity_type: (*entity_type).to_string(),
             values,
+            schema,
         })
     }
 
-    fn parse_values(s: &str) -> Vec<StepValue> {
+    /// Parse a header record such as `FILE_NAME(...)` into its parameters.
+    pub(crate) fn parse_record(tokens: &[Token]) -> Option<Vec<StepValue>> {
+        let mut pos = 1;
+        let values = Self::parse_list(tokens, &mut pos)?;
+        (pos == tokens.len()).then_some(values)
+    }
+
+    /// Parse a parenthesised, comma-separated parameter list starting at `pos`.
+    fn parse_list(tokens: &[Token], pos: &mut usize) -> Option<Vec<StepValue>> {
+        if tokens.get(*pos)? != &Token::LParen {
+            return None;
+        }
+        *pos += 1;
+
         let mut values = Vec::new();
-        let mut current = String::new();
-        let mut in_string = false;
-        let mut paren_depth = 0;
-
-        for ch in s.chars() {
-            match ch {
-                '\'' if paren_depth == 0 => {
-                    in_string = !in_string;
-                    current.push(ch);
-                }
-                '(' if !in_string => {
-                    paren_depth += 1;
-                    current.push(ch);
-                }
-                ')' if !in_string => {
-                    paren_depth -= 1;
-                    current.push(ch);
-                }
-                ',' if !in_string && paren_depth == 0 => {
-                    values.push(Self::parse_single_value(current.trim()));
-                    current.clear();
+        if tokens.get(*pos)? == &Token::RParen {
+            *pos += 1;
+            return Some(values);
+        }
+
+        loop {
+            values.push(Self::parse_value(tokens, pos)?);
+            match tokens.get(*pos)? {
+                Token::Comma => *pos += 1,
+                Token::RParen => {
+                    *pos += 1;
+                    return Some(values);
                 }
-                _ => current.push(ch),
+                _ => return None,
             }
         }
+    }
 
-        if !current.is_empty() {
-            values.push(Self::parse_single_value(current.trim()));
-        }
+    fn parse_value(tokens: &[Token], pos: &mut usize) -> Option<StepValue> {
+        let value = match tokens.get(*pos)? {
+            Token::LParen => return Self::parse_list(tokens, pos).map(StepValue::List),
+            Token::Keyword(type_name) => {
+                // Typed value like IFCBOOLEAN(.T.)
+                *pos += 1;
+                let mut inner = Self::parse_list(tokens, pos)?;
+                let value = if inner.len() == 1 {
+                    inner.remove(0)
+                } else {
+                    StepValue::Parameters(inner)
+                };
+                return Some(StepValue::Typed {
+                    type_name: (*type_name).to_string(),
+                    value: Box::new(value),
+                });
+            }
+            Token::String(raw) => StepValue::String(decode_step_string(raw)),
+            Token::Binary(raw) => StepValue::Binary((*raw).to_string()),
+            Token::Integer(i) => StepValue::Integer(*i),
+            Token::Real(f) => StepValue::Real(*f),
+            Token::Enumeration("T") => StepValue::Boolean(true),
+            Token::Enumeration("F") => StepValue::Boolean(false),
+            Token::Enumeration(e) => StepValue::Enum((*e).to_string()),
+            Token::EntityName(id) => StepValue::Reference(*id),
+            Token::Dollar => StepValue::Null,
+            Token::Asterisk => StepValue::Derived,
+            Token::RParen | Token::Comma | Token::Equals | Token::Semicolon => return None,
+        };
+        *pos += 1;
+        Some(value)
+    }
 
-        values
+    #[must_use]
+    pub fn get_entity(&self, id: u64) -> Option<&StepEntity> {
+        self.entities.get(&id)
     }
 
-    fn parse_single_value(s: &str) -> StepValue {
-        let s = s.trim();
+    /// IDs of all entities that reference `id`, in file order.
+    ///
+    /// Answers questions such as "which relationships point at this wall".
+    #[must_use]
+    pub fn referenced_by(&self, id: u64) -> &[u64] {
+        self.inverse_index.get(&id).map_or(&[], Vec::as_slice)
+    }
 
-        if s == "$" {
-            return StepValue::Null;
-        }
-        if s == "*" {
-            return StepValue::Derived;
-        }
-        if let Some(stripped) = s.strip_prefix('#') {
-            if let Ok(id) = stripped.parse::<u64>() {
-                return StepValue::Reference(id);
-            }
-        }
-        if s.starts_with('\'') && s.ends_with('\'') {
-            let raw = &s[1..s.len() - 1];
-            return StepValue::String(decode_step_string(raw));
+    /// Breadth-first walk of the reference graph starting at `start`, up to
+    /// `max_depth` hops. Each entity is visited once; the start entity is
+    /// returned first with depth 0. Dangling references are skipped.
+    #[must_use]
+    pub fn walk_references(
+        &self,
+        start: u64,
+        max_depth: usize,
+        direction: ReferenceDirection,
+    ) -> Vec<ReferenceStep> {
+        if !self.entities.contains_key(&start) {
+            return Vec::new();
         }
-        if s.starts_with('.') && s.ends_with('.') {
-            let inner = &s[1..s.len() - 1];
-            if inner == "T" {
-                return StepValue::Boolean(true);
+
+        let mut visited = HashSet::from([start]);
+        let mut steps = vec![ReferenceStep {
+            id: start,
+            depth: 0,
+            parent: None,
+        }];
+        let mut next = 0;
+
+        while next < steps.len() {
+            let current = steps[next];
+            next += 1;
+            if current.depth >= max_depth {
+                continue;
             }
-            if inner == "F" {
-                return StepValue::Boolean(false);
+
+            let neighbours = match direction {
+                ReferenceDirection::Forward => self
+                    .entities
+                    .get(&current.id)
+                    .map(StepEntity::references)
+                    .unwrap_or_default(),
+                ReferenceDirection::Inverse => self.referenced_by(current.id).to_vec(),
+            };
+
+            for id in neighbours {
+                if self.entities.contains_key(&id) && visited.insert(id) {
+                    steps.push(ReferenceStep {
+                        id,
+                        depth: current.depth + 1,
+                        parent: Some(current.id),
+                    });
+                }
             }
-            return StepValue::Enum(inner.to_string());
-        }
-        if s.starts_with('(') && s.ends_with(')') {
-            let inner = &s[1..s.len() - 1];
-            return StepValue::List(Self::parse_values(inner));
-        }
-        if let Ok(i) = s.parse::<i64>() {
-            return StepValue::Integer(i);
-        }
-        if let Ok(f) = s.parse::<f64>() {
-            return StepValue::Real(f);
-        }
-        // Typed value like IFCBOOLEAN(.T.)
-        if let Some(paren_pos) = s.find('(') {
-            let inner = &s[paren_pos + 1..s.len() - 1];
-            return Self::parse_single_value(inner);
         }
 
-        StepValue::String(s.to_string())
+        steps
     }
 
+    /// IDs of all entities of a given type, in file order.
     #[must_use]
-    pub fn get_entity(&self, id: u64) -> Option<&StepEntity> {
-        self.entities.get(&id)
+    pub fn entity_ids_by_type(&self, entity_type: &str) -> &[u64] {
+        self.types
+            .get(entity_type)
+            .map_or(&[], |symbol| &self.type_index[symbol.0 as usize])
     }
 
+    /// All entities of a given type, in file order.
     #[must_use]
     pub fn get_entities_by_type(&self, entity_type: &str) -> Vec<&StepEntity> {
-        self.entities
-            .values()
-            .filter(|e| e.entity_type == entity_type)
+        self.entity_ids_by_type(entity_type)
+            .iter()
+            .filter_map(|id| self.entities.get(id))
+            .collect()
+    }
+
+    /// All entities of a given type or any of its subtypes, e.g. every
+    /// `IFCWALL`, `IFCWALLSTANDARDCASE` and `IFCWALLELEMENTEDCASE` for
+    /// `IFCWALL` (see [`EntitySource::entity_ids_by_supertype`]).
+    #[must_use]
+    pub fn get_entities_by_supertype(&self, supertype: &str) -> Vec<&StepEntity> {
+        self.entity_ids_by_supertype(supertype)
+            .iter()
+            .filter_map(|id| self.entities.get(id))
+            .collect()
+    }
+
+    /// Distinct entity types with their instance counts, in order of first appearance.
+    ///
+    /// Types whose only instances were replaced by a later duplicate ID are
+    /// left out.
+    #[must_use]
+    pub fn entity_type_counts(&self) -> Vec<(&str, usize)> {
+        self.type_index
+            .iter()
+            .enumerate()
+            .filter(|(_, ids)| !ids.is_empty())
+            .map(|(i, ids)| (self.types.resolve(TypeSymbol(i as u32)), ids.len()))
             .collect()
     }
 }
 
-/// Decode STEP/IFC encoded strings with Unicode escape sequences.
-/// Supports:
-/// - `\X2\XXXX\X0\` - 2-byte Unicode (BMP), can have multiple 4-char hex codes
-/// - `\X\XX` - 1-byte ISO 8859-1
-/// - `\\` - escaped backslash
-/// - `''` - escaped apostrophe
-fn decode_step_string(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    let mut chars = s.chars().peekable();
-
-    while let Some(ch) = chars.next() {
-        if ch == '\\' {
-            match chars.peek() {
-                Some('X') => {
-                    chars.next(); // consume 'X'
-                    match chars.peek() {
-                        Some('2') => {
-                            // \X2\XXXX...\X0\ - 2-byte Unicode encoding
-                            chars.next(); // consume '2'
-                            chars.next(); // consume '\'
-
-                            let mut hex = String::new();
-                            while let Some(&c) = chars.peek() {
-                                if c == '\\' {
-                                    break;
-                                }
-                                hex.push(c);
-                                chars.next();
-                            }
-                            // Skip \X0\
-                            if chars.peek() == Some(&'\\') {
-                                chars.next(); // '\'
-                                chars.next(); // 'X'
-                                chars.next(); // '0'
-                                chars.next(); // '\'
-                            }
-                            // Decode hex pairs (each 4 chars = one Unicode char)
-                            for chunk in hex.as_bytes().chunks(4) {
-                                if chunk.len() == 4 {
-                                    if let Ok(s) = std::str::from_utf8(chunk) {
-                                        if let Ok(code) = u32::from_str_radix(s, 16) {
-                                            if let Some(c) = char::from_u32(code) {
-                                                result.push(c);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        Some('\\') => {
-                            // \X\ followed by 2 hex digits - ISO 8859-1
-                            chars.next(); // consume '\'
-                            let mut hex = String::new();
-                            for _ in 0..2 {
-                                if let Some(&c) = chars.peek() {
-                                    hex.push(c);
-                                    chars.next();
-                                }
-                            }
-                            if let Ok(code) = u8::from_str_radix(&hex, 16) {
-                                result.push(code as char);
-                            }
-                        }
-                        _ => {
-                            result.push('\\');
-                            result.push('X');
-                        }
-                    }
-                }
-                Some('\\') => {
-                    chars.next();
-                    result.push('\\');
-                }
-                Some('S') => {
-                    // \S\X - single char shift (ISO 8859-1 high bit)
-                    chars.next(); // 'S'
-                    chars.next(); // '\'
-                    if let Some(c) = chars.next() {
-                        result.push(((c as u8) + 128) as char);
-                    }
-                }
-                _ => result.push('\\'),
-            }
-        } else if ch == '\'' {
-            // '' is escaped apostrophe in STEP
-            if chars.peek() == Some(&'\'') {
-                chars.next();
-            }
-            result.push('\'');
-        } else {
-            result.push(ch);
-        }
+/// Read access to the entities of a parsed STEP file, whether they are held
+/// in memory ([`StepFile`]) or parsed on demand from a memory-mapped file
+/// ([`MappedStepFile`](crate::parser::MappedStepFile)).
+///
+/// The IFC extraction is written against this trait, so both give the same
+/// [`IfcProject`](crate::model::IfcProject).
+pub trait EntitySource: Sync {
+    fn header(&self) -> &StepHeader;
+
+    fn schema(&self) -> &str;
+
+    /// Problems found while parsing
+    fn diagnostics(&self) -> &[Diagnostic];
+
+    /// Number of DATA statements that could not be parsed
+    fn skipped_entities(&self) -> usize;
+
+    /// The entity with the given ID, borrowed if it is held in memory.
+    fn entity(&self, id: u64) -> Option<Cow<'_, StepEntity>>;
+
+    /// Type name of the entity with the given ID, without parsing it.
+    fn entity_type(&self, id: u64) -> Option<&str>;
+
+    /// IDs of all entities of a given type, in file order.
+    fn entity_ids_by_type(&self, entity_type: &str) -> &[u64];
+
+    /// Distinct entity types with their instance counts, in order of first appearance.
+    fn entity_type_counts(&self) -> Vec<(&str, usize)>;
+
+    /// All entities of a given type, in file order.
+    fn entities_by_type(&self, entity_type: &str) -> impl Iterator<Item = Cow<'_, StepEntity>> {
+        self.entity_ids_by_type(entity_type)
+            .iter()
+            .filter_map(|&id| self.entity(id))
+    }
+
+    /// Schema version named in `FILE_SCHEMA` (IFC4 if it is not recognised).
+    fn schema_version(&self) -> SchemaVersion {
+        SchemaVersion::from_schemas(&self.header().schemas)
+    }
+
+    /// IDs of all entities of `supertype` or any of its subtypes in the
+    /// file's schema version, e.g. every wall, slab, door, ... for
+    /// `IFCBUILDINGELEMENT`. Grouped by type in order of first appearance,
+    /// each type in file order.
+    fn entity_ids_by_supertype(&self, supertype: &str) -> Vec<u64> {
+        let version = self.schema_version();
+        self.entity_type_counts()
+            .into_iter()
+            .filter(|(entity_type, _)| version.is_subtype_of(entity_type, supertype))
+            .flat_map(|(entity_type, _)| self.entity_ids_by_type(entity_type))
+            .copied()
+            .collect()
+    }
+
+    /// All entities of `supertype` or any of its subtypes, in the order of
+    /// [`EntitySource::entity_ids_by_supertype`].
+    fn entities_by_supertype(&self, supertype: &str) -> impl Iterator<Item = Cow<'_, StepEntity>> {
+        self.entity_ids_by_supertype(supertype)
+            .into_iter()
+            .filter_map(|id| self.entity(id))
+    }
+}
+
+impl EntitySource for StepFile {
+    fn header(&self) -> &StepHeader {
+        &self.header
+    }
+
+    fn schema(&self) -> &str {
+        &self.schema
+    }
+
+    fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    fn skipped_entities(&self) -> usize {
+        self.skipped_entities
+    }
+
+    fn entity(&self, id: u64) -> Option<Cow<'_, StepEntity>> {
+        self.entities.get(&id).map(Cow::Borrowed)
+    }
+
+    fn entity_type(&self, id: u64) -> Option<&str> {
+        self.entities.get(&id).map(|e| e.entity_type.as_str())
     }
 
-    result
+    fn entity_ids_by_type(&self, entity_type: &str) -> &[u64] {
+        StepFile::entity_ids_by_type(self, entity_type)
+    }
+
+    fn entity_type_counts(&self) -> Vec<(&str, usize)> {
+        StepFile::entity_type_counts(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn parse(data: &str) -> StepFile {
+        StepFile::parse(&format!(
+            "ISO-10303-21;HEADER;FILE_SCHEMA(('IFC4'));ENDSEC;DATA;{data}ENDSEC;END-ISO-10303-21;"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn later_duplicate_replaces_the_earlier_entity() {
+        let step_file = parse("#1=IFCWALL('a',$);#2=IFCSLAB('b',$);#1=IFCDOOR('c',$);");
+        assert_eq!(step_file.entities.len(), 2);
+        assert_eq!(step_file.entity_type(1), Some("IFCDOOR"));
+        assert_eq!(step_file.entity_ids_by_type("IFCWALL"), &[] as &[u64]);
+        assert_eq!(step_file.entity_ids_by_type("IFCDOOR"), &[1]);
+        assert_eq!(step_file.diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn type_counts_leave_out_types_without_instances() {
+        let step_file = parse("#1=IFCWALL('a',$);#2=IFCWALL('b',$);#3=IFCSLAB('c',$);#1=IFCDOOR('d',$);#3=IFCDOOR('e',$);");
+        assert_eq!(
+            step_file.entity_type_counts(),
+            vec![("IFCWALL", 1), ("IFCDOOR", 2)]
+        );
+    }
 }
@@ -0,0 +1,367 @@
+//! ISO-10303-21 serializer for [`StepFile`].
+//!
+//! Writes the HEADER and DATA sections back in valid STEP syntax, so a file
+//! that was parsed can be written out again with equivalent content.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::parser::encoding::encode_step_string;
+use crate::parser::step::{StepEntity, StepFile, StepHeader, StepValue};
+
+impl StepFile {
+    /// Write the file in ISO-10303-21 syntax.
+    ///
+    /// Entities are written in ascending ID order. Strings are re-encoded
+    /// with `\X2\`…`\X0\` escapes and reals keep their full precision.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ifc_inspector::parser::StepFile;
+    ///
+    /// let content = std::fs::read_to_string("model.ifc")?;
+    /// let step_file = StepFile::parse(&content)?;
+    /// step_file.write_to(std::fs::File::create("copy.ifc")?)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(writer);
+
+        writeln!(writer, "ISO-10303-21;")?;
+        writeln!(writer, "HEADER;")?;
+        write_header(&mut writer, &self.header, &self.schema)?;
+        writeln!(writer, "ENDSEC;")?;
+        writeln!(writer, "DATA;")?;
+
+        let mut ids: Vec<u64> = self.entities.keys().copied().collect();
+        ids.sort_unstable();
+        let mut line = String::new();
+        for id in ids {
+            line.clear();
+            write_entity(&mut line, &self.entities[&id]);
+            writeln!(writer, "{line}")?;
+        }
+
+        writeln!(writer, "ENDSEC;")?;
+        writeln!(writer, "END-ISO-10303-21;")?;
+        writer.flush()
+    }
+
+    /// The file in ISO-10303-21 syntax as a string.
+    #[must_use]
+    pub fn to_step_string(&self) -> String {
+        let mut out = Vec::new();
+        // Writing to a Vec cannot fail
+        let _ = self.write_to(&mut out);
+        String::from_utf8(out).unwrap_or_default()
+    }
+}
+
+fn write_header<W: Write>(writer: &mut W, header: &StepHeader, schema: &str) -> io::Result<()> {
+    let list = |items: &[String]| {
+        let values: Vec<StepValue> = items.iter().cloned().map(StepValue::String).collect();
+        let mut out = String::new();
+        write_value(&mut out, &StepValue::List(values));
+        out
+    };
+    let string = |s: &str| format!("'{}'", encode_step_string(s));
+
+    let description = if header.description.is_empty() && !header.view_definition.is_empty() {
+        vec![format!("ViewDefinition [{}]", header.view_definition)]
+    } else {
+        header.description.clone()
+    };
+    let implementation_level = if header.implementation_level.is_empty() {
+        "2;1"
+    } else {
+        &header.implementation_level
+    };
+    let schemas = if header.schemas.is_empty() && !schema.is_empty() {
+        vec![schema.to_string()]
+    } else {
+        header.schemas.clone()
+    };
+
+    writeln!(
+        writer,
+        "FILE_DESCRIPTION({},{});",
+        list(&description),
+        string(implementation_level)
+    )?;
+    writeln!(
+        writer,
+        "FILE_NAME({},{},{},{},{},{},{});",
+        string(&header.name),
+        string(&header.time_stamp),
+        list(&header.author),
+        list(&header.organization),
+        string(&header.preprocessor_version),
+        string(&header.originating_system),
+        string(&header.authorization)
+    )?;
+    writeln!(writer, "FILE_SCHEMA({});", list(&schemas))?;
+    for record in &header.other_records {
+        writeln!(writer, "{record};")?;
+    }
+    Ok(())
+}
+
+/// A header record such as `FILE_POPULATION('x',$)`, without the `;`.
+pub(crate) fn step_record(keyword: &str, values: &[StepValue]) -> String {
+    let mut out = format!("{keyword}(");
+    write_values(&mut out, values);
+    out.push(')');
+    out
+}
+
+fn write_entity(out: &mut String, entity: &StepEntity) {
+    let _ = write!(out, "#{}={}(", entity.id, entity.entity_type);
+    write_values(out, &entity.values);
+    out.push_str(");");
+}
+
+fn write_values(out: &mut String, values: &[StepValue]) {
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_value(out, value);
+    }
+}
+
+fn write_value(out: &mut String, value: &StepValue) {
+    match value {
+        StepValue::String(s) => {
+            out.push('\'');
+            out.push_str(&encode_step_string(s));
+            out.push('\'');
+        }
+        StepValue::Binary(hex) => {
+            out.push('"');
+            out.push_str(hex);
+            out.push('"');
+        }
+        StepValue::Real(f) => out.push_str(&format_step_real(*f)),
+        StepValue::Integer(i) => {
+            let _ = write!(out, "{i}");
+        }
+        StepValue::Boolean(b) => out.push_str(if *b { ".T." } else { ".F." }),
+        StepValue::Enum(e) => {
+            let _ = write!(out, ".{e}.");
+        }
+        StepValue::Reference(id) => {
+            let _ = write!(out, "#{id}");
+        }
+        StepValue::List(list) => {
+            out.push('(');
+            write_values(out, list);
+            out.push(')');
+        }
+        StepValue::Typed { type_name, value } => {
+            out.push_str(type_name);
+            out.push('(');
+            write_value(out, value);
+            out.push(')');
+        }
+        StepValue::Parameters(values) => write_values(out, values),
+        StepValue::Null => out.push('$'),
+        StepValue::Derived => out.push('*'),
+    }
+}
+
+/// Format a real so that it reads back as the same `f64` and is valid STEP
+/// (a decimal point is mandatory: `3.`, `1.5E-05`).
+#[must_use]
+pub fn format_step_real(f: f64) -> String {
+    if !f.is_finite() {
+        // STEP has no representation for NaN or infinity
+        return "$".to_string();
+    }
+
+    // Debug formatting is the shortest representation that round-trips
+    let repr = format!("{f:?}");
+    let (mantissa, exponent) = match repr.split_once('e') {
+        Some((m, e)) => (m, Some(e)),
+        None => (repr.as_str(), None),
+    };
+
+    let mut out = mantissa.to_string();
+    if !out.contains('.') {
+        out.push('.');
+    }
+    if let Some(exponent) = exponent {
+        out.push('E');
+        out.push_str(exponent);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::SchemaVersion;
+    use pretty_assertions::assert_eq;
+
+    const HEADER: &str = "ISO-10303-21;HEADER;\
+        FILE_DESCRIPTION(('ViewDefinition [CoordinationView]'),'2;1');\
+        FILE_NAME('model.ifc','2024-01-01T00:00:00',('Anna'),('ACME'),'pre','sys','auth');\
+        FILE_SCHEMA(('IFC4'));\
+        FILE_POPULATION('IFC4','walls',$);\
+        ENDSEC;DATA;";
+    const FOOTER: &str = "ENDSEC;END-ISO-10303-21;";
+
+    fn parse(data: &str) -> StepFile {
+        StepFile::parse(&format!("{HEADER}{data}{FOOTER}")).unwrap()
+    }
+
+    /// Parse the written file again and check it has the same content.
+    fn assert_round_trip(step_file: &StepFile) -> StepFile {
+        let written = step_file.to_step_string();
+        let reparsed = StepFile::parse(&written).unwrap();
+        assert!(
+            reparsed.diagnostics.is_empty(),
+            "{:?}",
+            reparsed.diagnostics
+        );
+        assert_eq!(
+            format!("{:?}", reparsed.header),
+            format!("{:?}", step_file.header)
+        );
+        assert_eq!(reparsed.schema, step_file.schema);
+        assert_eq!(reparsed.entities.len(), step_file.entities.len());
+        for (id, entity) in &step_file.entities {
+            let copy = &reparsed.entities[id];
+            assert_eq!(copy.entity_type, entity.entity_type);
+            assert_eq!(copy.values, entity.values, "#{id}");
+        }
+        // Writing again gives the same text
+        assert_eq!(reparsed.to_step_string(), written);
+        reparsed
+    }
+
+    #[test]
+    fn round_trips_entities_and_header() {
+        let step_file = parse(
+            "#1=IFCPROJECT('0YvctVUKr0kugbFTf53O9L',$,'Project',$,$,$,$,(#2),$);\
+             #2=IFCGEOMETRICREPRESENTATIONCONTEXT($,'Model',3,1.E-05,#3,$);\
+             #3=IFCAXIS2PLACEMENT3D(#4,*,$);\
+             #4=IFCCARTESIANPOINT((0.,-1.5,2.25E+20));\
+             #5=IFCWALL('w',$,'Wall',$,$,$,$,$,.STANDARD.);\
+             #6=IFCPIXELTEXTURE(.T.,.F.,$,$,$,10,10,3,(\"0FF00\",\"1AB\"));",
+        );
+        let reparsed = assert_round_trip(&step_file);
+        assert_eq!(reparsed.header.view_definition, "CoordinationView");
+        assert_eq!(reparsed.header.author, ["Anna"]);
+        assert_eq!(reparsed.header.schemas, ["IFC4"]);
+    }
+
+    #[test]
+    fn keeps_other_header_records() {
+        let step_file = parse("");
+        assert_eq!(
+            step_file.header.other_records,
+            ["FILE_POPULATION('IFC4','walls',$)"]
+        );
+        assert!(step_file
+            .to_step_string()
+            .contains("\nFILE_POPULATION('IFC4','walls',$);\n"));
+        assert_round_trip(&step_file);
+    }
+
+    #[test]
+    fn round_trips_typed_values() {
+        let step_file = parse(
+            "#1=IFCPROPERTYSINGLEVALUE('Width',$,IFCLENGTHMEASURE(200.),$);\
+             #2=IFCPROPERTYSINGLEVALUE('Flag',$,IFCBOOLEAN(.T.),$);\
+             #3=IFCPROPERTYLISTVALUE('L',$,(IFCLABEL('a'),IFCINTEGER(3)),$);\
+             #4=IFCPROPERTYSINGLEVALUE('C',$,IFCCOMPLEXNUMBER((1.,2.)),$);\
+             #5=IFCPROPERTYSINGLEVALUE('P',$,T(1,2),$);\
+             #6=IFCPROPERTYSINGLEVALUE('E',$,T(),$);",
+        );
+        assert_eq!(
+            step_file.entities[&5].values[2],
+            StepValue::Typed {
+                type_name: "T".to_string(),
+                value: Box::new(StepValue::Parameters(vec![
+                    StepValue::Integer(1),
+                    StepValue::Integer(2),
+                ])),
+            }
+        );
+        let written = step_file.to_step_string();
+        assert!(written.contains("IFCCOMPLEXNUMBER((1.0,2.0))"), "{written}");
+        assert!(written.contains("#5=IFCPROPERTYSINGLEVALUE('P',$,T(1,2),$);"));
+        assert!(written.contains("#6=IFCPROPERTYSINGLEVALUE('E',$,T(),$);"));
+        assert_round_trip(&step_file);
+    }
+
+    #[test]
+    fn round_trips_strings_with_escapes() {
+        let step_file = parse(
+            "#1=IFCLABEL('It''s');\
+             #2=IFCLABEL('back\\\\slash');\
+             #3=IFCLABEL('\\X2\\00D3\\X0\\w \\X2\\D83DDEAA\\X0\\');\
+             #4=IFCLABEL('\\S\\D\\PE\\\\S\\D');\
+             #5=IFCLABEL('');",
+        );
+        assert_eq!(
+            step_file.entities[&1].values[0],
+            StepValue::String("It's".to_string())
+        );
+        assert_eq!(
+            step_file.entities[&3].values[0],
+            StepValue::String("Ów 🚪".to_string())
+        );
+        assert_round_trip(&step_file);
+    }
+
+    #[test]
+    fn round_trips_nested_lists() {
+        let step_file = parse(
+            "#1=IFCCARTESIANPOINTLIST3D(((0.,0.,0.),(1.,0.,0.),(1.,1.,0.)),$);\
+             #2=IFCINDEXEDPOLYCURVE(#1,(IFCLINEINDEX((1,2)),IFCARCINDEX((1,2,3))),$);\
+             #3=IFCTEST(((()),(#1,(#2))));",
+        );
+        assert_round_trip(&step_file);
+    }
+
+    #[test]
+    fn writes_reals_that_read_back_exactly() {
+        for f in [
+            0.0,
+            -0.0,
+            1.0,
+            0.1,
+            1.0 / 3.0,
+            1e-5,
+            2.5e20,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+        ] {
+            let written = format_step_real(f);
+            assert!(written.contains('.'), "{written}");
+            let step_file = parse(&format!("#1=IFCREAL({written});"));
+            assert_eq!(step_file.entities[&1].values, [StepValue::Real(f)]);
+        }
+    }
+
+    #[test]
+    fn writes_non_finite_reals_as_unset() {
+        let mut step_file = parse("");
+        step_file.insert(StepEntity {
+            id: 1,
+            entity_type: "IFCREAL".to_string(),
+            values: vec![
+                StepValue::Real(f64::NAN),
+                StepValue::Real(f64::INFINITY),
+                StepValue::Real(f64::NEG_INFINITY),
+            ],
+            schema: SchemaVersion::from_schemas(&step_file.header.schemas),
+        });
+        let written = step_file.to_step_string();
+        assert!(written.contains("#1=IFCREAL($,$,$);"), "{written}");
+        let reparsed = StepFile::parse(&written).unwrap();
+        assert_eq!(reparsed.entities[&1].values, vec![StepValue::Null; 3]);
+    }
+}
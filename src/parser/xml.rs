@@ -0,0 +1,682 @@
+//! ifcXML reader.
+//!
+//! Converts an ifcXML document (the IFC4 XSD binding, and the older
+//! ISO 10303-28 based IFC2x3 binding) into a [`StepFile`], so the IFC
+//! extraction in `ifc.rs` works the same for both formats.
+//!
+//! ifcXML names every attribute while STEP lists them by position, so
+//! attributes are put in the STEP order of the
+//! [schema definitions](crate::schema). Entities missing there keep the
+//! `IfcRoot` attributes at their STEP positions; attributes the schema does
+//! not list follow in document order.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+
+use roxmltree::{Document, Node};
+
+use crate::error::ParseError;
+use crate::parser::diagnostic::{Diagnostic, ParseOptions, Position};
+use crate::parser::encoding::{decode_bytes, FallbackEncoding};
+use crate::parser::step::{StepEntity, StepFile, StepHeader, StepValue};
+use crate::schema::SchemaVersion;
+
+/// Attributes that are aggregates even when they hold a single item.
+const LIST_ATTRIBUTES: &[&str] = &[
+    "RelatedObjects",
+    "RelatedElements",
+    "RelatedDefinitions",
+    "HasProperties",
+    "HasPropertySets",
+    "RepresentationMaps",
+    "RepresentationContexts",
+    "Quantities",
+];
+
+/// Inverse attributes, used by the IFC4 binding to nest relationships inside
+/// the objects they relate, with the attribute of the nested entity that
+/// points back at its parent. The binding leaves that reference out, since
+/// the nesting implies it.
+const INVERSE_ATTRIBUTES: &[(&str, &str)] = &[
+    ("IsDefinedBy", "RelatedObjects"),
+    ("IsTypedBy", "RelatedObjects"),
+    ("Types", "RelatingType"),
+    ("DefinesOccurrence", "RelatingPropertyDefinition"),
+    ("PropertyDefinitionOf", "RelatingPropertyDefinition"),
+    ("DefinesType", "HasPropertySets"),
+    ("IsDecomposedBy", "RelatingObject"),
+    ("Decomposes", "RelatedObjects"),
+    ("IsNestedBy", "RelatingObject"),
+    ("Nests", "RelatedObjects"),
+    ("ContainsElements", "RelatingStructure"),
+    ("ContainedInStructure", "RelatedElements"),
+    ("ReferencedInStructures", "RelatedElements"),
+    ("ReferencedBy", "RelatingProduct"),
+    ("HasAssociations", "RelatedObjects"),
+    ("HasAssignments", "RelatedObjects"),
+    ("HasContext", "RelatedDefinitions"),
+    ("Declares", "RelatingContext"),
+    ("HasOpenings", "RelatingBuildingElement"),
+    ("FillsVoids", "RelatedBuildingElement"),
+    ("VoidsElements", "RelatedOpeningElement"),
+    ("HasFillings", "RelatingOpeningElement"),
+    ("ConnectedTo", "RelatingElement"),
+    ("ConnectedFrom", "RelatedElement"),
+];
+
+/// Attributes whose value is always text, even if it looks like a number.
+const STRING_ATTRIBUTES: &[&str] = &[
+    "GlobalId",
+    "Name",
+    "Description",
+    "ObjectType",
+    "LongName",
+    "Phase",
+    "Tag",
+    "ApplicableOccurrence",
+    "ElementType",
+    "Identification",
+    "UserDefinedOperationType",
+    "UserDefinedPartitioningType",
+];
+
+/// Defined types whose value is text.
+const TEXT_TYPES: &[&str] = &[
+    "IFCLABEL",
+    "IFCTEXT",
+    "IFCIDENTIFIER",
+    "IFCGLOBALLYUNIQUEID",
+    "IFCDESCRIPTIVEMEASURE",
+    "IFCDATE",
+    "IFCDATETIME",
+    "IFCTIME",
+    "IFCDURATION",
+    "IFCURIREFERENCE",
+];
+
+/// Defined types whose value is an integer.
+const INTEGER_TYPES: &[&str] = &[
+    "IFCINTEGER",
+    "IFCPOSITIVEINTEGER",
+    "IFCCOUNTMEASURE",
+    "IFCTIMESTAMP",
+];
+
+impl StepFile {
+    /// Parse an ifcXML document.
+    ///
+    /// ```
+    /// use ifc_inspector::parser::StepFile;
+    ///
+    /// let xml = r#"<ifcXML xmlns="http://www.buildingsmart-tech.org/ifcXML/IFC4/final">
+    ///   <IfcBuildingStorey id="i5" GlobalId="0gTDr5XGL0dhHgxvMXprD3" Name="Level 1" Elevation="3000"/>
+    /// </ifcXML>"#;
+    /// let step_file = StepFile::from_xml(xml)?;
+    /// let storey = step_file.get_entity(5).unwrap();
+    /// assert_eq!(step_file.schema, "IFC4");
+    /// assert_eq!(storey.entity_type, "IFCBUILDINGSTOREY");
+    /// assert_eq!(storey.get_attr("Elevation"), Some(&ifc_inspector::parser::StepValue::Real(3000.0)));
+    /// # Ok::<(), ifc_inspector::error::ParseError>(())
+    /// ```
+    ///
+    /// Relationships nested under an inverse attribute get the reference to
+    /// their parent that the IFC4 binding leaves out:
+    ///
+    /// ```
+    /// use ifc_inspector::parser::{StepFile, StepValue};
+    ///
+    /// let xml = r#"<ifcXML xmlns="http://www.buildingsmart-tech.org/ifcXML/IFC4/final">
+    ///   <IfcBuildingStorey id="i5" GlobalId="0gTDr5XGL0dhHgxvMXprD3" Name="Level 1">
+    ///     <ContainsElements>
+    ///       <IfcRelContainedInSpatialStructure id="i9" GlobalId="1hUEs6YHM1eiIhywNYqsE4">
+    ///         <RelatedElements><IfcWall ref="i7"/></RelatedElements>
+    ///       </IfcRelContainedInSpatialStructure>
+    ///     </ContainsElements>
+    ///   </IfcBuildingStorey>
+    ///   <IfcWall id="i7" GlobalId="2iVFt7ZIN2fjJizxOZrtF5" Name="Wall"/>
+    /// </ifcXML>"#;
+    /// let step_file = StepFile::from_xml(xml)?;
+    /// let rel = step_file.get_entity(9).unwrap();
+    /// assert_eq!(rel.get_attr("RelatingStructure"), Some(&StepValue::Reference(5)));
+    /// # Ok::<(), ifc_inspector::error::ParseError>(())
+    /// ```
+    pub fn from_xml(content: &str) -> Result<Self, ParseError> {
+        Self::from_xml_with_options(content, ParseOptions::default())
+    }
+
+    pub fn from_xml_with_options(content: &str, options: ParseOptions) -> Result<Self, ParseError> {
+        let max_file_size = options.limits.max_file_size;
+        if content.len() as u64 > max_file_size {
+            return Err(ParseError::LimitExceeded {
+                message: format!("input is larger than {max_file_size} bytes"),
+            });
+        }
+        check_nesting(content, options.limits.max_nesting)?;
+        let document = Document::parse(content).map_err(|e| ParseError::InvalidXml {
+            message: e.to_string(),
+        })?;
+
+        let schema = xml_schema(document.root_element());
+        let version = SchemaVersion::from_name(&schema).unwrap_or_default();
+        let mut converter = XmlConverter::new(&document, options, version);
+        converter.reserve_ids(document.root_element());
+        converter.convert_children(document.root_element())?;
+
+        let mut step_file = converter.step_file;
+        step_file.schema = schema;
+        if !step_file.schema.is_empty() {
+            step_file.header.schemas = vec![step_file.schema.clone()];
+        }
+        Ok(step_file)
+    }
+
+    /// Read an ifcXML document from `reader`.
+    ///
+    /// ifcXML is normally UTF-8; other bytes are decoded with
+    /// [`ParseOptions::encoding`] and reported as a diagnostic.
+    pub fn from_xml_reader_with_options<R: Read>(
+        reader: R,
+        options: ParseOptions,
+    ) -> Result<Self, ParseError> {
+        let max_file_size = options.limits.max_file_size;
+        let mut bytes = Vec::new();
+        reader
+            .take(max_file_size.saturating_add(1))
+            .read_to_end(&mut bytes)
+            .map_err(|source| ParseError::Read { source })?;
+        if bytes.len() as u64 > max_file_size {
+            return Err(ParseError::LimitExceeded {
+                message: format!("input is larger than {max_file_size} bytes"),
+            });
+        }
+
+        match String::from_utf8(bytes) {
+            Ok(text) => Self::from_xml_with_options(&text, options),
+            Err(e) => {
+                let encoding = options.encoding;
+                let (text, _) = decode_bytes(e.as_bytes(), encoding);
+                let message = if encoding == FallbackEncoding::Lossy {
+                    "document is not valid UTF-8".to_string()
+                } else {
+                    format!("document is not valid UTF-8, decoded as {encoding}")
+                };
+                if options.strict {
+                    return Err(ParseError::InvalidXml { message });
+                }
+                let mut step_file = Self::from_xml_with_options(&text, options)?;
+                let diagnostic = Diagnostic::new(Position::default(), None, message);
+                step_file.diagnostics.insert(0, diagnostic);
+                Ok(step_file)
+            }
+        }
+    }
+}
+
+/// Schema name from the ifcXML namespace, e.g. `.../ifcXML/IFC4/Add2` → `IFC4`.
+fn xml_schema(root: Node) -> String {
+    let namespaces: Vec<String> = root
+        .namespaces()
+        .map(|ns| ns.uri().to_ascii_uppercase())
+        .collect();
+    ["IFC4X3", "IFC4", "IFC2X3"]
+        .iter()
+        .find(|schema| namespaces.iter().any(|ns| ns.contains(*schema)))
+        .map(|schema| (*schema).to_string())
+        .unwrap_or_default()
+}
+
+struct XmlConverter<'a, 'input> {
+    document: &'a Document<'input>,
+    options: ParseOptions,
+    schema: SchemaVersion,
+    step_file: StepFile,
+    ids: HashMap<&'a str, u64>,
+    next_id: u64,
+}
+
+impl<'a, 'input> XmlConverter<'a, 'input> {
+    fn new(document: &'a Document<'input>, options: ParseOptions, schema: SchemaVersion) -> Self {
+        Self {
+            document,
+            options,
+            schema,
+            step_file: StepFile::default(),
+            ids: HashMap::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Keep numeric XML ids (`i123` → `#123`) so entities can be found by the
+    /// same number as in the STEP export of the model.
+    fn reserve_ids(&mut self, root: Node<'a, 'input>) {
+        let mut used = HashSet::new();
+        let mut pending = Vec::new();
+        for node in root.descendants().filter(Node::is_element) {
+            let Some(xml_id) = node.attribute("id") else {
+                continue;
+            };
+            let number = xml_id
+                .trim_start_matches(|c: char| !c.is_ascii_digit())
+                .parse::<u64>()
+                .ok()
+                .filter(|&n| n > 0 && !used.contains(&n));
+            match number {
+                Some(n) if !self.ids.contains_key(xml_id) => {
+                    used.insert(n);
+                    self.ids.insert(xml_id, n);
+                }
+                _ => pending.push(xml_id),
+            }
+        }
+        self.next_id = used.iter().max().map_or(1, |max| max + 1);
+        for xml_id in pending {
+            self.id_for(xml_id);
+        }
+    }
+
+    fn id_for(&mut self, xml_id: &'a str) -> u64 {
+        if let Some(&id) = self.ids.get(xml_id) {
+            return id;
+        }
+        let id = self.fresh_id();
+        self.ids.insert(xml_id, id);
+        id
+    }
+
+    fn fresh_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn report(&mut self, node: Node, message: String) -> Result<(), ParseError> {
+        let text_pos = self.document.text_pos_at(node.range().start);
+        let position = Position {
+            offset: node.range().start as u64,
+            line: text_pos.row as usize,
+            column: text_pos.col as usize,
+        };
+        let entity_id = node
+            .ancestors()
+            .find_map(|n| n.attribute("id"))
+            .and_then(|id| self.ids.get(id).copied());
+        let diagnostic = Diagnostic::new(position, entity_id, message);
+        if self.options.strict {
+            return Err(ParseError::InvalidXml {
+                message: diagnostic.to_string(),
+            });
+        }
+        self.step_file.diagnostics.push(diagnostic);
+        Ok(())
+    }
+
+    fn limit_exceeded(&self, node: Node, message: String) -> ParseError {
+        let text_pos = self.document.text_pos_at(node.range().start);
+        let position = Position {
+            offset: node.range().start as u64,
+            line: text_pos.row as usize,
+            column: text_pos.col as usize,
+        };
+        ParseError::LimitExceeded {
+            message: Diagnostic::new(position, None, message).to_string(),
+        }
+    }
+
+    /// Convert the header and every entity below a container element
+    /// (`<ifcXML>`, or `<iso_10303_28>`/`<uos>` in the IFC2x3 binding).
+    fn convert_children(&mut self, parent: Node<'a, 'input>) -> Result<(), ParseError> {
+        for child in parent.children().filter(Node::is_element) {
+            let name = child.tag_name().name();
+            if name == "header" || name == "iso_10303_28_header" {
+                self.step_file.header = xml_header(child);
+            } else if is_entity_name(name) {
+                self.convert_entity(child, None)?;
+            } else {
+                self.convert_children(child)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Convert an entity element (and the entities nested in it), returning its ID.
+    ///
+    /// `back_reference` is set for a relationship nested under an inverse
+    /// attribute: the attribute pointing back at the parent, and its ID.
+    fn convert_entity(
+        &mut self,
+        node: Node<'a, 'input>,
+        back_reference: Option<(&'static str, u64)>,
+    ) -> Result<u64, ParseError> {
+        let max_entities = self.options.limits.max_entities;
+        if self.step_file.entities.len() >= max_entities {
+            return Err(self.limit_exceeded(node, format!("more than {max_entities} entities")));
+        }
+        let id = match node.attribute("id") {
+            Some(xml_id) => self.id_for(xml_id),
+            None => self.fresh_id(),
+        };
+        let entity_type = node.tag_name().name().to_ascii_uppercase();
+
+        let mut named: Vec<(&str, StepValue)> = Vec::new();
+        for attribute in node.attributes() {
+            if attribute.namespace().is_some() || matches!(attribute.name(), "id" | "pos") {
+                continue;
+            }
+            named.push((
+                attribute.name(),
+                text_value(attribute.name(), attribute.value()),
+            ));
+        }
+        for child in node.children().filter(Node::is_element) {
+            let name = child.tag_name().name();
+            if let Some(&(_, forward)) = INVERSE_ATTRIBUTES.iter().find(|(inv, _)| *inv == name) {
+                // Nested relationships are separate entities, not attribute values
+                self.convert_related(child, forward, id)?;
+                continue;
+            }
+            let value = self.attribute_value(name, child)?;
+            named.push((name, value));
+        }
+
+        if let Some((attr, parent)) = back_reference {
+            set_back_reference(&mut named, attr, parent);
+        }
+
+        let order = self
+            .schema
+            .entity(&entity_type)
+            .or_else(|| {
+                // Unknown entity: keep at least the IfcRoot attributes in place
+                let is_rooted = named.iter().any(|(name, _)| *name == "GlobalId");
+                is_rooted.then(|| self.schema.entity("IFCROOT")).flatten()
+            })
+            .map(|definition| definition.attributes.as_slice())
+            .unwrap_or_default();
+        let mut values: Vec<StepValue> = order
+            .iter()
+            .map(|attr| take_named(&mut named, attr).unwrap_or(StepValue::Null))
+            .collect();
+        values.extend(named.into_iter().map(|(_, value)| value));
+
+        self.step_file.insert(StepEntity {
+            id,
+            entity_type,
+            values,
+            schema: self.schema,
+        });
+        Ok(id)
+    }
+
+    /// Convert the relationships nested under an inverse attribute of entity
+    /// `parent`, pointing their `forward` attribute back at it. References
+    /// to relationships defined elsewhere are left alone; those carry the
+    /// parent themselves.
+    fn convert_related(
+        &mut self,
+        node: Node<'a, 'input>,
+        forward: &'static str,
+        parent: u64,
+    ) -> Result<(), ParseError> {
+        for item in node.children().filter(Node::is_element) {
+            if item.attribute("ref").is_none() && is_entity_name(item.tag_name().name()) {
+                self.convert_entity(item, Some((forward, parent)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Value of an attribute written as a child element (`<Name>…</Name>`,
+    /// `<RelatedObjects>…</RelatedObjects>`, `<NominalValue>…</NominalValue>`).
+    fn attribute_value(
+        &mut self,
+        name: &str,
+        node: Node<'a, 'input>,
+    ) -> Result<StepValue, ParseError> {
+        let items: Vec<Node> = node.children().filter(Node::is_element).collect();
+        if items.is_empty() {
+            let text = node.text().unwrap_or_default().trim();
+            if let Some(type_name) = xsi_type(node) {
+                return Ok(typed_value(&type_name, text));
+            }
+            if node.attribute("ref").is_some() {
+                return self.item_value(node);
+            }
+            return Ok(text_value(name, text));
+        }
+
+        let is_list = items.len() > 1
+            || LIST_ATTRIBUTES.contains(&name)
+            || node.attributes().any(|a| a.name() == "cType");
+        let mut values = Vec::with_capacity(items.len());
+        for item in items {
+            values.push(self.item_value(item)?);
+        }
+        if is_list {
+            Ok(StepValue::List(values))
+        } else {
+            Ok(values.pop().unwrap_or(StepValue::Null))
+        }
+    }
+
+    /// Value of one item inside an attribute element: a reference, a nested
+    /// entity, a defined type such as `<IfcLabel-wrapper>` or a plain value.
+    fn item_value(&mut self, node: Node<'a, 'input>) -> Result<StepValue, ParseError> {
+        if let Some(xml_id) = node.attribute("ref") {
+            if !self.ids.contains_key(xml_id) {
+                self.report(node, format!("reference to undefined id '{xml_id}'"))?;
+            }
+            return Ok(StepValue::Reference(self.id_for(xml_id)));
+        }
+
+        let name = node.tag_name().name();
+        let has_children = node.children().any(|c| c.is_element());
+        let has_attributes = node.attributes().any(|a| a.namespace().is_none());
+        if let Some(type_name) = name.strip_suffix("-wrapper") {
+            let text = node.text().unwrap_or_default().trim();
+            return Ok(typed_value(&type_name.to_ascii_uppercase(), text));
+        }
+        if is_entity_name(name) && (has_children || has_attributes) {
+            return Ok(StepValue::Reference(self.convert_entity(node, None)?));
+        }
+
+        let text = node.text().unwrap_or_default().trim();
+        if is_entity_name(name) {
+            // IFC2x3 binding: <IfcLabel>Foo</IfcLabel>
+            return Ok(typed_value(&name.to_ascii_uppercase(), text));
+        }
+        Ok(text_value(name, text))
+    }
+}
+
+/// Reject elements nested deeper than `max_nesting` before the document is
+/// parsed; both the XML parser and the conversion recurse per element.
+///
+/// Only tags are followed (comments, CDATA, processing instructions and
+/// quoted attribute values are skipped); well-formedness is left to the parser.
+fn check_nesting(content: &str, max_nesting: usize) -> Result<(), ParseError> {
+    let bytes = content.as_bytes();
+    let skip_past = |from: usize, end: &[u8]| {
+        bytes[from..]
+            .windows(end.len())
+            .position(|w| w == end)
+            .map_or(bytes.len(), |i| from + i + end.len())
+    };
+    let mut depth = 0usize;
+    let mut i = 0;
+    while let Some(found) = bytes[i..].iter().position(|&b| b == b'<') {
+        let start = i + found;
+        let rest = &bytes[start..];
+        if rest.starts_with(b"<!--") {
+            i = skip_past(start, b"-->");
+        } else if rest.starts_with(b"<![CDATA[") {
+            i = skip_past(start, b"]]>");
+        } else if rest.starts_with(b"<?") {
+            i = skip_past(start, b"?>");
+        } else if rest.starts_with(b"</") || rest.starts_with(b"<!") {
+            depth = depth.saturating_sub(usize::from(rest[1] == b'/'));
+            i = skip_past(start, b">");
+        } else {
+            depth += 1;
+            if depth > max_nesting {
+                let mut position = Position::default();
+                bytes[..start].iter().for_each(|&b| position.advance(b));
+                return Err(ParseError::LimitExceeded {
+                    message: Diagnostic::new(
+                        position,
+                        None,
+                        format!("elements nested deeper than {max_nesting}"),
+                    )
+                    .to_string(),
+                });
+            }
+            let mut quote = None;
+            i = bytes.len();
+            for (j, &b) in rest.iter().enumerate().skip(1) {
+                match (quote, b) {
+                    (Some(q), _) if b == q => quote = None,
+                    (None, b'"' | b'\'') => quote = Some(b),
+                    (None, b'>') => {
+                        if rest[j - 1] == b'/' {
+                            depth -= 1;
+                        }
+                        i = start + j + 1;
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_entity_name(name: &str) -> bool {
+    name.len() > 3
+        && name
+            .get(..3)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("ifc"))
+        && !name.ends_with("-wrapper")
+}
+
+/// Point attribute `attr` at `parent`: appended to a list attribute such as
+/// `RelatedObjects`, set on a single one such as `RelatingStructure`.
+fn set_back_reference<'n>(named: &mut Vec<(&'n str, StepValue)>, attr: &'n str, parent: u64) {
+    let reference = StepValue::Reference(parent);
+    let index = named.iter().position(|(name, _)| *name == attr);
+    if LIST_ATTRIBUTES.contains(&attr) {
+        match index.map(|i| &mut named[i].1) {
+            Some(StepValue::List(list)) => {
+                if !list.contains(&reference) {
+                    list.push(reference);
+                }
+            }
+            Some(value @ StepValue::Null) => *value = StepValue::List(vec![reference]),
+            Some(value) => {
+                if *value != reference {
+                    let single = std::mem::replace(value, StepValue::Null);
+                    *value = StepValue::List(vec![single, reference]);
+                }
+            }
+            None => named.push((attr, StepValue::List(vec![reference]))),
+        }
+    } else {
+        match index {
+            Some(i) if named[i].1 != StepValue::Null => {}
+            Some(i) => named[i].1 = reference,
+            None => named.push((attr, reference)),
+        }
+    }
+}
+
+fn take_named(named: &mut Vec<(&str, StepValue)>, attr: &str) -> Option<StepValue> {
+    let index = named.iter().position(|(name, _)| *name == attr)?;
+    Some(named.remove(index).1)
+}
+
+/// `xsi:type="IfcLabel-wrapper"` on an attribute element selects the defined type.
+fn xsi_type(node: Node) -> Option<String> {
+    node.attributes()
+        .find(|a| a.name() == "type" && a.namespace().is_some())
+        .map(|a| {
+            let name = a.value().rsplit(':').next().unwrap_or_default();
+            name.trim_end_matches("-wrapper").to_ascii_uppercase()
+        })
+}
+
+/// Value of a defined type such as `IFCLABEL` or `IFCLENGTHMEASURE`.
+fn typed_value(type_name: &str, text: &str) -> StepValue {
+    let value = if TEXT_TYPES.contains(&type_name) {
+        StepValue::String(text.to_string())
+    } else if type_name == "IFCBOOLEAN" || type_name == "IFCLOGICAL" {
+        match text {
+            "true" => StepValue::Boolean(true),
+            "false" => StepValue::Boolean(false),
+            _ => StepValue::Enum("U".to_string()),
+        }
+    } else if let (true, Ok(i)) = (INTEGER_TYPES.contains(&type_name), text.parse::<i64>()) {
+        StepValue::Integer(i)
+    } else {
+        text.parse::<f64>()
+            .map_or_else(|_| StepValue::String(text.to_string()), StepValue::Real)
+    };
+    StepValue::Typed {
+        type_name: type_name.to_string(),
+        value: Box::new(value),
+    }
+}
+
+/// Value of a simple attribute without a declared type.
+fn text_value(name: &str, text: &str) -> StepValue {
+    if STRING_ATTRIBUTES.contains(&name) {
+        return StepValue::String(text.to_string());
+    }
+    match text {
+        "true" => return StepValue::Boolean(true),
+        "false" => return StepValue::Boolean(false),
+        _ => {}
+    }
+    if let Ok(f) = text.parse::<f64>() {
+        return StepValue::Real(f);
+    }
+    // Lists of numbers are written space-separated: Coordinates="0 0 3000"
+    if text.contains(' ') {
+        let numbers: Option<Vec<StepValue>> = text
+            .split_whitespace()
+            .map(|part| part.parse::<f64>().ok().map(StepValue::Real))
+            .collect();
+        if let Some(numbers) = numbers {
+            return StepValue::List(numbers);
+        }
+    }
+    // Enumerations are written in lower case: PredefinedType="notdefined"
+    let is_identifier = !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if name.ends_with("Type") && is_identifier {
+        return StepValue::Enum(text.to_ascii_uppercase());
+    }
+    StepValue::String(text.to_string())
+}
+
+fn xml_header(node: Node) -> StepHeader {
+    let mut header = StepHeader::default();
+    for child in node.children().filter(Node::is_element) {
+        let text = child.text().unwrap_or_default().trim().to_string();
+        if text.is_empty() {
+            continue;
+        }
+        match child.tag_name().name() {
+            "name" => header.name = text,
+            "time_stamp" => header.time_stamp = text,
+            "author" => header.author.push(text),
+            "organization" => header.organization.push(text),
+            "preprocessor_version" => header.preprocessor_version = text,
+            "originating_system" => header.originating_system = text,
+            "authorization" => header.authorization = text,
+            "documentation" => header.description.push(text),
+            _ => {}
+        }
+    }
+    header
+}
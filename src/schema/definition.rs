@@ -0,0 +1,330 @@
+//! Attribute names and STEP positions per entity and schema version.
+//!
+//! STEP lists attributes by position, and positions shift between schema
+//! versions (IFC4 moved `RelatedObjects` from `IfcRelDefines` into each
+//! subtype, IFC4x3 put `IfcFacility` between `IfcSpatialStructureElement` and
+//! `IfcBuilding`, ...). [`Schema`] resolves inherited attributes once so an
+//! attribute can be found by name instead. The built-in versions come from
+//! the tables in `entities.rs`; other schemas can be loaded from EXPRESS
+//! with [`Schema::from_express`].
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::schema::entities::{EntityRow, ENTITIES, IFC2X3, IFC4, IFC4X3};
+use crate::schema::express::ExpressSchema;
+
+/// IFC schema version of a model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SchemaVersion {
+    Ifc2x3,
+    /// Also used for files whose schema is missing or not recognised
+    #[default]
+    Ifc4,
+    Ifc4x3,
+}
+
+impl SchemaVersion {
+    pub const ALL: [Self; 3] = [Self::Ifc2x3, Self::Ifc4, Self::Ifc4x3];
+
+    /// Version for a `FILE_SCHEMA` entry such as `IFC2X3`, `IFC4` or `IFC4X3_ADD2`.
+    ///
+    /// ```
+    /// use ifc_inspector::schema::SchemaVersion;
+    ///
+    /// assert_eq!(SchemaVersion::from_name("IFC4X3_ADD2"), Some(SchemaVersion::Ifc4x3));
+    /// assert_eq!(SchemaVersion::from_name("ifc2x3"), Some(SchemaVersion::Ifc2x3));
+    /// assert_eq!(SchemaVersion::from_name("CONFIG_CONTROL_DESIGN"), None);
+    /// ```
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_uppercase();
+        if name.starts_with("IFC4X3") {
+            Some(Self::Ifc4x3)
+        } else if name.starts_with("IFC4") {
+            // IFC4X1 and IFC4X2 only added infrastructure entities
+            Some(Self::Ifc4)
+        } else if name.starts_with("IFC2X") {
+            Some(Self::Ifc2x3)
+        } else {
+            None
+        }
+    }
+
+    /// Version of the first recognised entry of `FILE_SCHEMA`, or IFC4.
+    #[must_use]
+    pub fn from_schemas(schemas: &[String]) -> Self {
+        schemas
+            .iter()
+            .find_map(|name| Self::from_name(name))
+            .unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ifc2x3 => "IFC2X3",
+            Self::Ifc4 => "IFC4",
+            Self::Ifc4x3 => "IFC4X3",
+        }
+    }
+
+    /// The entity definitions of this version.
+    #[must_use]
+    pub fn schema(self) -> &'static Schema {
+        static SCHEMAS: [OnceLock<Schema>; 3] = [OnceLock::new(), OnceLock::new(), OnceLock::new()];
+        SCHEMAS[self as usize].get_or_init(|| Schema::from_rows(self, ENTITIES))
+    }
+
+    /// Definition of `entity_type` (any case).
+    ///
+    /// An entity this version does not define (as in a model written with the
+    /// wrong `FILE_SCHEMA`) is looked up in the other versions, newest first.
+    #[must_use]
+    pub fn entity(self, entity_type: &str) -> Option<&'static EntityDefinition> {
+        self.defining_schema(entity_type)?.entity(entity_type)
+    }
+
+    /// Whether `entity_type` is `supertype` or one of its subtypes, looked
+    /// up like [`SchemaVersion::entity`]. A type outside the schema
+    /// definitions only matches itself.
+    ///
+    /// ```
+    /// use ifc_inspector::schema::SchemaVersion;
+    ///
+    /// let v = SchemaVersion::Ifc4;
+    /// assert!(v.is_subtype_of("IFCWALLSTANDARDCASE", "IFCWALL"));
+    /// assert!(v.is_subtype_of("IFCWALL", "IFCBUILDINGELEMENT"));
+    /// assert!(!v.is_subtype_of("IFCWALL", "IFCWALLSTANDARDCASE"));
+    /// ```
+    #[must_use]
+    pub fn is_subtype_of(self, entity_type: &str, supertype: &str) -> bool {
+        match self.defining_schema(entity_type) {
+            Some(schema) => schema.is_subtype_of(entity_type, supertype),
+            None => entity_type.eq_ignore_ascii_case(supertype),
+        }
+    }
+
+    /// `entity_type` and all its subtypes in this version, sorted by name.
+    #[must_use]
+    pub fn subtypes_of(self, entity_type: &str) -> Vec<&'static str> {
+        self.schema().subtypes_of(entity_type)
+    }
+
+    /// This version's schema if it defines `entity_type`, otherwise the
+    /// newest other version that does.
+    fn defining_schema(self, entity_type: &str) -> Option<&'static Schema> {
+        std::iter::once(self)
+            .chain(Self::ALL.into_iter().rev().filter(|&other| other != self))
+            .map(Self::schema)
+            .find(|schema| schema.entity(entity_type).is_some())
+    }
+
+    /// Position of `attribute` in the STEP attribute list of `entity_type`,
+    /// looked up like [`SchemaVersion::entity`].
+    ///
+    /// ```
+    /// use ifc_inspector::schema::SchemaVersion;
+    ///
+    /// let v = SchemaVersion::Ifc4;
+    /// assert_eq!(v.attribute_index("IFCBUILDINGSTOREY", "Elevation"), Some(9));
+    /// assert_eq!(v.attribute_index("IFCRELDEFINESBYTYPE", "RelatingType"), Some(5));
+    /// assert_eq!(v.attribute_index("IFCWALL", "Elevation"), None);
+    /// ```
+    #[must_use]
+    pub fn attribute_index(self, entity_type: &str, attribute: &str) -> Option<usize> {
+        self.entity(entity_type)?.attribute_index(attribute)
+    }
+
+    fn bit(self) -> u8 {
+        match self {
+            Self::Ifc2x3 => IFC2X3,
+            Self::Ifc4 => IFC4,
+            Self::Ifc4x3 => IFC4X3,
+        }
+    }
+}
+
+impl fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// An entity with its full attribute list (inherited attributes first).
+#[derive(Debug, Clone)]
+pub struct EntityDefinition {
+    /// Upper-case name as written in STEP, e.g. `IFCBUILDINGSTOREY`
+    pub name: String,
+    pub supertype: Option<String>,
+    /// Explicit attributes in STEP order, including inherited ones
+    pub attributes: Vec<String>,
+}
+
+impl EntityDefinition {
+    /// Position of an attribute (matched case-insensitively) in STEP order.
+    #[must_use]
+    pub fn attribute_index(&self, attribute: &str) -> Option<usize> {
+        self.attributes
+            .iter()
+            .position(|a| a.eq_ignore_ascii_case(attribute))
+    }
+}
+
+/// The entity definitions of one schema version.
+#[derive(Debug)]
+pub struct Schema {
+    name: String,
+    version: SchemaVersion,
+    entities: HashMap<String, EntityDefinition>,
+}
+
+impl Schema {
+    fn from_rows(version: SchemaVersion, rows: &[EntityRow]) -> Self {
+        let own = rows
+            .iter()
+            .filter(|(versions, ..)| versions & version.bit() != 0)
+            .map(|&(_, name, supertype, attributes)| {
+                let attributes = attributes.iter().map(|a| (*a).to_string()).collect();
+                (
+                    name.to_string(),
+                    (supertype.map(str::to_string), attributes),
+                )
+            })
+            .collect();
+        Self::resolve(version.name().to_string(), version, &own)
+    }
+
+    /// Entity definitions of a schema read from EXPRESS. The version
+    /// (used when the schema name is not recognised, IFC4) only tells
+    /// which built-in schema it corresponds to.
+    ///
+    /// ```
+    /// use ifc_inspector::schema::{express::ExpressSchema, Schema, SchemaVersion};
+    ///
+    /// let express = ExpressSchema::parse(
+    ///     "SCHEMA IFC4X3_ADD2;
+    ///      ENTITY IfcRoot ABSTRACT; GlobalId : STRING; Name : OPTIONAL STRING; END_ENTITY;
+    ///      ENTITY IfcSensorProbe SUBTYPE OF (IfcRoot); Reading : REAL; END_ENTITY;
+    ///      END_SCHEMA;",
+    /// )?;
+    /// let schema = Schema::from_express(&express);
+    /// assert_eq!(schema.name(), "IFC4X3_ADD2");
+    /// assert_eq!(schema.version(), SchemaVersion::Ifc4x3);
+    /// assert_eq!(schema.attribute_index("IfcSensorProbe", "Reading"), Some(2));
+    /// # Ok::<(), ifc_inspector::error::ParseError>(())
+    /// ```
+    #[must_use]
+    pub fn from_express(express: &ExpressSchema) -> Self {
+        let own = express
+            .entities
+            .iter()
+            .map(|entity| {
+                let supertype = entity.supertypes.first().map(|s| s.to_ascii_uppercase());
+                let attributes = entity.attributes.iter().map(|a| a.name.clone()).collect();
+                (entity.name.to_ascii_uppercase(), (supertype, attributes))
+            })
+            .collect();
+        let version = SchemaVersion::from_name(&express.name).unwrap_or_default();
+        Self::resolve(express.name.clone(), version, &own)
+    }
+
+    /// Definitions with inherited attributes from each entity's own
+    /// supertype and attributes.
+    fn resolve(
+        name: String,
+        version: SchemaVersion,
+        own: &HashMap<String, (Option<String>, Vec<String>)>,
+    ) -> Self {
+        let entities = own
+            .iter()
+            .map(|(name, (supertype, _))| {
+                let mut chain = Vec::new();
+                let mut current = Some(name);
+                while let Some((parent, attributes)) = current.and_then(|n| own.get(n)) {
+                    // A cyclic SUBTYPE OF in a loaded schema must not loop forever
+                    if chain.len() > own.len() {
+                        break;
+                    }
+                    chain.push(attributes);
+                    current = parent.as_ref();
+                }
+                let attributes = chain.into_iter().rev().flatten().cloned().collect();
+                let definition = EntityDefinition {
+                    name: name.clone(),
+                    supertype: supertype.clone(),
+                    attributes,
+                };
+                (name.clone(), definition)
+            })
+            .collect();
+        Self {
+            name,
+            version,
+            entities,
+        }
+    }
+
+    /// Schema name, e.g. `IFC4` or the name of a loaded EXPRESS schema.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn version(&self) -> SchemaVersion {
+        self.version
+    }
+
+    /// Definition of an entity by name (any case).
+    #[must_use]
+    pub fn entity(&self, name: &str) -> Option<&EntityDefinition> {
+        self.entities
+            .get(name)
+            .or_else(|| self.entities.get(&name.to_ascii_uppercase()))
+    }
+
+    /// Position of `attribute` in the STEP attribute list of `entity_type`.
+    #[must_use]
+    pub fn attribute_index(&self, entity_type: &str, attribute: &str) -> Option<usize> {
+        self.entity(entity_type)?.attribute_index(attribute)
+    }
+
+    /// Whether `entity_type` is `supertype` or one of its subtypes. Names are
+    /// matched in any case.
+    #[must_use]
+    pub fn is_subtype_of(&self, entity_type: &str, supertype: &str) -> bool {
+        let mut current = self.entity(entity_type);
+        // Bounded so that a cyclic SUBTYPE OF in a loaded schema terminates
+        for _ in 0..=self.entities.len() {
+            let Some(entity) = current else { break };
+            if entity.name.eq_ignore_ascii_case(supertype) {
+                return true;
+            }
+            current = entity
+                .supertype
+                .as_deref()
+                .and_then(|name| self.entity(name));
+        }
+        false
+    }
+
+    /// `entity_type` and all its subtypes, sorted by name.
+    #[must_use]
+    pub fn subtypes_of(&self, entity_type: &str) -> Vec<&str> {
+        let mut subtypes: Vec<&str> = self
+            .entities
+            .keys()
+            .map(String::as_str)
+            .filter(|name| self.is_subtype_of(name, entity_type))
+            .collect();
+        subtypes.sort_unstable();
+        subtypes
+    }
+
+    /// Every entity of this version, in no particular order.
+    pub fn entities(&self) -> impl Iterator<Item = &EntityDefinition> {
+        self.entities.values()
+    }
+}
@@ -0,0 +1,598 @@
+//! Entity definitions of the IFC2x3, IFC4 and IFC4x3 schemas.
+//!
+//! Transcribed from the official EXPRESS schemas. Only the entities the
+//! inspector works with are listed: the `IfcRoot` branch down to every
+//! `IfcElement` subtype and its type object, spatial structure,
+//! relationships, property sets and quantities. Each row gives the versions
+//! it applies to, the entity name, its supertype and its own explicit
+//! attributes in STEP order.
+
+pub(crate) const IFC2X3: u8 = 1;
+pub(crate) const IFC4: u8 = 2;
+pub(crate) const IFC4X3: u8 = 4;
+const ALL: u8 = IFC2X3 | IFC4 | IFC4X3;
+
+/// Versions (bit set), entity name, supertype, own attributes.
+pub(crate) type EntityRow = (
+    u8,
+    &'static str,
+    Option<&'static str>,
+    &'static [&'static str],
+);
+
+#[rustfmt::skip]
+pub(crate) const ENTITIES: &[EntityRow] = &[
+    (ALL, "IFCROOT", None, &["GlobalId", "OwnerHistory", "Name", "Description"]),
+    (ALL, "IFCOBJECTDEFINITION", Some("IFCROOT"), &[]),
+    (ALL, "IFCOBJECT", Some("IFCOBJECTDEFINITION"), &["ObjectType"]),
+    (IFC4 | IFC4X3, "IFCCONTEXT", Some("IFCOBJECTDEFINITION"), &["ObjectType", "LongName", "Phase", "RepresentationContexts", "UnitsInContext"]),
+    (IFC2X3, "IFCPROJECT", Some("IFCOBJECT"), &["LongName", "Phase", "RepresentationContexts", "UnitsInContext"]),
+    (IFC4 | IFC4X3, "IFCPROJECT", Some("IFCCONTEXT"), &[]),
+    (IFC4 | IFC4X3, "IFCPROJECTLIBRARY", Some("IFCCONTEXT"), &[]),
+    (ALL, "IFCPRODUCT", Some("IFCOBJECT"), &["ObjectPlacement", "Representation"]),
+    (ALL, "IFCGROUP", Some("IFCOBJECT"), &[]),
+    (IFC2X3, "IFCZONE", Some("IFCGROUP"), &[]),
+    (IFC4 | IFC4X3, "IFCZONE", Some("IFCGROUP"), &["LongName"]),
+    // Spatial
+    (IFC4 | IFC4X3, "IFCSPATIALELEMENT", Some("IFCPRODUCT"), &["LongName"]),
+    (IFC2X3, "IFCSPATIALSTRUCTUREELEMENT", Some("IFCPRODUCT"), &["LongName", "CompositionType"]),
+    (IFC4 | IFC4X3, "IFCSPATIALSTRUCTUREELEMENT", Some("IFCSPATIALELEMENT"), &["CompositionType"]),
+    (ALL, "IFCSITE", Some("IFCSPATIALSTRUCTUREELEMENT"), &["RefLatitude", "RefLongitude", "RefElevation", "LandTitleNumber", "SiteAddress"]),
+    (IFC2X3 | IFC4, "IFCBUILDING", Some("IFCSPATIALSTRUCTUREELEMENT"), &["ElevationOfRefHeight", "ElevationOfTerrain", "BuildingAddress"]),
+    (IFC4X3, "IFCFACILITY", Some("IFCSPATIALSTRUCTUREELEMENT"), &[]),
+    (IFC4X3, "IFCBUILDING", Some("IFCFACILITY"), &["ElevationOfRefHeight", "ElevationOfTerrain", "BuildingAddress"]),
+    (IFC4X3, "IFCBRIDGE", Some("IFCFACILITY"), &["PredefinedType"]),
+    (IFC4X3, "IFCMARINEFACILITY", Some("IFCFACILITY"), &["PredefinedType"]),
+    (IFC4X3, "IFCRAILWAY", Some("IFCFACILITY"), &["PredefinedType"]),
+    (IFC4X3, "IFCROAD", Some("IFCFACILITY"), &["PredefinedType"]),
+    (IFC4X3, "IFCFACILITYPART", Some("IFCSPATIALSTRUCTUREELEMENT"), &["UsageType"]),
+    (IFC4X3, "IFCBRIDGEPART", Some("IFCFACILITYPART"), &["PredefinedType"]),
+    (IFC4X3, "IFCFACILITYPARTCOMMON", Some("IFCFACILITYPART"), &["PredefinedType"]),
+    (IFC4X3, "IFCMARINEPART", Some("IFCFACILITYPART"), &["PredefinedType"]),
+    (IFC4X3, "IFCRAILWAYPART", Some("IFCFACILITYPART"), &["PredefinedType"]),
+    (IFC4X3, "IFCROADPART", Some("IFCFACILITYPART"), &["PredefinedType"]),
+    (ALL, "IFCBUILDINGSTOREY", Some("IFCSPATIALSTRUCTUREELEMENT"), &["Elevation"]),
+    (IFC2X3, "IFCSPACE", Some("IFCSPATIALSTRUCTUREELEMENT"), &["InteriorOrExteriorSpace", "ElevationWithFlooring"]),
+    (IFC4 | IFC4X3, "IFCSPACE", Some("IFCSPATIALSTRUCTUREELEMENT"), &["PredefinedType", "ElevationWithFlooring"]),
+    (IFC4 | IFC4X3, "IFCEXTERNALSPATIALSTRUCTUREELEMENT", Some("IFCSPATIALELEMENT"), &[]),
+    (IFC4 | IFC4X3, "IFCEXTERNALSPATIALELEMENT", Some("IFCEXTERNALSPATIALSTRUCTUREELEMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSPATIALZONE", Some("IFCSPATIALELEMENT"), &["PredefinedType"]),
+    // Elements
+    (ALL, "IFCELEMENT", Some("IFCPRODUCT"), &["Tag"]),
+    (IFC2X3 | IFC4, "IFCBUILDINGELEMENT", Some("IFCELEMENT"), &[]),
+    // IFC2x3 building elements
+    (IFC2X3, "IFCBEAM", Some("IFCBUILDINGELEMENT"), &[]),
+    (IFC2X3, "IFCCOLUMN", Some("IFCBUILDINGELEMENT"), &[]),
+    (IFC2X3, "IFCCURTAINWALL", Some("IFCBUILDINGELEMENT"), &[]),
+    (IFC2X3, "IFCMEMBER", Some("IFCBUILDINGELEMENT"), &[]),
+    (IFC2X3, "IFCPLATE", Some("IFCBUILDINGELEMENT"), &[]),
+    (IFC2X3, "IFCWALL", Some("IFCBUILDINGELEMENT"), &[]),
+    (IFC2X3, "IFCRAMPFLIGHT", Some("IFCBUILDINGELEMENT"), &[]),
+    (IFC2X3, "IFCWALLSTANDARDCASE", Some("IFCWALL"), &[]),
+    (IFC2X3, "IFCBUILDINGELEMENTPROXY", Some("IFCBUILDINGELEMENT"), &["CompositionType"]),
+    (IFC2X3, "IFCCOVERING", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC2X3, "IFCFOOTING", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC2X3, "IFCRAILING", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC2X3, "IFCSLAB", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC2X3, "IFCPILE", Some("IFCBUILDINGELEMENT"), &["PredefinedType", "ConstructionType"]),
+    (IFC2X3, "IFCRAMP", Some("IFCBUILDINGELEMENT"), &["ShapeType"]),
+    (IFC2X3, "IFCROOF", Some("IFCBUILDINGELEMENT"), &["ShapeType"]),
+    (IFC2X3, "IFCSTAIR", Some("IFCBUILDINGELEMENT"), &["ShapeType"]),
+    (IFC2X3, "IFCSTAIRFLIGHT", Some("IFCBUILDINGELEMENT"), &["NumberOfRiser", "NumberOfTreads", "RiserHeight", "TreadLength"]),
+    (IFC2X3, "IFCDOOR", Some("IFCBUILDINGELEMENT"), &["OverallHeight", "OverallWidth"]),
+    (IFC2X3, "IFCWINDOW", Some("IFCBUILDINGELEMENT"), &["OverallHeight", "OverallWidth"]),
+    (IFC2X3, "IFCBUILDINGELEMENTCOMPONENT", Some("IFCBUILDINGELEMENT"), &[]),
+    (IFC2X3, "IFCBUILDINGELEMENTPART", Some("IFCBUILDINGELEMENTCOMPONENT"), &[]),
+    (IFC2X3, "IFCREINFORCINGELEMENT", Some("IFCBUILDINGELEMENTCOMPONENT"), &["SteelGrade"]),
+    (IFC2X3, "IFCREINFORCINGBAR", Some("IFCREINFORCINGELEMENT"), &["NominalDiameter", "CrossSectionArea", "BarLength", "BarRole", "BarSurface"]),
+    (IFC2X3, "IFCREINFORCINGMESH", Some("IFCREINFORCINGELEMENT"), &["MeshLength", "MeshWidth", "LongitudinalBarNominalDiameter", "TransverseBarNominalDiameter", "LongitudinalBarCrossSectionArea", "TransverseBarCrossSectionArea", "LongitudinalBarSpacing", "TransverseBarSpacing"]),
+    (IFC2X3, "IFCTENDON", Some("IFCREINFORCINGELEMENT"), &["PredefinedType", "NominalDiameter", "CrossSectionArea", "TensionForce", "PreStress", "FrictionCoefficient", "AnchorageSlip", "MinCurvatureRadius"]),
+    (IFC2X3, "IFCTENDONANCHOR", Some("IFCREINFORCINGELEMENT"), &[]),
+    // IFC4 building elements
+    (IFC4X3, "IFCBUILTELEMENT", Some("IFCELEMENT"), &[]),
+    (IFC4, "IFCBEAM", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCBEAM", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCCHIMNEY", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCCHIMNEY", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCCOLUMN", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCCOLUMN", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCCOVERING", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCCOVERING", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCCURTAINWALL", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCCURTAINWALL", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCFOOTING", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCFOOTING", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCMEMBER", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCMEMBER", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCPLATE", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCPLATE", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCRAILING", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCRAILING", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCRAMP", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCRAMP", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCRAMPFLIGHT", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCRAMPFLIGHT", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCROOF", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCROOF", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCSHADINGDEVICE", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCSHADINGDEVICE", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCSLAB", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCSLAB", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCSTAIR", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCSTAIR", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCWALL", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCWALL", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCBUILDINGELEMENTPROXY", Some("IFCBUILDINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCBUILDINGELEMENTPROXY", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4, "IFCPILE", Some("IFCBUILDINGELEMENT"), &["PredefinedType", "ConstructionType"]),
+    (IFC4, "IFCDOOR", Some("IFCBUILDINGELEMENT"), &["OverallHeight", "OverallWidth", "PredefinedType", "OperationType", "UserDefinedOperationType"]),
+    (IFC4X3, "IFCDOOR", Some("IFCBUILTELEMENT"), &["OverallHeight", "OverallWidth", "PredefinedType", "OperationType", "UserDefinedOperationType"]),
+    (IFC4, "IFCWINDOW", Some("IFCBUILDINGELEMENT"), &["OverallHeight", "OverallWidth", "PredefinedType", "PartitioningType", "UserDefinedPartitioningType"]),
+    (IFC4X3, "IFCWINDOW", Some("IFCBUILTELEMENT"), &["OverallHeight", "OverallWidth", "PredefinedType", "PartitioningType", "UserDefinedPartitioningType"]),
+    (IFC4, "IFCSTAIRFLIGHT", Some("IFCBUILDINGELEMENT"), &["NumberOfRisers", "NumberOfTreads", "RiserHeight", "TreadLength", "PredefinedType"]),
+    (IFC4X3, "IFCSTAIRFLIGHT", Some("IFCBUILTELEMENT"), &["NumberOfRisers", "NumberOfTreads", "RiserHeight", "TreadLength", "PredefinedType"]),
+    (IFC4, "IFCBEAMSTANDARDCASE", Some("IFCBEAM"), &[]),
+    (IFC4, "IFCCOLUMNSTANDARDCASE", Some("IFCCOLUMN"), &[]),
+    (IFC4, "IFCDOORSTANDARDCASE", Some("IFCDOOR"), &[]),
+    (IFC4, "IFCMEMBERSTANDARDCASE", Some("IFCMEMBER"), &[]),
+    (IFC4, "IFCPLATESTANDARDCASE", Some("IFCPLATE"), &[]),
+    (IFC4, "IFCSLABELEMENTEDCASE", Some("IFCSLAB"), &[]),
+    (IFC4, "IFCSLABSTANDARDCASE", Some("IFCSLAB"), &[]),
+    (IFC4, "IFCWALLELEMENTEDCASE", Some("IFCWALL"), &[]),
+    (IFC4, "IFCWALLSTANDARDCASE", Some("IFCWALL"), &[]),
+    (IFC4, "IFCWINDOWSTANDARDCASE", Some("IFCWINDOW"), &[]),
+    (IFC4X3, "IFCBEARING", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCCOURSE", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCKERB", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCMOORINGDEVICE", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCNAVIGATIONELEMENT", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCPAVEMENT", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCRAIL", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCTRACKELEMENT", Some("IFCBUILTELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCDEEPFOUNDATION", Some("IFCBUILTELEMENT"), &[]),
+    (IFC4X3, "IFCCAISSONFOUNDATION", Some("IFCDEEPFOUNDATION"), &["PredefinedType"]),
+    (IFC4X3, "IFCPILE", Some("IFCDEEPFOUNDATION"), &["PredefinedType", "ConstructionType"]),
+    (IFC4X3, "IFCEARTHWORKSELEMENT", Some("IFCBUILTELEMENT"), &[]),
+    (IFC4X3, "IFCEARTHWORKSFILL", Some("IFCEARTHWORKSELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCREINFORCEDSOIL", Some("IFCEARTHWORKSELEMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCIVILELEMENT", Some("IFCELEMENT"), &[]),
+    // Distribution
+    (ALL, "IFCDISTRIBUTIONELEMENT", Some("IFCELEMENT"), &[]),
+    (IFC2X3, "IFCDISTRIBUTIONCONTROLELEMENT", Some("IFCDISTRIBUTIONELEMENT"), &["ControlElementId"]),
+    (IFC4 | IFC4X3, "IFCDISTRIBUTIONCONTROLELEMENT", Some("IFCDISTRIBUTIONELEMENT"), &[]),
+    (IFC4 | IFC4X3, "IFCACTUATOR", Some("IFCDISTRIBUTIONCONTROLELEMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCALARM", Some("IFCDISTRIBUTIONCONTROLELEMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCONTROLLER", Some("IFCDISTRIBUTIONCONTROLELEMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCFLOWINSTRUMENT", Some("IFCDISTRIBUTIONCONTROLELEMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCPROTECTIVEDEVICETRIPPINGUNIT", Some("IFCDISTRIBUTIONCONTROLELEMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSENSOR", Some("IFCDISTRIBUTIONCONTROLELEMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCUNITARYCONTROLELEMENT", Some("IFCDISTRIBUTIONCONTROLELEMENT"), &["PredefinedType"]),
+    (ALL, "IFCDISTRIBUTIONFLOWELEMENT", Some("IFCDISTRIBUTIONELEMENT"), &[]),
+    (ALL, "IFCENERGYCONVERSIONDEVICE", Some("IFCDISTRIBUTIONFLOWELEMENT"), &[]),
+    (ALL, "IFCFLOWCONTROLLER", Some("IFCDISTRIBUTIONFLOWELEMENT"), &[]),
+    (ALL, "IFCFLOWFITTING", Some("IFCDISTRIBUTIONFLOWELEMENT"), &[]),
+    (ALL, "IFCFLOWMOVINGDEVICE", Some("IFCDISTRIBUTIONFLOWELEMENT"), &[]),
+    (ALL, "IFCFLOWSEGMENT", Some("IFCDISTRIBUTIONFLOWELEMENT"), &[]),
+    (ALL, "IFCFLOWSTORAGEDEVICE", Some("IFCDISTRIBUTIONFLOWELEMENT"), &[]),
+    (ALL, "IFCFLOWTERMINAL", Some("IFCDISTRIBUTIONFLOWELEMENT"), &[]),
+    (ALL, "IFCFLOWTREATMENTDEVICE", Some("IFCDISTRIBUTIONFLOWELEMENT"), &[]),
+    (IFC2X3, "IFCDISTRIBUTIONCHAMBERELEMENT", Some("IFCDISTRIBUTIONFLOWELEMENT"), &[]),
+    (IFC4 | IFC4X3, "IFCDISTRIBUTIONCHAMBERELEMENT", Some("IFCDISTRIBUTIONFLOWELEMENT"), &["PredefinedType"]),
+    (IFC2X3, "IFCELECTRICDISTRIBUTIONPOINT", Some("IFCFLOWCONTROLLER"), &["DistributionPointFunction", "UserDefinedFunction"]),
+    (IFC4 | IFC4X3, "IFCAIRTOAIRHEATRECOVERY", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCBOILER", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCBURNER", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCHILLER", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCOIL", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCONDENSER", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCOOLEDBEAM", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCOOLINGTOWER", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCELECTRICGENERATOR", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCELECTRICMOTOR", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCENGINE", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCEVAPORATIVECOOLER", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCEVAPORATOR", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCHEATEXCHANGER", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCHUMIDIFIER", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCMOTORCONNECTION", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSOLARDEVICE", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCTRANSFORMER", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCTUBEBUNDLE", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCUNITARYEQUIPMENT", Some("IFCENERGYCONVERSIONDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCAIRTERMINALBOX", Some("IFCFLOWCONTROLLER"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCDAMPER", Some("IFCFLOWCONTROLLER"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCELECTRICTIMECONTROL", Some("IFCFLOWCONTROLLER"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCFLOWMETER", Some("IFCFLOWCONTROLLER"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCPROTECTIVEDEVICE", Some("IFCFLOWCONTROLLER"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSWITCHINGDEVICE", Some("IFCFLOWCONTROLLER"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCVALVE", Some("IFCFLOWCONTROLLER"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCABLECARRIERFITTING", Some("IFCFLOWFITTING"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCABLEFITTING", Some("IFCFLOWFITTING"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCDUCTFITTING", Some("IFCFLOWFITTING"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCJUNCTIONBOX", Some("IFCFLOWFITTING"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCPIPEFITTING", Some("IFCFLOWFITTING"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCOMPRESSOR", Some("IFCFLOWMOVINGDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCFAN", Some("IFCFLOWMOVINGDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCPUMP", Some("IFCFLOWMOVINGDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCABLECARRIERSEGMENT", Some("IFCFLOWSEGMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCABLESEGMENT", Some("IFCFLOWSEGMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCDUCTSEGMENT", Some("IFCFLOWSEGMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCPIPESEGMENT", Some("IFCFLOWSEGMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCELECTRICFLOWSTORAGEDEVICE", Some("IFCFLOWSTORAGEDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCTANK", Some("IFCFLOWSTORAGEDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCAIRTERMINAL", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCAUDIOVISUALAPPLIANCE", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCOMMUNICATIONSAPPLIANCE", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCELECTRICAPPLIANCE", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCFIRESUPPRESSIONTERMINAL", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCLAMP", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCLIGHTFIXTURE", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCMEDICALDEVICE", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCOUTLET", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSANITARYTERMINAL", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSPACEHEATER", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSTACKTERMINAL", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCWASTETERMINAL", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCDUCTSILENCER", Some("IFCFLOWTREATMENTDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCFILTER", Some("IFCFLOWTREATMENTDEVICE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCINTERCEPTOR", Some("IFCFLOWTREATMENTDEVICE"), &["PredefinedType"]),
+    (IFC4, "IFCELECTRICDISTRIBUTIONBOARD", Some("IFCFLOWCONTROLLER"), &["PredefinedType"]),
+    (IFC4X3, "IFCDISTRIBUTIONBOARD", Some("IFCFLOWCONTROLLER"), &["PredefinedType"]),
+    (IFC4X3, "IFCCONVEYORSEGMENT", Some("IFCFLOWSEGMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCLIQUIDTERMINAL", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4X3, "IFCMOBILETELECOMMUNICATIONSAPPLIANCE", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4X3, "IFCSIGNAL", Some("IFCFLOWTERMINAL"), &["PredefinedType"]),
+    (IFC4X3, "IFCELECTRICFLOWTREATMENTDEVICE", Some("IFCFLOWTREATMENTDEVICE"), &["PredefinedType"]),
+    // Other elements
+    (ALL, "IFCELEMENTASSEMBLY", Some("IFCELEMENT"), &["AssemblyPlace", "PredefinedType"]),
+    (ALL, "IFCELEMENTCOMPONENT", Some("IFCELEMENT"), &[]),
+    (IFC2X3, "IFCDISCRETEACCESSORY", Some("IFCELEMENTCOMPONENT"), &[]),
+    (IFC2X3, "IFCFASTENER", Some("IFCELEMENTCOMPONENT"), &[]),
+    (IFC2X3, "IFCMECHANICALFASTENER", Some("IFCFASTENER"), &["NominalDiameter", "NominalLength"]),
+    (IFC4 | IFC4X3, "IFCBUILDINGELEMENTPART", Some("IFCELEMENTCOMPONENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCDISCRETEACCESSORY", Some("IFCELEMENTCOMPONENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCFASTENER", Some("IFCELEMENTCOMPONENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCVIBRATIONISOLATOR", Some("IFCELEMENTCOMPONENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCIMPACTPROTECTIONDEVICE", Some("IFCELEMENTCOMPONENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCSIGN", Some("IFCELEMENTCOMPONENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCVIBRATIONDAMPER", Some("IFCELEMENTCOMPONENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCMECHANICALFASTENER", Some("IFCELEMENTCOMPONENT"), &["NominalDiameter", "NominalLength", "PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCREINFORCINGELEMENT", Some("IFCELEMENTCOMPONENT"), &["SteelGrade"]),
+    (IFC4 | IFC4X3, "IFCREINFORCINGBAR", Some("IFCREINFORCINGELEMENT"), &["NominalDiameter", "CrossSectionArea", "BarLength", "PredefinedType", "BarSurface"]),
+    (IFC4 | IFC4X3, "IFCREINFORCINGMESH", Some("IFCREINFORCINGELEMENT"), &["MeshLength", "MeshWidth", "LongitudinalBarNominalDiameter", "TransverseBarNominalDiameter", "LongitudinalBarCrossSectionArea", "TransverseBarCrossSectionArea", "LongitudinalBarSpacing", "TransverseBarSpacing", "PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCTENDON", Some("IFCREINFORCINGELEMENT"), &["PredefinedType", "NominalDiameter", "CrossSectionArea", "TensionForce", "PreStress", "FrictionCoefficient", "AnchorageSlip", "MinCurvatureRadius"]),
+    (IFC4 | IFC4X3, "IFCTENDONANCHOR", Some("IFCREINFORCINGELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCTENDONCONDUIT", Some("IFCREINFORCINGELEMENT"), &["PredefinedType"]),
+    (ALL, "IFCFEATUREELEMENT", Some("IFCELEMENT"), &[]),
+    (ALL, "IFCFEATUREELEMENTADDITION", Some("IFCFEATUREELEMENT"), &[]),
+    (ALL, "IFCFEATUREELEMENTSUBTRACTION", Some("IFCFEATUREELEMENT"), &[]),
+    (IFC2X3, "IFCPROJECTIONELEMENT", Some("IFCFEATUREELEMENTADDITION"), &[]),
+    (IFC4 | IFC4X3, "IFCPROJECTIONELEMENT", Some("IFCFEATUREELEMENTADDITION"), &["PredefinedType"]),
+    (IFC2X3, "IFCOPENINGELEMENT", Some("IFCFEATUREELEMENTSUBTRACTION"), &[]),
+    (IFC4 | IFC4X3, "IFCOPENINGELEMENT", Some("IFCFEATUREELEMENTSUBTRACTION"), &["PredefinedType"]),
+    (IFC4, "IFCOPENINGSTANDARDCASE", Some("IFCOPENINGELEMENT"), &[]),
+    (IFC4 | IFC4X3, "IFCVOIDINGFEATURE", Some("IFCFEATUREELEMENTSUBTRACTION"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSURFACEFEATURE", Some("IFCFEATUREELEMENT"), &["PredefinedType"]),
+    (IFC2X3, "IFCEDGEFEATURE", Some("IFCFEATUREELEMENTSUBTRACTION"), &["FeatureLength"]),
+    (IFC2X3, "IFCCHAMFEREDGEFEATURE", Some("IFCEDGEFEATURE"), &["Width", "Height"]),
+    (IFC2X3, "IFCROUNDEDEDGEFEATURE", Some("IFCEDGEFEATURE"), &["Radius"]),
+    (ALL, "IFCFURNISHINGELEMENT", Some("IFCELEMENT"), &[]),
+    (IFC4 | IFC4X3, "IFCFURNITURE", Some("IFCFURNISHINGELEMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSYSTEMFURNITUREELEMENT", Some("IFCFURNISHINGELEMENT"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCGEOGRAPHICELEMENT", Some("IFCELEMENT"), &["PredefinedType"]),
+    (IFC2X3, "IFCEQUIPMENTELEMENT", Some("IFCELEMENT"), &[]),
+    (IFC2X3, "IFCELECTRICALELEMENT", Some("IFCELEMENT"), &[]),
+    (IFC2X3, "IFCTRANSPORTELEMENT", Some("IFCELEMENT"), &["OperationType", "CapacityByWeight", "CapacityByNumber"]),
+    (IFC4, "IFCTRANSPORTELEMENT", Some("IFCELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCTRANSPORTATIONDEVICE", Some("IFCELEMENT"), &[]),
+    (IFC4X3, "IFCTRANSPORTELEMENT", Some("IFCTRANSPORTATIONDEVICE"), &["PredefinedType"]),
+    (IFC4X3, "IFCVEHICLE", Some("IFCTRANSPORTATIONDEVICE"), &["PredefinedType"]),
+    (IFC2X3 | IFC4, "IFCVIRTUALELEMENT", Some("IFCELEMENT"), &[]),
+    (IFC4X3, "IFCVIRTUALELEMENT", Some("IFCELEMENT"), &["PredefinedType"]),
+    (IFC4X3, "IFCGEOTECHNICALELEMENT", Some("IFCELEMENT"), &[]),
+    (IFC4X3, "IFCGEOTECHNICALASSEMBLY", Some("IFCGEOTECHNICALELEMENT"), &[]),
+    (IFC4X3, "IFCBOREHOLE", Some("IFCGEOTECHNICALASSEMBLY"), &[]),
+    (IFC4X3, "IFCGEOMODEL", Some("IFCGEOTECHNICALASSEMBLY"), &[]),
+    (IFC4X3, "IFCGEOSLICE", Some("IFCGEOTECHNICALASSEMBLY"), &[]),
+    (IFC4X3, "IFCGEOTECHNICALSTRATUM", Some("IFCGEOTECHNICALELEMENT"), &["PredefinedType"]),
+    // Type objects
+    (ALL, "IFCTYPEOBJECT", Some("IFCOBJECTDEFINITION"), &["ApplicableOccurrence", "HasPropertySets"]),
+    (ALL, "IFCTYPEPRODUCT", Some("IFCTYPEOBJECT"), &["RepresentationMaps", "Tag"]),
+    (ALL, "IFCELEMENTTYPE", Some("IFCTYPEPRODUCT"), &["ElementType"]),
+    (IFC2X3 | IFC4, "IFCBUILDINGELEMENTTYPE", Some("IFCELEMENTTYPE"), &[]),
+    (IFC4X3, "IFCBUILTELEMENTTYPE", Some("IFCELEMENTTYPE"), &[]),
+    (IFC4X3, "IFCBEARINGTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCCOURSETYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCKERBTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCMOORINGDEVICETYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCNAVIGATIONELEMENTTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCPAVEMENTTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCRAILTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCTRACKELEMENTTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCDEEPFOUNDATIONTYPE", Some("IFCBUILTELEMENTTYPE"), &[]),
+    (IFC4X3, "IFCCAISSONFOUNDATIONTYPE", Some("IFCDEEPFOUNDATIONTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCBEAMTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCCOLUMNTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCCOVERINGTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCCURTAINWALLTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCMEMBERTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCPLATETYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCRAILINGTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCRAMPFLIGHTTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCSLABTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCSTAIRFLIGHTTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCWALLTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCBUILDINGELEMENTPROXYTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCBEAMTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCBEAMTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCCHIMNEYTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCCHIMNEYTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCCOLUMNTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCCOLUMNTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCCOVERINGTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCCOVERINGTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCCURTAINWALLTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCCURTAINWALLTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCFOOTINGTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCFOOTINGTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCMEMBERTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCMEMBERTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCPILETYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCPILETYPE", Some("IFCDEEPFOUNDATIONTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCPLATETYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCPLATETYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCRAILINGTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCRAILINGTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCRAMPTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCRAMPTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCRAMPFLIGHTTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCRAMPFLIGHTTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCROOFTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCROOFTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCSHADINGDEVICETYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCSHADINGDEVICETYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCSLABTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCSLABTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCSTAIRTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCSTAIRTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCSTAIRFLIGHTTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCSTAIRFLIGHTTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCWALLTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCWALLTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCBUILDINGELEMENTPROXYTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCBUILDINGELEMENTPROXYTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCDOORTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType", "OperationType", "ParameterTakesPrecedence", "UserDefinedOperationType"]),
+    (IFC4X3, "IFCDOORTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType", "OperationType", "ParameterTakesPrecedence", "UserDefinedOperationType"]),
+    (IFC4, "IFCWINDOWTYPE", Some("IFCBUILDINGELEMENTTYPE"), &["PredefinedType", "PartitioningType", "ParameterTakesPrecedence", "UserDefinedPartitioningType"]),
+    (IFC4X3, "IFCWINDOWTYPE", Some("IFCBUILTELEMENTTYPE"), &["PredefinedType", "PartitioningType", "ParameterTakesPrecedence", "UserDefinedPartitioningType"]),
+    (IFC2X3 | IFC4, "IFCDOORSTYLE", Some("IFCTYPEPRODUCT"), &["OperationType", "ConstructionType", "ParameterTakesPrecedence", "Sizeable"]),
+    (IFC2X3 | IFC4, "IFCWINDOWSTYLE", Some("IFCTYPEPRODUCT"), &["ConstructionType", "OperationType", "ParameterTakesPrecedence", "Sizeable"]),
+    (ALL, "IFCFURNISHINGELEMENTTYPE", Some("IFCELEMENTTYPE"), &[]),
+    (IFC2X3, "IFCFURNITURETYPE", Some("IFCFURNISHINGELEMENTTYPE"), &["AssemblyPlace"]),
+    (IFC4 | IFC4X3, "IFCFURNITURETYPE", Some("IFCFURNISHINGELEMENTTYPE"), &["AssemblyPlace", "PredefinedType"]),
+    (IFC2X3, "IFCSYSTEMFURNITUREELEMENTTYPE", Some("IFCFURNISHINGELEMENTTYPE"), &[]),
+    (IFC4 | IFC4X3, "IFCSYSTEMFURNITUREELEMENTTYPE", Some("IFCFURNISHINGELEMENTTYPE"), &["PredefinedType"]),
+    (ALL, "IFCDISTRIBUTIONELEMENTTYPE", Some("IFCELEMENTTYPE"), &[]),
+    (ALL, "IFCDISTRIBUTIONCONTROLELEMENTTYPE", Some("IFCDISTRIBUTIONELEMENTTYPE"), &[]),
+    (ALL, "IFCDISTRIBUTIONFLOWELEMENTTYPE", Some("IFCDISTRIBUTIONELEMENTTYPE"), &[]),
+    (ALL, "IFCDISTRIBUTIONCHAMBERELEMENTTYPE", Some("IFCDISTRIBUTIONFLOWELEMENTTYPE"), &["PredefinedType"]),
+    (ALL, "IFCENERGYCONVERSIONDEVICETYPE", Some("IFCDISTRIBUTIONFLOWELEMENTTYPE"), &[]),
+    (ALL, "IFCFLOWCONTROLLERTYPE", Some("IFCDISTRIBUTIONFLOWELEMENTTYPE"), &[]),
+    (ALL, "IFCFLOWFITTINGTYPE", Some("IFCDISTRIBUTIONFLOWELEMENTTYPE"), &[]),
+    (ALL, "IFCFLOWMOVINGDEVICETYPE", Some("IFCDISTRIBUTIONFLOWELEMENTTYPE"), &[]),
+    (ALL, "IFCFLOWSEGMENTTYPE", Some("IFCDISTRIBUTIONFLOWELEMENTTYPE"), &[]),
+    (ALL, "IFCFLOWSTORAGEDEVICETYPE", Some("IFCDISTRIBUTIONFLOWELEMENTTYPE"), &[]),
+    (ALL, "IFCFLOWTERMINALTYPE", Some("IFCDISTRIBUTIONFLOWELEMENTTYPE"), &[]),
+    (ALL, "IFCFLOWTREATMENTDEVICETYPE", Some("IFCDISTRIBUTIONFLOWELEMENTTYPE"), &[]),
+    (ALL, "IFCACTUATORTYPE", Some("IFCDISTRIBUTIONCONTROLELEMENTTYPE"), &["PredefinedType"]),
+    (ALL, "IFCALARMTYPE", Some("IFCDISTRIBUTIONCONTROLELEMENTTYPE"), &["PredefinedType"]),
+    (ALL, "IFCCONTROLLERTYPE", Some("IFCDISTRIBUTIONCONTROLELEMENTTYPE"), &["PredefinedType"]),
+    (ALL, "IFCFLOWINSTRUMENTTYPE", Some("IFCDISTRIBUTIONCONTROLELEMENTTYPE"), &["PredefinedType"]),
+    (ALL, "IFCSENSORTYPE", Some("IFCDISTRIBUTIONCONTROLELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCPROTECTIVEDEVICETRIPPINGUNITTYPE", Some("IFCDISTRIBUTIONCONTROLELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCUNITARYCONTROLELEMENTTYPE", Some("IFCDISTRIBUTIONCONTROLELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCAIRTOAIRHEATRECOVERYTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCBOILERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCCHILLERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCCOILTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCCONDENSERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCCOOLEDBEAMTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCCOOLINGTOWERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCELECTRICGENERATORTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCELECTRICMOTORTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCEVAPORATIVECOOLERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCEVAPORATORTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCHEATEXCHANGERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCHUMIDIFIERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCMOTORCONNECTIONTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCSPACEHEATERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCTRANSFORMERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCTUBEBUNDLETYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCUNITARYEQUIPMENTTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCAIRTERMINALBOXTYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCDAMPERTYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCELECTRICTIMECONTROLTYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCFLOWMETERTYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCPROTECTIVEDEVICETYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCSWITCHINGDEVICETYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCVALVETYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCCABLECARRIERFITTINGTYPE", Some("IFCFLOWFITTINGTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCDUCTFITTINGTYPE", Some("IFCFLOWFITTINGTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCJUNCTIONBOXTYPE", Some("IFCFLOWFITTINGTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCPIPEFITTINGTYPE", Some("IFCFLOWFITTINGTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCCOMPRESSORTYPE", Some("IFCFLOWMOVINGDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCFANTYPE", Some("IFCFLOWMOVINGDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCPUMPTYPE", Some("IFCFLOWMOVINGDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCCABLECARRIERSEGMENTTYPE", Some("IFCFLOWSEGMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCCABLESEGMENTTYPE", Some("IFCFLOWSEGMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCDUCTSEGMENTTYPE", Some("IFCFLOWSEGMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCPIPESEGMENTTYPE", Some("IFCFLOWSEGMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCELECTRICFLOWSTORAGEDEVICETYPE", Some("IFCFLOWSTORAGEDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCTANKTYPE", Some("IFCFLOWSTORAGEDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCAIRTERMINALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCELECTRICAPPLIANCETYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCELECTRICHEATERTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCFIRESUPPRESSIONTERMINALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCGASTERMINALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCLAMPTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCLIGHTFIXTURETYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCOUTLETTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCSANITARYTERMINALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCSTACKTERMINALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCWASTETERMINALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCDUCTSILENCERTYPE", Some("IFCFLOWTREATMENTDEVICETYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCFILTERTYPE", Some("IFCFLOWTREATMENTDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCAIRTOAIRHEATRECOVERYTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCBOILERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCBURNERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCHILLERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCOILTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCONDENSERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCOOLEDBEAMTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCOOLINGTOWERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCELECTRICGENERATORTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCELECTRICMOTORTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCENGINETYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCEVAPORATIVECOOLERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCEVAPORATORTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCHEATEXCHANGERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCHUMIDIFIERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCMOTORCONNECTIONTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSOLARDEVICETYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCTRANSFORMERTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCTUBEBUNDLETYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCUNITARYEQUIPMENTTYPE", Some("IFCENERGYCONVERSIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCAIRTERMINALBOXTYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCDAMPERTYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCELECTRICTIMECONTROLTYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCFLOWMETERTYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCPROTECTIVEDEVICETYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSWITCHINGDEVICETYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCVALVETYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCABLECARRIERFITTINGTYPE", Some("IFCFLOWFITTINGTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCABLEFITTINGTYPE", Some("IFCFLOWFITTINGTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCDUCTFITTINGTYPE", Some("IFCFLOWFITTINGTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCJUNCTIONBOXTYPE", Some("IFCFLOWFITTINGTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCPIPEFITTINGTYPE", Some("IFCFLOWFITTINGTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCOMPRESSORTYPE", Some("IFCFLOWMOVINGDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCFANTYPE", Some("IFCFLOWMOVINGDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCPUMPTYPE", Some("IFCFLOWMOVINGDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCABLECARRIERSEGMENTTYPE", Some("IFCFLOWSEGMENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCABLESEGMENTTYPE", Some("IFCFLOWSEGMENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCDUCTSEGMENTTYPE", Some("IFCFLOWSEGMENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCPIPESEGMENTTYPE", Some("IFCFLOWSEGMENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCELECTRICFLOWSTORAGEDEVICETYPE", Some("IFCFLOWSTORAGEDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCTANKTYPE", Some("IFCFLOWSTORAGEDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCAIRTERMINALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCAUDIOVISUALAPPLIANCETYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCOMMUNICATIONSAPPLIANCETYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCELECTRICAPPLIANCETYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCFIRESUPPRESSIONTERMINALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCLAMPTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCLIGHTFIXTURETYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCMEDICALDEVICETYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCOUTLETTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSANITARYTERMINALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSPACEHEATERTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSTACKTERMINALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCWASTETERMINALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCDUCTSILENCERTYPE", Some("IFCFLOWTREATMENTDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCFILTERTYPE", Some("IFCFLOWTREATMENTDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCINTERCEPTORTYPE", Some("IFCFLOWTREATMENTDEVICETYPE"), &["PredefinedType"]),
+    (IFC4, "IFCELECTRICDISTRIBUTIONBOARDTYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCDISTRIBUTIONBOARDTYPE", Some("IFCFLOWCONTROLLERTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCCONVEYORSEGMENTTYPE", Some("IFCFLOWSEGMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCLIQUIDTERMINALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCMOBILETELECOMMUNICATIONSAPPLIANCETYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCSIGNALTYPE", Some("IFCFLOWTERMINALTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCELECTRICFLOWTREATMENTDEVICETYPE", Some("IFCFLOWTREATMENTDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCELEMENTASSEMBLYTYPE", Some("IFCELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCELEMENTCOMPONENTTYPE", Some("IFCELEMENTTYPE"), &[]),
+    (IFC4 | IFC4X3, "IFCBUILDINGELEMENTPARTTYPE", Some("IFCELEMENTCOMPONENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCDISCRETEACCESSORYTYPE", Some("IFCELEMENTCOMPONENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCFASTENERTYPE", Some("IFCELEMENTCOMPONENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCVIBRATIONISOLATORTYPE", Some("IFCELEMENTCOMPONENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCIMPACTPROTECTIONDEVICETYPE", Some("IFCELEMENTCOMPONENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCSIGNTYPE", Some("IFCELEMENTCOMPONENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCVIBRATIONDAMPERTYPE", Some("IFCELEMENTCOMPONENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCMECHANICALFASTENERTYPE", Some("IFCELEMENTCOMPONENTTYPE"), &["PredefinedType", "NominalDiameter", "NominalLength"]),
+    (IFC4 | IFC4X3, "IFCREINFORCINGELEMENTTYPE", Some("IFCELEMENTCOMPONENTTYPE"), &[]),
+    (IFC4 | IFC4X3, "IFCREINFORCINGBARTYPE", Some("IFCREINFORCINGELEMENTTYPE"), &["PredefinedType", "NominalDiameter", "CrossSectionArea", "BarLength", "BarSurface", "BendingShapeCode", "BendingParameters"]),
+    (IFC4 | IFC4X3, "IFCREINFORCINGMESHTYPE", Some("IFCREINFORCINGELEMENTTYPE"), &["PredefinedType", "MeshLength", "MeshWidth", "LongitudinalBarNominalDiameter", "TransverseBarNominalDiameter", "LongitudinalBarCrossSectionArea", "TransverseBarCrossSectionArea", "LongitudinalBarSpacing", "TransverseBarSpacing", "BendingShapeCode", "BendingParameters"]),
+    (IFC4 | IFC4X3, "IFCTENDONTYPE", Some("IFCREINFORCINGELEMENTTYPE"), &["PredefinedType", "NominalDiameter", "CrossSectionArea", "SheathDiameter"]),
+    (IFC4 | IFC4X3, "IFCTENDONANCHORTYPE", Some("IFCREINFORCINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCTENDONCONDUITTYPE", Some("IFCREINFORCINGELEMENTTYPE"), &["PredefinedType"]),
+    (IFC2X3, "IFCDISCRETEACCESSORYTYPE", Some("IFCELEMENTCOMPONENTTYPE"), &[]),
+    (IFC2X3, "IFCELEMENTCOMPONENTTYPE", Some("IFCELEMENTTYPE"), &[]),
+    (IFC2X3, "IFCFASTENERTYPE", Some("IFCELEMENTCOMPONENTTYPE"), &[]),
+    (IFC2X3, "IFCMECHANICALFASTENERTYPE", Some("IFCFASTENERTYPE"), &[]),
+    (IFC2X3, "IFCTRANSPORTELEMENTTYPE", Some("IFCELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4, "IFCTRANSPORTELEMENTTYPE", Some("IFCELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCTRANSPORTATIONDEVICETYPE", Some("IFCELEMENTTYPE"), &[]),
+    (IFC4X3, "IFCTRANSPORTELEMENTTYPE", Some("IFCTRANSPORTATIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4X3, "IFCVEHICLETYPE", Some("IFCTRANSPORTATIONDEVICETYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCGEOGRAPHICELEMENTTYPE", Some("IFCELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCCIVILELEMENTTYPE", Some("IFCELEMENTTYPE"), &[]),
+    (IFC2X3, "IFCSPATIALSTRUCTUREELEMENTTYPE", Some("IFCELEMENTTYPE"), &[]),
+    (IFC2X3, "IFCSPACETYPE", Some("IFCSPATIALSTRUCTUREELEMENTTYPE"), &["PredefinedType"]),
+    (IFC4 | IFC4X3, "IFCSPATIALELEMENTTYPE", Some("IFCTYPEPRODUCT"), &["ElementType"]),
+    (IFC4 | IFC4X3, "IFCSPATIALSTRUCTUREELEMENTTYPE", Some("IFCSPATIALELEMENTTYPE"), &[]),
+    (IFC4 | IFC4X3, "IFCSPACETYPE", Some("IFCSPATIALSTRUCTUREELEMENTTYPE"), &["PredefinedType", "LongName"]),
+    // Relationships
+    (ALL, "IFCRELATIONSHIP", Some("IFCROOT"), &[]),
+    (ALL, "IFCRELCONNECTS", Some("IFCRELATIONSHIP"), &[]),
+    (ALL, "IFCRELCONTAINEDINSPATIALSTRUCTURE", Some("IFCRELCONNECTS"), &["RelatedElements", "RelatingStructure"]),
+    (ALL, "IFCRELREFERENCEDINSPATIALSTRUCTURE", Some("IFCRELCONNECTS"), &["RelatedElements", "RelatingStructure"]),
+    (ALL, "IFCRELSPACEBOUNDARY", Some("IFCRELCONNECTS"), &["RelatingSpace", "RelatedBuildingElement", "ConnectionGeometry", "PhysicalOrVirtualBoundary", "InternalOrExternalBoundary"]),
+    (ALL, "IFCRELFILLSELEMENT", Some("IFCRELCONNECTS"), &["RelatingOpeningElement", "RelatedBuildingElement"]),
+    (IFC2X3, "IFCRELDECOMPOSES", Some("IFCRELATIONSHIP"), &["RelatingObject", "RelatedObjects"]),
+    (IFC4 | IFC4X3, "IFCRELDECOMPOSES", Some("IFCRELATIONSHIP"), &[]),
+    (IFC2X3, "IFCRELAGGREGATES", Some("IFCRELDECOMPOSES"), &[]),
+    (IFC2X3, "IFCRELNESTS", Some("IFCRELDECOMPOSES"), &[]),
+    (IFC4 | IFC4X3, "IFCRELAGGREGATES", Some("IFCRELDECOMPOSES"), &["RelatingObject", "RelatedObjects"]),
+    (IFC4 | IFC4X3, "IFCRELNESTS", Some("IFCRELDECOMPOSES"), &["RelatingObject", "RelatedObjects"]),
+    (IFC2X3, "IFCRELVOIDSELEMENT", Some("IFCRELCONNECTS"), &["RelatingBuildingElement", "RelatedOpeningElement"]),
+    (IFC4 | IFC4X3, "IFCRELVOIDSELEMENT", Some("IFCRELDECOMPOSES"), &["RelatingBuildingElement", "RelatedOpeningElement"]),
+    (IFC2X3, "IFCRELDEFINES", Some("IFCRELATIONSHIP"), &["RelatedObjects"]),
+    (IFC4 | IFC4X3, "IFCRELDEFINES", Some("IFCRELATIONSHIP"), &[]),
+    (IFC2X3, "IFCRELDEFINESBYTYPE", Some("IFCRELDEFINES"), &["RelatingType"]),
+    (IFC4 | IFC4X3, "IFCRELDEFINESBYTYPE", Some("IFCRELDEFINES"), &["RelatedObjects", "RelatingType"]),
+    (IFC2X3, "IFCRELDEFINESBYPROPERTIES", Some("IFCRELDEFINES"), &["RelatingPropertyDefinition"]),
+    (IFC4 | IFC4X3, "IFCRELDEFINESBYPROPERTIES", Some("IFCRELDEFINES"), &["RelatedObjects", "RelatingPropertyDefinition"]),
+    (ALL, "IFCRELASSOCIATES", Some("IFCRELATIONSHIP"), &["RelatedObjects"]),
+    (ALL, "IFCRELASSOCIATESMATERIAL", Some("IFCRELASSOCIATES"), &["RelatingMaterial"]),
+    (ALL, "IFCRELASSIGNS", Some("IFCRELATIONSHIP"), &["RelatedObjects", "RelatedObjectsType"]),
+    (ALL, "IFCRELASSIGNSTOGROUP", Some("IFCRELASSIGNS"), &["RelatingGroup"]),
+    // Properties
+    (ALL, "IFCPROPERTYDEFINITION", Some("IFCROOT"), &[]),
+    (ALL, "IFCPROPERTYSETDEFINITION", Some("IFCPROPERTYDEFINITION"), &[]),
+    (ALL, "IFCPROPERTYSET", Some("IFCPROPERTYSETDEFINITION"), &["HasProperties"]),
+    (IFC4 | IFC4X3, "IFCQUANTITYSET", Some("IFCPROPERTYSETDEFINITION"), &[]),
+    (IFC2X3, "IFCELEMENTQUANTITY", Some("IFCPROPERTYSETDEFINITION"), &["MethodOfMeasurement", "Quantities"]),
+    (IFC4 | IFC4X3, "IFCELEMENTQUANTITY", Some("IFCQUANTITYSET"), &["MethodOfMeasurement", "Quantities"]),
+    (IFC4 | IFC4X3, "IFCPROPERTYABSTRACTION", None, &[]),
+    (IFC2X3, "IFCPROPERTY", None, &["Name", "Description"]),
+    (IFC4 | IFC4X3, "IFCPROPERTY", Some("IFCPROPERTYABSTRACTION"), &["Name", "Description"]),
+    (ALL, "IFCSIMPLEPROPERTY", Some("IFCPROPERTY"), &[]),
+    (ALL, "IFCPROPERTYSINGLEVALUE", Some("IFCSIMPLEPROPERTY"), &["NominalValue", "Unit"]),
+    (ALL, "IFCPROPERTYENUMERATEDVALUE", Some("IFCSIMPLEPROPERTY"), &["EnumerationValues", "EnumerationReference"]),
+    (IFC2X3, "IFCPROPERTYBOUNDEDVALUE", Some("IFCSIMPLEPROPERTY"), &["UpperBoundValue", "LowerBoundValue", "Unit"]),
+    (IFC4 | IFC4X3, "IFCPROPERTYBOUNDEDVALUE", Some("IFCSIMPLEPROPERTY"), &["UpperBoundValue", "LowerBoundValue", "Unit", "SetPointValue"]),
+    (ALL, "IFCPROPERTYLISTVALUE", Some("IFCSIMPLEPROPERTY"), &["ListValues", "Unit"]),
+    (ALL, "IFCPROPERTYREFERENCEVALUE", Some("IFCSIMPLEPROPERTY"), &["UsageName", "PropertyReference"]),
+    (ALL, "IFCCOMPLEXPROPERTY", Some("IFCPROPERTY"), &["UsageName", "HasProperties"]),
+    (ALL, "IFCPHYSICALQUANTITY", None, &["Name", "Description"]),
+    (ALL, "IFCPHYSICALSIMPLEQUANTITY", Some("IFCPHYSICALQUANTITY"), &["Unit"]),
+    (IFC2X3, "IFCQUANTITYLENGTH", Some("IFCPHYSICALSIMPLEQUANTITY"), &["LengthValue"]),
+    (IFC4 | IFC4X3, "IFCQUANTITYLENGTH", Some("IFCPHYSICALSIMPLEQUANTITY"), &["LengthValue", "Formula"]),
+    (IFC2X3, "IFCQUANTITYAREA", Some("IFCPHYSICALSIMPLEQUANTITY"), &["AreaValue"]),
+    (IFC4 | IFC4X3, "IFCQUANTITYAREA", Some("IFCPHYSICALSIMPLEQUANTITY"), &["AreaValue", "Formula"]),
+    (IFC2X3, "IFCQUANTITYVOLUME", Some("IFCPHYSICALSIMPLEQUANTITY"), &["VolumeValue"]),
+    (IFC4 | IFC4X3, "IFCQUANTITYVOLUME", Some("IFCPHYSICALSIMPLEQUANTITY"), &["VolumeValue", "Formula"]),
+    (IFC2X3, "IFCQUANTITYCOUNT", Some("IFCPHYSICALSIMPLEQUANTITY"), &["CountValue"]),
+    (IFC4 | IFC4X3, "IFCQUANTITYCOUNT", Some("IFCPHYSICALSIMPLEQUANTITY"), &["CountValue", "Formula"]),
+    (IFC2X3, "IFCQUANTITYWEIGHT", Some("IFCPHYSICALSIMPLEQUANTITY"), &["WeightValue"]),
+    (IFC4 | IFC4X3, "IFCQUANTITYWEIGHT", Some("IFCPHYSICALSIMPLEQUANTITY"), &["WeightValue", "Formula"]),
+    (IFC2X3, "IFCQUANTITYTIME", Some("IFCPHYSICALSIMPLEQUANTITY"), &["TimeValue"]),
+    (IFC4 | IFC4X3, "IFCQUANTITYTIME", Some("IFCPHYSICALSIMPLEQUANTITY"), &["TimeValue", "Formula"]),
+];
@@ -0,0 +1,752 @@
+//! Parser for EXPRESS (ISO 10303-11) schema files.
+//!
+//! Reads the declarations a STEP reader needs from an `.exp` file: entities
+//! with their supertypes and explicit, derived and inverse attributes, and
+//! defined, enumeration and select types. Rules, functions, constants and
+//! `WHERE`/`UNIQUE` clauses are skipped. [`Schema::from_express`] turns the
+//! result into the attribute layout used for lookups by name.
+//!
+//! [`Schema::from_express`]: crate::schema::Schema::from_express
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::ParseError;
+
+/// The declarations of one `SCHEMA ... END_SCHEMA;` block.
+#[derive(Debug, Clone, Default)]
+pub struct ExpressSchema {
+    /// Schema name as written, e.g. `IFC4X3_ADD2`
+    pub name: String,
+    /// Entities in declaration order
+    pub entities: Vec<ExpressEntity>,
+    /// Defined, enumeration and select types in declaration order
+    pub types: Vec<ExpressType>,
+    entity_index: HashMap<String, usize>,
+    type_index: HashMap<String, usize>,
+}
+
+/// An `ENTITY` declaration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpressEntity {
+    /// Name as written, e.g. `IfcWall`
+    pub name: String,
+    pub is_abstract: bool,
+    /// `SUBTYPE OF (...)`; IFC only uses single inheritance
+    pub supertypes: Vec<String>,
+    /// Own explicit attributes in STEP order (redeclarations excluded)
+    pub attributes: Vec<ExpressAttribute>,
+    /// Names of derived attributes, including inherited explicit attributes
+    /// redeclared as derived (written as `*` in STEP)
+    pub derived: Vec<String>,
+    pub inverses: Vec<ExpressInverse>,
+}
+
+/// An explicit attribute, e.g. `PredefinedType : OPTIONAL IfcWallTypeEnum;`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpressAttribute {
+    pub name: String,
+    pub optional: bool,
+    pub ty: AttributeType,
+}
+
+/// An inverse attribute, e.g. `HasOpenings : SET [0:?] OF IfcRelVoidsElement FOR RelatingBuildingElement;`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpressInverse {
+    pub name: String,
+    /// The referencing entity, wrapped in the aggregate if there is one
+    pub ty: AttributeType,
+    /// Attribute of the referencing entity that points back
+    pub for_attribute: String,
+}
+
+/// A `TYPE` declaration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpressType {
+    pub name: String,
+    pub kind: TypeKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeKind {
+    /// `TYPE IfcLabel = STRING(255);`
+    Defined(AttributeType),
+    /// `TYPE IfcWallTypeEnum = ENUMERATION OF (MOVABLE, ...);`, items as written
+    Enumeration(Vec<String>),
+    /// `TYPE IfcValue = SELECT (IfcMeasureValue, ...);`
+    Select(Vec<String>),
+}
+
+/// The type of an attribute or of an aggregate's elements.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeType {
+    Simple(SimpleType),
+    /// An entity, defined type, enumeration or select, by name
+    Named(String),
+    Aggregate {
+        kind: AggregateKind,
+        bounds: Bounds,
+        element: Box<AttributeType>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimpleType {
+    Binary,
+    Boolean,
+    Integer,
+    Logical,
+    Number,
+    Real,
+    String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateKind {
+    Array,
+    Bag,
+    List,
+    Set,
+}
+
+/// Size bounds of an aggregate, `[1:?]` is `lower: 1, upper: None`.
+///
+/// Bounds given as expressions instead of literals are read as `[0:?]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bounds {
+    pub lower: u64,
+    pub upper: Option<u64>,
+}
+
+impl ExpressSchema {
+    /// Parse the first schema in an EXPRESS file.
+    ///
+    /// ```
+    /// use ifc_inspector::schema::express::{AttributeType, ExpressSchema, TypeKind};
+    ///
+    /// let schema = ExpressSchema::parse(
+    ///     "SCHEMA MINI;
+    ///      TYPE IfcLabel = STRING(255); END_TYPE;
+    ///      TYPE IfcWallTypeEnum = ENUMERATION OF (MOVABLE, PARAPET, NOTDEFINED); END_TYPE;
+    ///      ENTITY IfcRoot ABSTRACT SUPERTYPE OF (ONEOF(IfcWall));
+    ///        GlobalId : IfcLabel;
+    ///        Name : OPTIONAL IfcLabel;
+    ///      END_ENTITY;
+    ///      ENTITY IfcWall SUBTYPE OF (IfcRoot);
+    ///        PredefinedType : OPTIONAL IfcWallTypeEnum;
+    ///      END_ENTITY;
+    ///      END_SCHEMA;",
+    /// )?;
+    /// assert_eq!(schema.name, "MINI");
+    /// let wall = schema.entity("IFCWALL").unwrap();
+    /// assert_eq!(wall.supertypes, ["IfcRoot"]);
+    /// assert_eq!(
+    ///     schema.attributes("IfcWall").iter().map(|a| a.name.as_str()).collect::<Vec<_>>(),
+    ///     ["GlobalId", "Name", "PredefinedType"],
+    /// );
+    /// assert!(matches!(
+    ///     &schema.type_decl("IfcWallTypeEnum").unwrap().kind,
+    ///     TypeKind::Enumeration(items) if items.len() == 3
+    /// ));
+    /// # Ok::<(), ifc_inspector::error::ParseError>(())
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let tokens = tokenize(text)?;
+        Parser { tokens, pos: 0 }.schema()
+    }
+
+    /// Read and parse an `.exp` file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|source| ParseError::FileRead {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&text)
+    }
+
+    /// Entity by name (any case).
+    #[must_use]
+    pub fn entity(&self, name: &str) -> Option<&ExpressEntity> {
+        let index = self.entity_index.get(&name.to_ascii_uppercase())?;
+        self.entities.get(*index)
+    }
+
+    /// Defined, enumeration or select type by name (any case).
+    #[must_use]
+    pub fn type_decl(&self, name: &str) -> Option<&ExpressType> {
+        let index = self.type_index.get(&name.to_ascii_uppercase())?;
+        self.types.get(*index)
+    }
+
+    /// Explicit attributes of an entity in STEP order, inherited ones first.
+    /// Empty for unknown entities.
+    #[must_use]
+    pub fn attributes(&self, entity: &str) -> Vec<&ExpressAttribute> {
+        let mut chain = Vec::new();
+        let mut current = self.entity(entity);
+        while let Some(decl) = current {
+            // Guard against cyclic SUBTYPE OF declarations
+            if chain.len() > self.entities.len() {
+                break;
+            }
+            chain.push(decl);
+            current = decl.supertypes.first().and_then(|name| self.entity(name));
+        }
+        chain
+            .into_iter()
+            .rev()
+            .flat_map(|decl| &decl.attributes)
+            .collect()
+    }
+
+    /// Whether `entity` is `supertype` or one of its subtypes (any case).
+    #[must_use]
+    pub fn is_subtype_of(&self, entity: &str, supertype: &str) -> bool {
+        let mut current = self.entity(entity);
+        let mut steps = 0;
+        while let Some(decl) = current {
+            if decl.name.eq_ignore_ascii_case(supertype) {
+                return true;
+            }
+            steps += 1;
+            if steps > self.entities.len() {
+                break;
+            }
+            current = decl.supertypes.first().and_then(|name| self.entity(name));
+        }
+        false
+    }
+
+    fn push_entity(&mut self, entity: ExpressEntity) {
+        self.entity_index
+            .insert(entity.name.to_ascii_uppercase(), self.entities.len());
+        self.entities.push(entity);
+    }
+
+    fn push_type(&mut self, ty: ExpressType) {
+        self.type_index
+            .insert(ty.name.to_ascii_uppercase(), self.types.len());
+        self.types.push(ty);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    /// Identifier or keyword
+    Word(String),
+    Number(String),
+    Str,
+    Sym(char),
+}
+
+/// Tokens with the line they start on.
+fn tokenize(text: &str) -> Result<Vec<(Tok, usize)>, ParseError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while let Some(&c) = chars.get(i) {
+        let start_line = line;
+        match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+            }
+            c if c.is_whitespace() => i += 1,
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                while chars.get(i).is_some_and(|&c| c != '\n') {
+                    i += 1;
+                }
+            }
+            '(' if chars.get(i + 1) == Some(&'*') => {
+                // Comments nest in EXPRESS
+                let mut depth = 0;
+                loop {
+                    match (chars.get(i), chars.get(i + 1)) {
+                        (Some('('), Some('*')) => {
+                            depth += 1;
+                            i += 2;
+                        }
+                        (Some('*'), Some(')')) => {
+                            depth -= 1;
+                            i += 2;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        (Some(&c), _) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            i += 1;
+                        }
+                        (None, _) => return Err(express_error(start_line, "unterminated comment")),
+                    }
+                }
+            }
+            '\'' | '"' => {
+                let quote = c;
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        // '' is an escaped quote inside a simple string
+                        Some(&q) if q == quote && chars.get(i + 1) == Some(&quote) => i += 2,
+                        Some(&q) if q == quote => {
+                            i += 1;
+                            break;
+                        }
+                        Some(&c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            i += 1;
+                        }
+                        None => return Err(express_error(start_line, "unterminated string")),
+                    }
+                }
+                tokens.push((Tok::Str, start_line));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while chars
+                    .get(i)
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_')
+                {
+                    i += 1;
+                }
+                tokens.push((Tok::Word(chars[start..i].iter().collect()), start_line));
+            }
+            c if c.is_ascii_digit() => {
+                let start = i;
+                while chars
+                    .get(i)
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '.')
+                {
+                    i += 1;
+                }
+                tokens.push((Tok::Number(chars[start..i].iter().collect()), start_line));
+            }
+            c => {
+                tokens.push((Tok::Sym(c), start_line));
+                i += 1;
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn express_error(line: usize, message: impl Into<String>) -> ParseError {
+    ParseError::InvalidExpress {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Keywords that end the explicit attributes of an entity or start a new section.
+const ENTITY_SECTIONS: [&str; 5] = ["DERIVE", "INVERSE", "UNIQUE", "WHERE", "END_ENTITY"];
+
+struct Parser {
+    tokens: Vec<(Tok, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn schema(mut self) -> Result<ExpressSchema, ParseError> {
+        self.expect_keyword("SCHEMA")?;
+        let mut schema = ExpressSchema {
+            name: self.word()?,
+            ..ExpressSchema::default()
+        };
+        // Optional schema version id: SCHEMA name 'version';
+        if self.peek() == Some(&Tok::Str) {
+            self.pos += 1;
+        }
+        self.expect_sym(';')?;
+
+        loop {
+            let keyword = self.word()?.to_ascii_uppercase();
+            match keyword.as_str() {
+                "END_SCHEMA" => {
+                    self.expect_sym(';')?;
+                    return Ok(schema);
+                }
+                "TYPE" => {
+                    let ty = self.type_decl()?;
+                    schema.push_type(ty);
+                }
+                "ENTITY" => {
+                    let entity = self.entity()?;
+                    schema.push_entity(entity);
+                }
+                "FUNCTION" | "PROCEDURE" | "RULE" | "CONSTANT" | "SUBTYPE_CONSTRAINT" => {
+                    self.skip_block(&keyword)?;
+                }
+                "USE" | "REFERENCE" => self.skip_past(';')?,
+                _ => {
+                    return Err(express_error(
+                        self.line(),
+                        format!("unexpected '{keyword}' in schema body"),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// `TYPE` has been read: `Name = underlying; [WHERE ...] END_TYPE;`
+    fn type_decl(&mut self) -> Result<ExpressType, ParseError> {
+        let name = self.word()?;
+        self.expect_sym('=')?;
+
+        let extensible = self.eat_keyword("EXTENSIBLE");
+        let generic = self.eat_keyword("GENERIC_ENTITY");
+        let kind = if self.eat_keyword("ENUMERATION") {
+            TypeKind::Enumeration(self.constructed_items("OF")?)
+        } else if self.eat_keyword("SELECT") {
+            TypeKind::Select(self.constructed_items("")?)
+        } else if extensible || generic {
+            return Err(express_error(self.line(), "expected ENUMERATION or SELECT"));
+        } else {
+            TypeKind::Defined(self.attribute_type()?)
+        };
+        self.skip_until_keyword("END_TYPE")?;
+        self.pos += 1;
+        self.expect_sym(';')?;
+        Ok(ExpressType { name, kind })
+    }
+
+    /// Items of `ENUMERATION OF (a, b)`, `SELECT (a, b)` or their
+    /// `BASED_ON x WITH (c, d)` extensions. `keyword` is read before the list.
+    fn constructed_items(&mut self, keyword: &str) -> Result<Vec<String>, ParseError> {
+        if self.eat_keyword("BASED_ON") {
+            self.word()?;
+            if !self.eat_keyword("WITH") {
+                return Ok(Vec::new());
+            }
+        } else if !keyword.is_empty() && !self.eat_keyword(keyword) {
+            // EXTENSIBLE ENUMERATION; without items
+            return Ok(Vec::new());
+        }
+        if self.peek() != Some(&Tok::Sym('(')) {
+            return Ok(Vec::new());
+        }
+        self.name_list()
+    }
+
+    /// `ENTITY` has been read: header, attribute sections and `END_ENTITY;`.
+    fn entity(&mut self) -> Result<ExpressEntity, ParseError> {
+        let mut entity = ExpressEntity {
+            name: self.word()?,
+            is_abstract: false,
+            supertypes: Vec::new(),
+            attributes: Vec::new(),
+            derived: Vec::new(),
+            inverses: Vec::new(),
+        };
+
+        while !self.eat_sym(';') {
+            let keyword = self.word()?.to_ascii_uppercase();
+            match keyword.as_str() {
+                "ABSTRACT" => entity.is_abstract = true,
+                "SUPERTYPE" => {
+                    if self.eat_keyword("OF") {
+                        self.skip_parenthesized()?;
+                    }
+                }
+                "SUBTYPE" => {
+                    self.expect_keyword("OF")?;
+                    entity.supertypes = self.name_list()?;
+                }
+                _ => {
+                    return Err(express_error(
+                        self.line(),
+                        format!("unexpected '{keyword}' in header of entity {}", entity.name),
+                    ))
+                }
+            }
+        }
+
+        let mut section = String::from("EXPLICIT");
+        loop {
+            if let Some(keyword) = self
+                .peek_keyword()
+                .filter(|k| ENTITY_SECTIONS.contains(&k.as_str()))
+            {
+                self.pos += 1;
+                if keyword == "END_ENTITY" {
+                    self.expect_sym(';')?;
+                    return Ok(entity);
+                }
+                section = keyword;
+                continue;
+            }
+
+            match section.as_str() {
+                "EXPLICIT" => {
+                    let (names, redeclared) = self.attribute_names()?;
+                    let optional = self.eat_keyword("OPTIONAL");
+                    let ty = self.attribute_type()?;
+                    self.expect_sym(';')?;
+                    if !redeclared {
+                        entity
+                            .attributes
+                            .extend(names.into_iter().map(|name| ExpressAttribute {
+                                name,
+                                optional,
+                                ty: ty.clone(),
+                            }));
+                    }
+                }
+                "DERIVE" => {
+                    let (names, _) = self.attribute_names()?;
+                    entity.derived.extend(names);
+                    self.skip_past(';')?;
+                }
+                "INVERSE" => {
+                    let (names, redeclared) = self.attribute_names()?;
+                    let ty = self.attribute_type()?;
+                    self.expect_keyword("FOR")?;
+                    let mut for_attribute = self.word()?;
+                    // FOR Entity.Attribute
+                    if self.eat_sym('.') {
+                        for_attribute = self.word()?;
+                    }
+                    self.expect_sym(';')?;
+                    if !redeclared {
+                        entity
+                            .inverses
+                            .extend(names.into_iter().map(|name| ExpressInverse {
+                                name,
+                                ty: ty.clone(),
+                                for_attribute: for_attribute.clone(),
+                            }));
+                    }
+                }
+                // UNIQUE and WHERE rules
+                _ => self.skip_past(';')?,
+            }
+        }
+    }
+
+    /// `a, b :` or `SELF\Supertype.a :`, with whether it is a redeclaration.
+    fn attribute_names(&mut self) -> Result<(Vec<String>, bool), ParseError> {
+        let mut names = Vec::new();
+        let mut redeclared = false;
+        loop {
+            let mut name = self.word()?;
+            if name.eq_ignore_ascii_case("SELF") && self.eat_sym('\\') {
+                self.word()?;
+                self.expect_sym('.')?;
+                name = self.word()?;
+                redeclared = true;
+            }
+            // Renamed redeclaration: SELF\X.Old RENAMED New
+            if self.eat_keyword("RENAMED") {
+                name = self.word()?;
+            }
+            names.push(name);
+            if !self.eat_sym(',') {
+                break;
+            }
+        }
+        self.expect_sym(':')?;
+        Ok((names, redeclared))
+    }
+
+    fn attribute_type(&mut self) -> Result<AttributeType, ParseError> {
+        let word = self.word()?;
+        let simple = match word.to_ascii_uppercase().as_str() {
+            "BINARY" => SimpleType::Binary,
+            "BOOLEAN" => SimpleType::Boolean,
+            "INTEGER" => SimpleType::Integer,
+            "LOGICAL" => SimpleType::Logical,
+            "NUMBER" => SimpleType::Number,
+            "REAL" => SimpleType::Real,
+            "STRING" => SimpleType::String,
+            "ARRAY" | "BAG" | "LIST" | "SET" => return self.aggregate(&word),
+            _ => return Ok(AttributeType::Named(word)),
+        };
+        // Width or precision: STRING(255) FIXED, REAL(15), BINARY(32)
+        if self.peek() == Some(&Tok::Sym('(')) {
+            self.skip_parenthesized()?;
+        }
+        self.eat_keyword("FIXED");
+        Ok(AttributeType::Simple(simple))
+    }
+
+    fn aggregate(&mut self, word: &str) -> Result<AttributeType, ParseError> {
+        let kind = match word.to_ascii_uppercase().as_str() {
+            "ARRAY" => AggregateKind::Array,
+            "BAG" => AggregateKind::Bag,
+            "LIST" => AggregateKind::List,
+            _ => AggregateKind::Set,
+        };
+        let bounds = if self.eat_sym('[') {
+            self.bounds()?
+        } else {
+            Bounds::default()
+        };
+        self.expect_keyword("OF")?;
+        // ARRAY ... OF OPTIONAL, LIST/ARRAY ... OF UNIQUE
+        self.eat_keyword("OPTIONAL");
+        self.eat_keyword("UNIQUE");
+        let element = Box::new(self.attribute_type()?);
+        Ok(AttributeType::Aggregate {
+            kind,
+            bounds,
+            element,
+        })
+    }
+
+    /// `[` has been read: `lower : upper ]`.
+    fn bounds(&mut self) -> Result<Bounds, ParseError> {
+        let literal = |tokens: &[(Tok, usize)]| match tokens {
+            [(Tok::Number(n), _)] => n.parse::<u64>().ok(),
+            _ => None,
+        };
+        let start = self.pos;
+        self.skip_until_sym(':')?;
+        let lower = literal(&self.tokens[start..self.pos]).unwrap_or(0);
+        self.pos += 1;
+        let start = self.pos;
+        self.skip_until_sym(']')?;
+        let upper = literal(&self.tokens[start..self.pos]);
+        self.pos += 1;
+        Ok(Bounds { lower, upper })
+    }
+
+    /// `(a, b, c)`
+    fn name_list(&mut self) -> Result<Vec<String>, ParseError> {
+        self.expect_sym('(')?;
+        let mut names = vec![self.word()?];
+        while self.eat_sym(',') {
+            names.push(self.word()?);
+        }
+        self.expect_sym(')')?;
+        Ok(names)
+    }
+
+    /// Skip a balanced `( ... )` starting at the current token.
+    fn skip_parenthesized(&mut self) -> Result<(), ParseError> {
+        self.expect_sym('(')?;
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next() {
+                Some(Tok::Sym('(')) => depth += 1,
+                Some(Tok::Sym(')')) => depth -= 1,
+                Some(_) => {}
+                None => return Err(express_error(self.line(), "unbalanced parentheses")),
+            }
+        }
+        Ok(())
+    }
+
+    /// Skip a `FUNCTION`, `RULE`, ... up to its `END_<keyword>;`.
+    fn skip_block(&mut self, keyword: &str) -> Result<(), ParseError> {
+        let end = format!("END_{keyword}");
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next() {
+                Some(Tok::Word(w)) if w.eq_ignore_ascii_case(keyword) => depth += 1,
+                Some(Tok::Word(w)) if w.eq_ignore_ascii_case(&end) => depth -= 1,
+                Some(_) => {}
+                None => return Err(express_error(self.line(), format!("missing {end}"))),
+            }
+        }
+        self.expect_sym(';')
+    }
+
+    fn skip_past(&mut self, sym: char) -> Result<(), ParseError> {
+        self.skip_until_sym(sym)?;
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn skip_until_sym(&mut self, sym: char) -> Result<(), ParseError> {
+        while self.peek() != Some(&Tok::Sym(sym)) {
+            if self.next().is_none() {
+                return Err(express_error(self.line(), format!("expected '{sym}'")));
+            }
+        }
+        Ok(())
+    }
+
+    fn skip_until_keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        while self.peek_keyword().as_deref() != Some(keyword) {
+            if self.next().is_none() {
+                return Err(express_error(self.line(), format!("missing {keyword}")));
+            }
+        }
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|(tok, _)| tok)
+    }
+
+    fn next(&mut self) -> Option<Tok> {
+        let tok = self.tokens.get(self.pos).map(|(tok, _)| tok.clone());
+        self.pos += 1;
+        tok
+    }
+
+    /// Line of the current token, or of the last one at the end of input.
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn peek_keyword(&self) -> Option<String> {
+        match self.peek() {
+            Some(Tok::Word(w)) => Some(w.to_ascii_uppercase()),
+            _ => None,
+        }
+    }
+
+    fn word(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Some(Tok::Word(w)) => {
+                let w = w.clone();
+                self.pos += 1;
+                Ok(w)
+            }
+            _ => Err(express_error(self.line(), "expected a name")),
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.peek_keyword().as_deref() == Some(keyword);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(express_error(self.line(), format!("expected {keyword}")))
+        }
+    }
+
+    fn eat_sym(&mut self, sym: char) -> bool {
+        let found = self.peek() == Some(&Tok::Sym(sym));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_sym(&mut self, sym: char) -> Result<(), ParseError> {
+        if self.eat_sym(sym) {
+            Ok(())
+        } else {
+            Err(express_error(self.line(), format!("expected '{sym}'")))
+        }
+    }
+}
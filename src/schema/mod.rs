@@ -0,0 +1,8 @@
+pub mod definition;
+mod entities;
+pub mod express;
+pub mod validate;
+
+pub use definition::{EntityDefinition, Schema, SchemaVersion};
+pub use express::ExpressSchema;
+pub use validate::{validate, Violation};
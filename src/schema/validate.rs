@@ -0,0 +1,377 @@
+//! Conformance of entities to an EXPRESS schema.
+//!
+//! Checks each entity's attribute count and every attribute value against
+//! its declared type: literal versus reference, enumeration items, select
+//! members, aggregate sizes, and that references resolve to an entity of a
+//! compatible type. `WHERE` rules and functions are not evaluated.
+
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::parser::{EntitySource, StepEntity, StepValue};
+use crate::schema::express::{AggregateKind, AttributeType, ExpressSchema, SimpleType, TypeKind};
+
+/// An entity that does not conform to the schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub entity_id: u64,
+    pub entity_type: String,
+    /// Attribute the problem is in, if it is not about the entity as a whole
+    pub attribute: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} {}", self.entity_id, self.entity_type)?;
+        if let Some(attribute) = &self.attribute {
+            write!(f, ".{attribute}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Check every entity of `source` against `schema`, ordered by entity ID.
+///
+/// ```
+/// use ifc_inspector::parser::StepFile;
+/// use ifc_inspector::schema::{validate, ExpressSchema};
+///
+/// let schema = ExpressSchema::parse(
+///     "SCHEMA IFC4;
+///      TYPE IfcLabel = STRING; END_TYPE;
+///      TYPE IfcWallTypeEnum = ENUMERATION OF (PARAPET, NOTDEFINED); END_TYPE;
+///      ENTITY IfcWall; Name : OPTIONAL IfcLabel; PredefinedType : IfcWallTypeEnum; END_ENTITY;
+///      ENTITY IfcRel; RelatedObjects : SET [1:?] OF IfcWall; END_ENTITY;
+///      END_SCHEMA;",
+/// )?;
+/// let step_file = StepFile::parse(
+///     "ISO-10303-21;HEADER;FILE_SCHEMA(('IFC4'));ENDSEC;DATA;\
+///      #1=IFCWALL('Wall',.PARAPET.);\
+///      #2=IFCWALL($,.CURVED.);\
+///      #3=IFCREL((#1,#9));\
+///      ENDSEC;END-ISO-10303-21;",
+/// )?;
+/// let violations = validate(&step_file, &schema);
+/// let messages: Vec<String> = violations.iter().map(ToString::to_string).collect();
+/// assert_eq!(messages, [
+///     "#2 IFCWALL.PredefinedType: .CURVED. is not an item of IfcWallTypeEnum",
+///     "#3 IFCREL.RelatedObjects: [2]: #9 does not exist",
+/// ]);
+/// # Ok::<(), ifc_inspector::error::ParseError>(())
+/// ```
+///
+/// A defined type that is defined in terms of itself is reported instead of
+/// followed:
+///
+/// ```
+/// # use ifc_inspector::parser::StepFile;
+/// # use ifc_inspector::schema::{validate, ExpressSchema};
+/// let schema = ExpressSchema::parse(
+///     "SCHEMA IFC4;
+///      TYPE IfcA = IfcB; END_TYPE;
+///      TYPE IfcB = IfcA; END_TYPE;
+///      ENTITY IfcWall; Name : IfcA; END_ENTITY;
+///      END_SCHEMA;",
+/// )?;
+/// let step_file = StepFile::parse(
+///     "ISO-10303-21;HEADER;FILE_SCHEMA(('IFC4'));ENDSEC;DATA;\
+///      #1=IFCWALL('Wall');\
+///      ENDSEC;END-ISO-10303-21;",
+/// )?;
+/// let violations = validate(&step_file, &schema);
+/// assert_eq!(
+///     violations[0].to_string(),
+///     "#1 IFCWALL.Name: IfcA is defined in terms of itself"
+/// );
+/// # Ok::<(), ifc_inspector::error::ParseError>(())
+/// ```
+#[must_use]
+pub fn validate<S: EntitySource>(source: &S, schema: &ExpressSchema) -> Vec<Violation> {
+    let mut ids: Vec<u64> = source
+        .entity_type_counts()
+        .into_iter()
+        .flat_map(|(entity_type, _)| source.entity_ids_by_type(entity_type))
+        .copied()
+        .collect();
+    ids.sort_unstable();
+
+    let validator = Validator { source, schema };
+    ids.par_iter()
+        .filter_map(|&id| source.entity(id))
+        .flat_map_iter(|entity| validator.entity(&entity))
+        .collect()
+}
+
+struct Validator<'a, S> {
+    source: &'a S,
+    schema: &'a ExpressSchema,
+}
+
+impl<S: EntitySource> Validator<'_, S> {
+    fn entity(&self, entity: &StepEntity) -> Vec<Violation> {
+        let violation = |attribute: Option<&str>, message: String| Violation {
+            entity_id: entity.id,
+            entity_type: entity.entity_type.clone(),
+            attribute: attribute.map(str::to_string),
+            message,
+        };
+
+        let Some(declaration) = self.schema.entity(&entity.entity_type) else {
+            return vec![violation(
+                None,
+                format!("entity type is not defined in {}", self.schema.name),
+            )];
+        };
+        let mut violations = Vec::new();
+        if declaration.is_abstract {
+            violations.push(violation(
+                None,
+                "abstract entity is instantiated".to_string(),
+            ));
+        }
+
+        let attributes = self.schema.attributes(&entity.entity_type);
+        if attributes.len() != entity.values.len() {
+            violations.push(violation(
+                None,
+                format!(
+                    "expected {} attributes, found {}",
+                    attributes.len(),
+                    entity.values.len()
+                ),
+            ));
+        }
+
+        let derived = self.derived_attributes(&entity.entity_type);
+        for (attribute, value) in attributes.iter().zip(&entity.values) {
+            let result = if derived.contains(&attribute.name.to_ascii_uppercase()) {
+                match value {
+                    StepValue::Derived => Ok(()),
+                    _ => Err("derived attribute must be written as *".to_string()),
+                }
+            } else {
+                match value {
+                    StepValue::Null if attribute.optional => Ok(()),
+                    StepValue::Null => Err("required attribute is $".to_string()),
+                    value => self.value(&attribute.ty, value),
+                }
+            };
+            if let Err(message) = result {
+                violations.push(violation(Some(&attribute.name), message));
+            }
+        }
+        violations
+    }
+
+    /// Upper-case names of the attributes redeclared as derived by the
+    /// entity or its supertypes.
+    fn derived_attributes(&self, entity_type: &str) -> HashSet<String> {
+        let mut derived = HashSet::new();
+        let mut current = self.schema.entity(entity_type);
+        for _ in 0..=self.schema.entities.len() {
+            let Some(declaration) = current else { break };
+            derived.extend(declaration.derived.iter().map(|n| n.to_ascii_uppercase()));
+            current = declaration
+                .supertypes
+                .first()
+                .and_then(|name| self.schema.entity(name));
+        }
+        derived
+    }
+
+    fn value(&self, ty: &AttributeType, value: &StepValue) -> Result<(), String> {
+        match ty {
+            AttributeType::Simple(simple) => simple_value(*simple, value),
+            AttributeType::Named(name) => self.named_value(name, value),
+            AttributeType::Aggregate {
+                kind,
+                bounds,
+                element,
+            } => {
+                let StepValue::List(items) = value else {
+                    return Err(format!("expected a list, found {}", describe(value)));
+                };
+                let len = items.len() as u64;
+                if len < bounds.lower || bounds.upper.is_some_and(|upper| len > upper) {
+                    let upper = bounds.upper.map_or("?".to_string(), |u| u.to_string());
+                    return Err(format!(
+                        "expected [{}:{upper}] items, found {len}",
+                        bounds.lower
+                    ));
+                }
+                for (i, item) in items.iter().enumerate() {
+                    // Only arrays can have unset positions
+                    if matches!(item, StepValue::Null) && *kind == AggregateKind::Array {
+                        continue;
+                    }
+                    self.value(element, item)
+                        .map_err(|message| format!("[{}]: {message}", i + 1))?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn named_value(&self, name: &str, value: &StepValue) -> Result<(), String> {
+        if self.schema.entity(name).is_some() {
+            return self.reference(value, &[name], name);
+        }
+        let Some(declaration) = self.schema.type_decl(name) else {
+            // Types the schema does not declare cannot be checked
+            return Ok(());
+        };
+        match &declaration.kind {
+            TypeKind::Defined(_) if self.is_cyclic_defined_type(name) => {
+                Err(format!("{name} is defined in terms of itself"))
+            }
+            TypeKind::Defined(underlying) => {
+                // Tolerate the redundant wrapper in IFCLABEL('x') for an IfcLabel attribute
+                let value = match value {
+                    StepValue::Typed { type_name, value }
+                        if type_name.eq_ignore_ascii_case(name) =>
+                    {
+                        value
+                    }
+                    value => value,
+                };
+                self.value(underlying, value)
+            }
+            TypeKind::Enumeration(items) => {
+                let item = match value {
+                    StepValue::Enum(item) => item.as_str(),
+                    // .T. and .F. are read as booleans
+                    StepValue::Boolean(true) => "T",
+                    StepValue::Boolean(false) => "F",
+                    value => return Err(format!("expected {name}, found {}", describe(value))),
+                };
+                if items.iter().any(|i| i.eq_ignore_ascii_case(item)) {
+                    Ok(())
+                } else {
+                    Err(format!(".{item}. is not an item of {name}"))
+                }
+            }
+            TypeKind::Select(_) => {
+                let mut members = Vec::new();
+                self.select_members(name, &mut members);
+                match value {
+                    StepValue::Reference(_) => {
+                        let entities: Vec<&str> = members
+                            .iter()
+                            .copied()
+                            .filter(|m| self.schema.entity(m).is_some())
+                            .collect();
+                        self.reference(value, &entities, name)
+                    }
+                    StepValue::Typed { type_name, value } => {
+                        let member = members.iter().find(|m| {
+                            m.eq_ignore_ascii_case(type_name) && self.schema.type_decl(m).is_some()
+                        });
+                        match member {
+                            Some(member) => self.named_value(member, value),
+                            None => Err(format!("{type_name} is not a member of {name}")),
+                        }
+                    }
+                    value => Err(format!(
+                        "expected a reference or typed value for {name}, found {}",
+                        describe(value)
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Whether following a defined type to the type it is defined as leads
+    /// back to a defined type seen before (`TYPE a = b; TYPE b = a;`), which
+    /// would recurse without consuming any of the value.
+    fn is_cyclic_defined_type(&self, name: &str) -> bool {
+        let mut current = name;
+        for _ in 0..=self.schema.types.len() {
+            match self.schema.type_decl(current).map(|t| &t.kind) {
+                Some(TypeKind::Defined(AttributeType::Named(underlying))) => current = underlying,
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Members of a select type, nested selects flattened.
+    fn select_members<'s>(&'s self, name: &str, out: &mut Vec<&'s str>) {
+        let Some(TypeKind::Select(items)) = self.schema.type_decl(name).map(|t| &t.kind) else {
+            return;
+        };
+        for item in items {
+            if out.iter().any(|seen| seen.eq_ignore_ascii_case(item)) {
+                continue;
+            }
+            out.push(item);
+            self.select_members(item, out);
+        }
+    }
+
+    /// A reference to an entity of one of `allowed` (or a subtype);
+    /// `expected` names the allowed types in the message.
+    fn reference(&self, value: &StepValue, allowed: &[&str], expected: &str) -> Result<(), String> {
+        let StepValue::Reference(id) = value else {
+            return Err(format!(
+                "expected a reference to {expected}, found {}",
+                describe(value)
+            ));
+        };
+        let Some(target) = self.source.entity_type(*id) else {
+            return Err(format!("#{id} does not exist"));
+        };
+        if allowed
+            .iter()
+            .any(|allowed| self.schema.is_subtype_of(target, allowed))
+        {
+            Ok(())
+        } else {
+            Err(format!("#{id} is {target}, expected {expected}"))
+        }
+    }
+}
+
+fn simple_value(simple: SimpleType, value: &StepValue) -> Result<(), String> {
+    let valid = match simple {
+        SimpleType::Real | SimpleType::Number => {
+            matches!(value, StepValue::Real(_) | StepValue::Integer(_))
+        }
+        SimpleType::Integer => matches!(value, StepValue::Integer(_)),
+        SimpleType::String => matches!(value, StepValue::String(_)),
+        SimpleType::Binary => matches!(value, StepValue::Binary(_)),
+        SimpleType::Boolean => matches!(value, StepValue::Boolean(_)),
+        SimpleType::Logical => {
+            matches!(value, StepValue::Boolean(_))
+                || matches!(value, StepValue::Enum(e) if e == "U")
+        }
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "expected {}, found {}",
+            format!("{simple:?}").to_ascii_uppercase(),
+            describe(value)
+        ))
+    }
+}
+
+fn describe(value: &StepValue) -> String {
+    match value {
+        StepValue::String(_) => "a string".to_string(),
+        StepValue::Binary(_) => "a binary".to_string(),
+        StepValue::Real(_) => "a real".to_string(),
+        StepValue::Integer(_) => "an integer".to_string(),
+        StepValue::Boolean(_) => "a boolean".to_string(),
+        StepValue::Enum(item) => format!(".{item}."),
+        StepValue::Reference(id) => format!("#{id}"),
+        StepValue::List(_) => "a list".to_string(),
+        StepValue::Parameters(values) => format!("{} parameters", values.len()),
+        StepValue::Typed { type_name, .. } => type_name.clone(),
+        StepValue::Null => "$".to_string(),
+        StepValue::Derived => "*".to_string(),
+    }
+}
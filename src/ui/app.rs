@@ -1,4 +1,4 @@
-use crate::model::{Category, ElementType, IfcProject};
+use crate::model::{Category, ElementType, IfcProject, Space};
 use crate::parser::step::StepFile;
 use color_eyre::Result;
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
@@ -15,6 +15,7 @@ pub enum View {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FocusPanel {
     Levels,
+    Spaces,
     Categories,
     Types,
 }
@@ -28,6 +29,7 @@ pub struct App {
     pub selected_type: usize,
     pub selected_instance: usize,
     pub selected_level: usize, // 0 = "All", 1+ = storey index
+    pub selected_space: usize, // 0 = "All", 1+ = index in get_level_spaces()
     pub types_scroll_offset: usize,
     pub property_scroll_offset: usize,
     pub instances_scroll_offset: usize,
@@ -46,6 +48,7 @@ impl App {
             selected_type: 0,
             selected_instance: 0,
             selected_level: 0, // 0 = "All"
+            selected_space: 0,
             types_scroll_offset: 0,
             property_scroll_offset: 0,
             instances_scroll_offset: 0,
@@ -105,6 +108,7 @@ impl App {
     fn navigate_up(&mut self) {
         match self.focus_panel {
             FocusPanel::Levels => self.previous_level(),
+            FocusPanel::Spaces => self.previous_space(),
             FocusPanel::Categories => self.previous_category(),
             FocusPanel::Types => self.previous_type(),
         }
@@ -113,6 +117,7 @@ impl App {
     fn navigate_down(&mut self) {
         match self.focus_panel {
             FocusPanel::Levels => self.next_level(),
+            FocusPanel::Spaces => self.next_space(),
             FocusPanel::Categories => self.next_category(),
             FocusPanel::Types => self.next_type(),
         }
@@ -121,14 +126,16 @@ impl App {
     fn navigate_left(&mut self) {
         match self.focus_panel {
             FocusPanel::Types => self.focus_panel = FocusPanel::Categories,
-            FocusPanel::Categories => self.focus_panel = FocusPanel::Levels,
+            FocusPanel::Categories => self.focus_panel = FocusPanel::Spaces,
+            FocusPanel::Spaces => self.focus_panel = FocusPanel::Levels,
             FocusPanel::Levels => {}
         }
     }
 
     fn navigate_right(&mut self) {
         match self.focus_panel {
-            FocusPanel::Levels => self.focus_panel = FocusPanel::Categories,
+            FocusPanel::Levels => self.focus_panel = FocusPanel::Spaces,
+            FocusPanel::Spaces => self.focus_panel = FocusPanel::Categories,
             FocusPanel::Categories => self.focus_panel = FocusPanel::Types,
             FocusPanel::Types => {}
         }
@@ -137,6 +144,7 @@ impl App {
     fn previous_level(&mut self) {
         if self.selected_level > 0 {
             self.selected_level -= 1;
+            self.selected_space = 0;
             self.selected_type = 0;
             self.types_scroll_offset = 0;
         }
@@ -147,6 +155,25 @@ impl App {
         let max_level = self.project.storeys.len();
         if self.selected_level < max_level {
             self.selected_level += 1;
+            self.selected_space = 0;
+            self.selected_type = 0;
+            self.types_scroll_offset = 0;
+        }
+    }
+
+    fn previous_space(&mut self) {
+        if self.selected_space > 0 {
+            self.selected_space -= 1;
+            self.selected_type = 0;
+            self.types_scroll_offset = 0;
+        }
+    }
+
+    fn next_space(&mut self) {
+        // 0 = "All", then the spaces on the selected level
+        let max_space = self.get_level_spaces().len();
+        if self.selected_space < max_space {
+            self.selected_space += 1;
             self.selected_type = 0;
             self.types_scroll_offset = 0;
         }
@@ -301,7 +328,7 @@ impl App {
         }
     }
 
-    /// Get types filtered by selected level
+    /// Get types filtered by selected level and space
     #[must_use]
     pub fn get_filtered_types(&self) -> Vec<&crate::model::ElementType> {
         let category = match self.project.categories.get(self.selected_category) {
@@ -309,29 +336,57 @@ impl App {
             None => return Vec::new(),
         };
 
-        if self.selected_level == 0 {
+        if !self.is_filtered() {
             // "All" - no filtering
             return category.types.iter().collect();
         }
 
-        // Get storey ID for selected level
-        let storey_id = match self.project.storeys.get(self.selected_level - 1) {
-            Some(s) => s.id,
-            None => return category.types.iter().collect(),
-        };
-
-        // Filter types that have instances on this level
+        // Filter types that have instances on this level and in this space
+        let (storey_id, space_id) = self.get_selected_filter();
         category
             .types
             .iter()
             .filter(|t| {
                 t.instance_ids
                     .iter()
-                    .any(|id| self.project.element_to_storey.get(id) == Some(&storey_id))
+                    .any(|&id| self.project.instance_matches(id, storey_id, space_id))
             })
             .collect()
     }
 
+    /// Spaces on the selected level (all spaces if "All" is selected)
+    #[must_use]
+    pub fn get_level_spaces(&self) -> Vec<&Space> {
+        let storey_id = self.get_selected_storey_id();
+        self.project
+            .spaces
+            .iter()
+            .filter(|s| storey_id.is_none() || s.storey_id == storey_id)
+            .collect()
+    }
+
+    /// Get selected space ID (None if "All" is selected)
+    fn get_selected_space_id(&self) -> Option<u64> {
+        if self.selected_space == 0 {
+            None
+        } else {
+            self.get_level_spaces()
+                .get(self.selected_space - 1)
+                .map(|s| s.id)
+        }
+    }
+
+    /// Whether a level or space filter is selected
+    fn is_filtered(&self) -> bool {
+        self.selected_level != 0 || self.selected_space != 0
+    }
+
+    /// Selected storey and space IDs, resolved once for a pass over the
+    /// instances with `IfcProject::instance_matches`
+    fn get_selected_filter(&self) -> (Option<u64>, Option<u64>) {
+        (self.get_selected_storey_id(), self.get_selected_space_id())
+    }
+
     #[must_use]
     pub fn get_selected_type(&self) -> Option<&crate::model::ElementType> {
         let filtered = self.get_filtered_types();
@@ -350,31 +405,33 @@ impl App {
         }
     }
 
-    /// Get filtered instance count for a category (respects `selected_level`)
+    /// Get filtered instance count for a category (respects `selected_level` and `selected_space`)
     #[must_use]
     pub fn get_filtered_category_count(&self, category: &Category) -> usize {
-        match self.get_selected_storey_id() {
-            None => category.total_count,
-            Some(storey_id) => category
-                .types
-                .iter()
-                .flat_map(|t| &t.instance_ids)
-                .filter(|id| self.project.element_to_storey.get(*id) == Some(&storey_id))
-                .count(),
+        if !self.is_filtered() {
+            return category.total_count;
         }
+        let (storey_id, space_id) = self.get_selected_filter();
+        category
+            .types
+            .iter()
+            .flat_map(|t| &t.instance_ids)
+            .filter(|&&id| self.project.instance_matches(id, storey_id, space_id))
+            .count()
     }
 
-    /// Get filtered instance count for a type (respects `selected_level`)
+    /// Get filtered instance count for a type (respects `selected_level` and `selected_space`)
     #[must_use]
     pub fn get_filtered_instance_count(&self, element_type: &ElementType) -> usize {
-        match self.get_selected_storey_id() {
-            None => element_type.instance_count,
-            Some(storey_id) => element_type
-                .instance_ids
-                .iter()
-                .filter(|id| self.project.element_to_storey.get(*id) == Some(&storey_id))
-                .count(),
+        if !self.is_filtered() {
+            return element_type.instance_count;
         }
+        let (storey_id, space_id) = self.get_selected_filter();
+        element_type
+            .instance_ids
+            .iter()
+            .filter(|&&id| self.project.instance_matches(id, storey_id, space_id))
+            .count()
     }
 
     /// Get the ID of the currently selected instance
@@ -399,7 +456,7 @@ impl App {
         // For now, use the properties we have from the type
         // In future, we could load all instance properties from step_file
         for (name, value) in &element_type.properties {
-            if let Some(num) = parse_numeric_value(value) {
+            if let Some(num) = self.numeric_property_value(element_type, None, name, value) {
                 property_values.entry(name.clone()).or_default().push(num);
             }
         }
@@ -436,7 +493,10 @@ impl App {
         element_type
             .properties
             .iter()
-            .filter(|(_, v)| parse_numeric_value(v).is_none())
+            .filter(|(k, v)| {
+                self.numeric_property_value(element_type, None, k, v)
+                    .is_none()
+            })
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect()
     }
@@ -454,7 +514,8 @@ impl App {
         let mut all_props: HashMap<String, String> = element_type.properties.clone();
 
         // Override/merge with instance-level properties if available
-        if let Some(instance_id) = self.get_selected_instance_id() {
+        let instance_id = self.get_selected_instance_id();
+        if let Some(instance_id) = instance_id {
             if let Some(instance_props) = self.project.element_properties.get(&instance_id) {
                 for (k, v) in instance_props {
                     all_props.insert(k.clone(), v.clone());
@@ -465,7 +526,9 @@ impl App {
         let mut props: Vec<(String, String, bool)> = all_props
             .iter()
             .map(|(k, v)| {
-                let is_numeric = parse_numeric_value(v).is_some();
+                let is_numeric = self
+                    .numeric_property_value(element_type, instance_id, k, v)
+                    .is_some();
                 (k.clone(), v.clone(), is_numeric)
             })
             .collect();
@@ -480,6 +543,51 @@ impl App {
         props
     }
 
+    /// Numeric value of a property, unless its measure type marks it as text
+    /// (e.g. an `IFCLABEL('200')` reference is not a number)
+    fn numeric_property_value(
+        &self,
+        element_type: &ElementType,
+        instance_id: Option<u64>,
+        name: &str,
+        value: &str,
+    ) -> Option<f64> {
+        let is_text = self
+            .property_measure(element_type, instance_id, name)
+            .is_some_and(|m| matches!(m, "IFCLABEL" | "IFCTEXT" | "IFCIDENTIFIER"));
+        if is_text {
+            None
+        } else {
+            parse_numeric_value(value)
+        }
+    }
+
+    /// Measure type of the value shown for a property: from the instance if
+    /// it has the property, else from the type, else from the first instance
+    /// (the order `build_categories` fills type properties in)
+    fn property_measure(
+        &self,
+        element_type: &ElementType,
+        instance_id: Option<u64>,
+        name: &str,
+    ) -> Option<&str> {
+        let source = instance_id
+            .into_iter()
+            .chain([element_type.id])
+            .chain(element_type.instance_ids.first().copied())
+            .find(|id| {
+                self.project
+                    .element_properties
+                    .get(id)
+                    .is_some_and(|properties| properties.contains_key(name))
+            })?;
+        self.project
+            .property_measures
+            .get(&source)?
+            .get(name)
+            .map(String::as_str)
+    }
+
     /// Get storey name for an instance ID
     #[must_use]
     pub fn get_storey_name_for_instance(&self, instance_id: u64) -> String {
@@ -494,6 +602,25 @@ impl App {
         }
     }
 
+    /// Get the numbers of the spaces an instance is in or bounds
+    #[must_use]
+    pub fn get_space_names_for_instance(&self, instance_id: u64) -> String {
+        let names: Vec<&str> = self
+            .project
+            .element_to_spaces
+            .get(&instance_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.project.spaces.iter().find(|s| s.id == *id))
+            .map(|s| s.number.as_str())
+            .collect();
+        if names.is_empty() {
+            "-".to_string()
+        } else {
+            names.join(", ")
+        }
+    }
+
     /// Get `GlobalId` for an instance ID
     #[must_use]
     pub fn get_instance_global_id(&self, instance_id: u64) -> String {
@@ -504,6 +631,18 @@ impl App {
             .unwrap_or_else(|| "-".to_string())
     }
 
+    /// Get selected space name (for display)
+    #[must_use]
+    pub fn get_selected_space_name(&self) -> String {
+        if self.selected_space == 0 {
+            "All".to_string()
+        } else {
+            self.get_level_spaces()
+                .get(self.selected_space - 1)
+                .map_or_else(|| "-".to_string(), |s| s.number.clone())
+        }
+    }
+
     /// Get selected level name (for display)
     #[must_use]
     pub fn get_selected_level_name(&self) -> String {
@@ -32,7 +32,7 @@ const COUNT_COLOR: Color = BRAND_GREEN;
 
 pub fn draw_dashboard(frame: &mut Frame, app: &App) {
     let chunks = Layout::vertical([
-        Constraint::Length(3), // Header
+        Constraint::Length(4), // Header (title + file info)
         Constraint::Min(10),   // Main content
         Constraint::Length(3), // Footer
     ])
@@ -55,9 +55,56 @@ fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
         app.project.total_elements()
     );
 
-    let header = Paragraph::new(title)
-        .style(HEADER_STYLE)
-        .block(Block::default().borders(Borders::ALL));
+    // Second line: schema, MVD and authoring tool (helps triage where a file came from)
+    let file_header = &app.project.header;
+    let mut info: Vec<String> = Vec::new();
+    if let Some(model) = &app.project.model {
+        let models = &app.project.archive_models;
+        match models.iter().position(|m| m == model) {
+            Some(index) if models.len() > 1 => {
+                info.push(format!(
+                    "{model} ({} of {} models)",
+                    index + 1,
+                    models.len()
+                ));
+            }
+            _ => info.push(model.clone()),
+        }
+    }
+    info.push(app.project.schema.clone());
+    if !file_header.view_definition.is_empty() {
+        info.push(file_header.view_definition.clone());
+    }
+    if !file_header.originating_system.is_empty() {
+        info.push(file_header.originating_system.clone());
+    }
+    if !file_header.preprocessor_version.is_empty() {
+        info.push(file_header.preprocessor_version.clone());
+    }
+    if !file_header.time_stamp.is_empty() {
+        info.push(file_header.time_stamp.clone());
+    }
+
+    let mut info_line = vec![Span::styled(
+        format!(" {} ", info.join(" | ")),
+        Style::default().fg(BRAND_MUTED),
+    )];
+    if !app.project.diagnostics.is_empty() {
+        info_line.push(Span::styled(
+            format!(
+                "| {} skipped entities ({} diagnostics) ",
+                app.project.skipped_entities,
+                app.project.diagnostics.len()
+            ),
+            Style::default().fg(BRAND_ORANGE),
+        ));
+    }
+
+    let header = Paragraph::new(vec![
+        Line::from(Span::styled(title, HEADER_STYLE)),
+        Line::from(info_line),
+    ])
+    .block(Block::default().borders(Borders::ALL));
 
     frame.render_widget(header, area);
 }
@@ -65,14 +112,16 @@ fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
 fn draw_main_content(frame: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::horizontal([
         Constraint::Percentage(15), // Levels
-        Constraint::Percentage(25), // Categories
-        Constraint::Percentage(60), // Types
+        Constraint::Percentage(15), // Spaces
+        Constraint::Percentage(20), // Categories
+        Constraint::Percentage(50), // Types
     ])
     .split(area);
 
     draw_levels(frame, chunks[0], app);
-    draw_categories(frame, chunks[1], app);
-    draw_types(frame, chunks[2], app);
+    draw_spaces(frame, chunks[1], app);
+    draw_categories(frame, chunks[2], app);
+    draw_types(frame, chunks[3], app);
 }
 
 fn draw_levels(frame: &mut Frame, area: Rect, app: &App) {
@@ -100,10 +149,28 @@ fn draw_levels(frame: &mut Frame, area: Rect, app: &App) {
         Span::styled(all_marker, Style::default().fg(BRAND_ORANGE)),
     ])));
 
-    // Storeys (index 1+)
-    for (i, storey) in app.project.storeys.iter().enumerate() {
+    // Storeys (index 1+), under a heading per building if there are several
+    let storeys = &app.project.storeys;
+    let grouped = storeys
+        .iter()
+        .any(|s| s.building_id != storeys[0].building_id);
+    for (i, storey) in storeys.iter().enumerate() {
         let is_selected = (i + 1) == app.selected_level;
 
+        if grouped && (i == 0 || storeys[i - 1].building_id != storey.building_id) {
+            let building = storey
+                .building_id
+                .and_then(|id| app.project.spatial_node(id))
+                .map_or("(no building)", |b| b.name.as_str());
+            items.push(ListItem::new(Line::from(Span::styled(
+                building,
+                Style::default()
+                    .fg(BRAND_MUTED)
+                    .add_modifier(Modifier::BOLD),
+            ))));
+        }
+        let indent = if grouped { "  " } else { "" };
+
         let elev_str = if storey.elevation >= 0.0 {
             format!("+{:.1}m", storey.elevation / 1000.0)
         } else {
@@ -125,6 +192,7 @@ fn draw_levels(frame: &mut Frame, area: Rect, app: &App) {
         };
 
         let content = Line::from(vec![
+            Span::raw(indent),
             Span::styled(&storey.name, style),
             Span::styled(format!(" {elev_str}"), Style::default().fg(BRAND_MUTED)),
             Span::styled(marker, Style::default().fg(BRAND_ORANGE)),
@@ -150,22 +218,37 @@ fn draw_levels(frame: &mut Frame, area: Rect, app: &App) {
     frame.render_widget(list, area);
 }
 
-fn draw_categories(frame: &mut Frame, area: Rect, app: &App) {
-    let is_focused = app.focus_panel == FocusPanel::Categories;
+fn draw_spaces(frame: &mut Frame, area: Rect, app: &App) {
+    let is_focused = app.focus_panel == FocusPanel::Spaces;
 
-    let items: Vec<ListItem> = app
-        .project
-        .categories
-        .iter()
+    // "All" first, then the spaces on the selected level
+    let spaces = app.get_level_spaces();
+    let names = std::iter::once(("All".to_string(), None)).chain(spaces.iter().map(|space| {
+        let name = match &space.long_name {
+            Some(long_name) => format!("{} {long_name}", space.number),
+            None => space.number.clone(),
+        };
+        (name, space.area)
+    }));
+
+    // Keep the selected space visible (subtract 2 for borders)
+    let visible_rows = (area.height as usize).saturating_sub(2);
+    let scroll_offset = if app.selected_space >= visible_rows {
+        app.selected_space - visible_rows + 1
+    } else {
+        0
+    };
+
+    let items: Vec<ListItem> = names
         .enumerate()
-        .map(|(i, cat)| {
-            let is_selected = i == app.selected_category;
+        .skip(scroll_offset)
+        .take(visible_rows)
+        .map(|(i, (name, area))| {
+            let is_selected = i == app.selected_space;
             let style = if is_selected && is_focused {
                 SELECTED_STYLE
             } else if is_selected {
                 Style::default().add_modifier(Modifier::BOLD)
-            } else if cat.is_priority {
-                Style::default().fg(PRIORITY_COLOR)
             } else {
                 Style::default()
             };
@@ -176,20 +259,13 @@ fn draw_categories(frame: &mut Frame, area: Rect, app: &App) {
                 ""
             };
 
-            // Get filtered count (respects selected_level)
-            let filtered_count = app.get_filtered_category_count(cat);
+            let area = area.map(|a| format!(" {a:.1}m²")).unwrap_or_default();
 
-            let content = Line::from(vec![
-                Span::styled(&cat.name, style),
-                Span::raw(" "),
-                Span::styled(
-                    format!("({filtered_count})"),
-                    Style::default().fg(COUNT_COLOR),
-                ),
+            ListItem::new(Line::from(vec![
+                Span::styled(name, style),
+                Span::styled(area, Style::default().fg(BRAND_MUTED)),
                 Span::styled(marker, Style::default().fg(BRAND_ORANGE)),
-            ]);
-
-            ListItem::new(content)
+            ]))
         })
         .collect();
 
@@ -199,6 +275,107 @@ fn draw_categories(frame: &mut Frame, area: Rect, app: &App) {
         Style::default()
     };
 
+    let title = format!(" Spaces ({}) ", spaces.len() + 1); // +1 for "All"
+    let list = List::new(items).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(border_style),
+    );
+
+    frame.render_widget(list, area);
+
+    // Draw scrollbar if needed
+    if spaces.len() + 1 > visible_rows {
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓"));
+        let mut scrollbar_state =
+            ScrollbarState::new(spaces.len() + 1).position(app.selected_space);
+
+        let scrollbar_area = Rect {
+            x: area.x + area.width - 1,
+            y: area.y + 1,
+            width: 1,
+            height: area.height.saturating_sub(2),
+        };
+        frame.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
+    }
+}
+
+fn draw_categories(frame: &mut Frame, area: Rect, app: &App) {
+    let is_focused = app.focus_panel == FocusPanel::Categories;
+
+    // Categories under a heading per discipline if there are several
+    let categories = &app.project.categories;
+    let grouped = categories
+        .iter()
+        .any(|c| c.discipline != categories[0].discipline);
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut selected_row = 0;
+    for (i, cat) in categories.iter().enumerate() {
+        if grouped && (i == 0 || categories[i - 1].discipline != cat.discipline) {
+            items.push(ListItem::new(Line::from(Span::styled(
+                cat.discipline.to_string(),
+                Style::default()
+                    .fg(BRAND_MUTED)
+                    .add_modifier(Modifier::BOLD),
+            ))));
+        }
+        let indent = if grouped { "  " } else { "" };
+
+        let is_selected = i == app.selected_category;
+        if is_selected {
+            selected_row = items.len();
+        }
+        let style = if is_selected && is_focused {
+            SELECTED_STYLE
+        } else if is_selected {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else if cat.is_priority {
+            Style::default().fg(PRIORITY_COLOR)
+        } else {
+            Style::default()
+        };
+
+        let marker = if is_selected && is_focused {
+            " ◄"
+        } else {
+            ""
+        };
+
+        // Get filtered count (respects selected_level)
+        let filtered_count = app.get_filtered_category_count(cat);
+
+        let content = Line::from(vec![
+            Span::raw(indent),
+            Span::styled(&cat.name, style),
+            Span::raw(" "),
+            Span::styled(
+                format!("({filtered_count})"),
+                Style::default().fg(COUNT_COLOR),
+            ),
+            Span::styled(marker, Style::default().fg(BRAND_ORANGE)),
+        ]);
+
+        items.push(ListItem::new(content));
+    }
+
+    // Keep the selected category visible (subtract 2 for borders)
+    let visible_rows = (area.height as usize).saturating_sub(2);
+    let scroll_offset = if selected_row >= visible_rows {
+        selected_row - visible_rows + 1
+    } else {
+        0
+    };
+    let items: Vec<ListItem> = items.into_iter().skip(scroll_offset).collect();
+
+    let border_style = if is_focused {
+        Style::default().fg(BRAND_ORANGE)
+    } else {
+        Style::default()
+    };
+
     let list = List::new(items).block(
         Block::default()
             .title(" Categories ")
@@ -331,6 +508,11 @@ pub fn draw_type_detail(frame: &mut Frame, app: &App) {
         |id| app.get_storey_name_for_instance(id),
     );
 
+    let instance_spaces = app.get_selected_instance_id().map_or_else(
+        || "-".to_string(),
+        |id| app.get_space_names_for_instance(id),
+    );
+
     let instance_id_str = app
         .get_selected_instance_id()
         .map_or_else(|| "-".to_string(), |id| format!("#{id}"));
@@ -350,7 +532,7 @@ pub fn draw_type_detail(frame: &mut Frame, app: &App) {
     };
 
     let info_text = format!(
-        "{family}  |  Level: {instance_level}  |  {instance_info}  |  GlobalId: {global_id}"
+        "{family}  |  Level: {instance_level}  |  Space: {instance_spaces}  |  {instance_info}  |  GlobalId: {global_id}"
     );
     let info_widget = Paragraph::new(info_text).block(Block::default().borders(Borders::ALL));
     frame.render_widget(info_widget, chunks[1]);